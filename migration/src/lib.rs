@@ -23,6 +23,26 @@ mod m20230323_001036_delete_planet_banned;
 mod m20230323_001050_add_member_banned;
 mod m20230413_201700_delete_component_order;
 mod m20230413_201830_add_component_position;
+mod m20261019_000001_create_audit_log;
+mod m20261019_000002_add_member_ban_details;
+mod m20261019_000003_add_user_ban_details;
+mod m20261019_000004_add_member_nickname;
+mod m20261019_000005_create_component_permission;
+mod m20261019_000006_add_member_indexes;
+mod m20261019_000007_add_member_last_active;
+mod m20261019_000008_create_join_request;
+mod m20261019_000009_add_planet_join_settings;
+mod m20261019_000010_create_auto_role;
+mod m20261019_000011_create_page;
+mod m20261019_000012_create_forum;
+mod m20261019_000013_create_chat;
+mod m20261019_000014_create_files;
+mod m20261019_000015_create_wiki;
+mod m20261019_000016_create_events;
+mod m20261019_000017_add_user_presence;
+mod m20261019_000018_create_notification;
+mod m20261019_000019_create_direct_messages;
+mod m20261019_000020_add_user_calendar_token;
 
 pub struct Migrator;
 
@@ -51,6 +71,26 @@ impl MigratorTrait for Migrator {
             Box::new(m20230323_001050_add_member_banned::Migration),
             Box::new(m20230413_201700_delete_component_order::Migration),
             Box::new(m20230413_201830_add_component_position::Migration),
+            Box::new(m20261019_000001_create_audit_log::Migration),
+            Box::new(m20261019_000002_add_member_ban_details::Migration),
+            Box::new(m20261019_000003_add_user_ban_details::Migration),
+            Box::new(m20261019_000004_add_member_nickname::Migration),
+            Box::new(m20261019_000005_create_component_permission::Migration),
+            Box::new(m20261019_000006_add_member_indexes::Migration),
+            Box::new(m20261019_000007_add_member_last_active::Migration),
+            Box::new(m20261019_000008_create_join_request::Migration),
+            Box::new(m20261019_000009_add_planet_join_settings::Migration),
+            Box::new(m20261019_000010_create_auto_role::Migration),
+            Box::new(m20261019_000011_create_page::Migration),
+            Box::new(m20261019_000012_create_forum::Migration),
+            Box::new(m20261019_000013_create_chat::Migration),
+            Box::new(m20261019_000014_create_files::Migration),
+            Box::new(m20261019_000015_create_wiki::Migration),
+            Box::new(m20261019_000016_create_events::Migration),
+            Box::new(m20261019_000017_add_user_presence::Migration),
+            Box::new(m20261019_000018_create_notification::Migration),
+            Box::new(m20261019_000019_create_direct_messages::Migration),
+            Box::new(m20261019_000020_add_user_calendar_token::Migration),
         ]
     }
 }
@@ -141,6 +141,9 @@ pub enum User {
     Sessions,
 
     Banned,
+    BanReason,
+    BanExpires,
+    BannedBy,
     Admin,
     NotificationSetting,
 
@@ -158,4 +161,9 @@ pub enum User {
     TokenGeofenced,
     TokenExpires,
     TokenIpLocked,
+
+    StatusMessage,
+    Invisible,
+
+    CalendarToken,
 }
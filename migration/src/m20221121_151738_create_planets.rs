@@ -100,4 +100,7 @@ pub enum Planet {
     Description,
 
     Home,
+
+    JoinRequestsEnabled,
+    JoinQuestion,
 }
@@ -67,4 +67,10 @@ pub enum PlanetMember {
     Permissions,
     Created,
     Banned,
+    BanReason,
+    BanExpires,
+    BannedBy,
+    BannedAt,
+    Nickname,
+    LastActive,
 }
@@ -0,0 +1,69 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221121_151738_create_planets::Planet;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLog::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(AuditLog::Id).text().not_null().primary_key())
+                    .col(ColumnDef::new(AuditLog::Planet).string().not_null())
+                    .col(ColumnDef::new(AuditLog::Actor).string())
+                    .col(ColumnDef::new(AuditLog::Action).string().not_null())
+                    .col(ColumnDef::new(AuditLog::Target).string())
+                    .col(ColumnDef::new(AuditLog::Details).text())
+                    .col(ColumnDef::new(AuditLog::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-audit-log-planet")
+                            .from(AuditLog::Table, AuditLog::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-audit-log-actor")
+                            .from(AuditLog::Table, AuditLog::Actor)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-audit-log-planet-created")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::Planet)
+                    .col(AuditLog::Created)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditLog::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum AuditLog {
+    Table,
+    Id,
+    Planet,
+    Actor,
+    Action,
+    Target,
+    Details,
+    Created,
+}
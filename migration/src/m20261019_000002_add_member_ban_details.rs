@@ -0,0 +1,55 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221122_145255_create_planet_member::PlanetMember;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PlanetMember::Table)
+                    .add_column(ColumnDef::new(PlanetMember::BanReason).string())
+                    .add_column(ColumnDef::new(PlanetMember::BanExpires).timestamp())
+                    .add_column(ColumnDef::new(PlanetMember::BannedBy).string())
+                    .add_column(ColumnDef::new(PlanetMember::BannedAt).timestamp())
+                    .add_foreign_key(
+                        ForeignKey::create()
+                            .name("fk-member-banned-by")
+                            .from(PlanetMember::Table, PlanetMember::BannedBy)
+                            .to(User::Table, User::Id)
+                            .get_foreign_key(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-planet-member-planet-banned-at")
+                    .table(PlanetMember::Table)
+                    .col(PlanetMember::Planet)
+                    .col(PlanetMember::BannedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PlanetMember::Table)
+                    .drop_column(PlanetMember::BanReason)
+                    .drop_column(PlanetMember::BanExpires)
+                    .drop_column(PlanetMember::BannedBy)
+                    .drop_column(PlanetMember::BannedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
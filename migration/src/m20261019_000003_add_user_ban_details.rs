@@ -0,0 +1,41 @@
+use super::m20221115_000001_create_users::User;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(ColumnDef::new(User::BanReason).string())
+                    .add_column(ColumnDef::new(User::BanExpires).timestamp())
+                    .add_column(ColumnDef::new(User::BannedBy).string())
+                    .add_foreign_key(
+                        ForeignKey::create()
+                            .name("fk-user-banned-by")
+                            .from(User::Table, User::BannedBy)
+                            .to(User::Table, User::Id)
+                            .get_foreign_key(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::BanReason)
+                    .drop_column(User::BanExpires)
+                    .drop_column(User::BannedBy)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,97 @@
+use super::m20221121_151738_create_planets::Planet;
+use super::m20221122_145255_create_planet_member::PlanetMember;
+use super::m20221203_221004_create_planet_component::PlanetComponent;
+use super::m20221218_002528_create_planet_role::PlanetRole;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ComponentPermission::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ComponentPermission::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ComponentPermission::Planet)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ComponentPermission::Component)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ComponentPermission::Role).text())
+                    .col(ColumnDef::new(ComponentPermission::Member).string())
+                    .col(
+                        ColumnDef::new(ComponentPermission::Permissions)
+                            .array(ColumnType::Text)
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-component-permission-planet")
+                            .from(ComponentPermission::Table, ComponentPermission::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-component-permission-component")
+                            .from(ComponentPermission::Table, ComponentPermission::Component)
+                            .to(PlanetComponent::Table, PlanetComponent::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-component-permission-role")
+                            .from(ComponentPermission::Table, ComponentPermission::Role)
+                            .to(PlanetRole::Table, PlanetRole::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-component-permission-member")
+                            .from(ComponentPermission::Table, ComponentPermission::Member)
+                            .to(PlanetMember::Table, PlanetMember::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-component-permission-component")
+                    .table(ComponentPermission::Table)
+                    .col(ComponentPermission::Component)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ComponentPermission::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum ComponentPermission {
+    Table,
+    Id,
+    Planet,
+    Component,
+    Role,
+    Member,
+    Permissions,
+}
@@ -0,0 +1,100 @@
+use super::m20221122_145255_create_planet_member::PlanetMember;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-planet-member-planet-created")
+                    .table(PlanetMember::Table)
+                    .col(PlanetMember::Planet)
+                    .col(PlanetMember::Created)
+                    .col(PlanetMember::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-planet-member-planet-banned")
+                    .table(PlanetMember::Table)
+                    .col(PlanetMember::Planet)
+                    .col(PlanetMember::Banned)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-planet-member-roles")
+                    .table(PlanetMember::Table)
+                    .col(PlanetMember::Roles)
+                    .index_type(IndexType::Custom(SeaRc::new(Alias::new("GIN"))))
+                    .to_owned(),
+            )
+            .await?;
+
+        // member searches match anywhere in usernames and nicknames, which needs trigram indexes;
+        // sea-query can't set an index's operator class, so these are created by hand
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        for sql in [
+            r#"CREATE EXTENSION IF NOT EXISTS pg_trgm;"#,
+            r#"CREATE INDEX "idx-user-username-trgm" ON "user" USING GIN ("username" gin_trgm_ops);"#,
+            r#"CREATE INDEX "idx-planet-member-nickname-trgm" ON "planet_member" USING GIN ("nickname" gin_trgm_ops);"#,
+        ] {
+            db.execute(Statement::from_string(backend, sql.to_owned())).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        // the pg_trgm extension is left installed, since other database objects may rely on it
+        for sql in [
+            r#"DROP INDEX "idx-planet-member-nickname-trgm";"#,
+            r#"DROP INDEX "idx-user-username-trgm";"#,
+        ] {
+            db.execute(Statement::from_string(backend, sql.to_owned())).await?;
+        }
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-planet-member-roles")
+                    .table(PlanetMember::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-planet-member-planet-banned")
+                    .table(PlanetMember::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-planet-member-planet-created")
+                    .table(PlanetMember::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
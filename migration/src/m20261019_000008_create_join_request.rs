@@ -0,0 +1,83 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221121_151738_create_planets::Planet;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(JoinRequest::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(JoinRequest::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(JoinRequest::Planet).string().not_null())
+                    .col(ColumnDef::new(JoinRequest::User).string().not_null())
+                    .col(ColumnDef::new(JoinRequest::Answer).text())
+                    .col(ColumnDef::new(JoinRequest::Status).string().not_null())
+                    .col(ColumnDef::new(JoinRequest::Created).timestamp().not_null())
+                    .col(ColumnDef::new(JoinRequest::ReviewedBy).string())
+                    .col(ColumnDef::new(JoinRequest::Reviewed).timestamp())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-join-request-planet")
+                            .from(JoinRequest::Table, JoinRequest::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-join-request-user")
+                            .from(JoinRequest::Table, JoinRequest::User)
+                            .to(User::Table, User::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-join-request-reviewed-by")
+                            .from(JoinRequest::Table, JoinRequest::ReviewedBy)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-join-request-planet-user")
+                    .table(JoinRequest::Table)
+                    .col(JoinRequest::Planet)
+                    .col(JoinRequest::User)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(JoinRequest::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum JoinRequest {
+    Table,
+    Id,
+    Planet,
+    User,
+    Answer,
+    Status,
+    Created,
+    ReviewedBy,
+    Reviewed,
+}
@@ -0,0 +1,37 @@
+use super::m20221121_151738_create_planets::Planet;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Planet::Table)
+                    .add_column(
+                        ColumnDef::new(Planet::JoinRequestsEnabled)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column(ColumnDef::new(Planet::JoinQuestion).string())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Planet::Table)
+                    .drop_column(Planet::JoinRequestsEnabled)
+                    .drop_column(Planet::JoinQuestion)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,157 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221121_151738_create_planets::Planet;
+use super::m20221122_145255_create_planet_member::PlanetMember;
+use super::m20221218_002528_create_planet_role::PlanetRole;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AutoRole::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(AutoRole::Id).text().not_null().primary_key())
+                    .col(ColumnDef::new(AutoRole::Planet).string().not_null())
+                    .col(ColumnDef::new(AutoRole::Role).text().not_null())
+                    .col(ColumnDef::new(AutoRole::Trigger).string().not_null())
+                    .col(ColumnDef::new(AutoRole::Days).integer())
+                    .col(ColumnDef::new(AutoRole::CreatedBy).string().not_null())
+                    .col(ColumnDef::new(AutoRole::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-auto-role-planet")
+                            .from(AutoRole::Table, AutoRole::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-auto-role-role")
+                            .from(AutoRole::Table, AutoRole::Role)
+                            .to(PlanetRole::Table, PlanetRole::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-auto-role-created-by")
+                            .from(AutoRole::Table, AutoRole::CreatedBy)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-auto-role-planet")
+                    .table(AutoRole::Table)
+                    .col(AutoRole::Planet)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AutoRoleAssignment::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(AutoRoleAssignment::Rule).text().not_null())
+                    .col(
+                        ColumnDef::new(AutoRoleAssignment::Member)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AutoRoleAssignment::Planet)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AutoRoleAssignment::Created)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(AutoRoleAssignment::Rule)
+                            .col(AutoRoleAssignment::Member),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-auto-role-assignment-rule")
+                            .from(AutoRoleAssignment::Table, AutoRoleAssignment::Rule)
+                            .to(AutoRole::Table, AutoRole::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-auto-role-assignment-member")
+                            .from(AutoRoleAssignment::Table, AutoRoleAssignment::Member)
+                            .to(PlanetMember::Table, PlanetMember::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-auto-role-assignment-planet")
+                            .from(AutoRoleAssignment::Table, AutoRoleAssignment::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-auto-role-assignment-member")
+                    .table(AutoRoleAssignment::Table)
+                    .col(AutoRoleAssignment::Member)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-auto-role-assignment-planet")
+                    .table(AutoRoleAssignment::Table)
+                    .col(AutoRoleAssignment::Planet)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AutoRoleAssignment::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(AutoRole::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum AutoRole {
+    Table,
+    Id,
+    Planet,
+    Role,
+    Trigger,
+    Days,
+    CreatedBy,
+    Created,
+}
+
+#[derive(Iden)]
+pub enum AutoRoleAssignment {
+    Table,
+    Rule,
+    Member,
+    Planet,
+    Created,
+}
@@ -0,0 +1,56 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221121_151738_create_planets::Planet;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Page::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Page::Id).text().not_null().primary_key())
+                    .col(ColumnDef::new(Page::Planet).string().not_null())
+                    .col(ColumnDef::new(Page::Content).text().not_null())
+                    .col(ColumnDef::new(Page::Created).timestamp().not_null())
+                    .col(ColumnDef::new(Page::Updated).timestamp().not_null())
+                    .col(ColumnDef::new(Page::UpdatedBy).string())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-page-planet")
+                            .from(Page::Table, Page::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-page-updated-by")
+                            .from(Page::Table, Page::UpdatedBy)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Page::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum Page {
+    Table,
+    Id,
+    Planet,
+    Content,
+    Created,
+    Updated,
+    UpdatedBy,
+}
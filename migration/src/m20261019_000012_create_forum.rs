@@ -0,0 +1,307 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221121_151738_create_planets::Planet;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Forum::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Forum::Id).text().not_null().primary_key())
+                    .col(ColumnDef::new(Forum::Planet).string().not_null())
+                    .col(ColumnDef::new(Forum::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-forum-planet")
+                            .from(Forum::Table, Forum::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ForumTag::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ForumTag::Id).text().not_null().primary_key())
+                    .col(ColumnDef::new(ForumTag::Forum).text().not_null())
+                    .col(ColumnDef::new(ForumTag::Name).string().not_null())
+                    .col(ColumnDef::new(ForumTag::Color).string().not_null())
+                    .col(ColumnDef::new(ForumTag::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-forum-tag-forum")
+                            .from(ForumTag::Table, ForumTag::Forum)
+                            .to(Forum::Table, Forum::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ForumPost::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ForumPost::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ForumPost::Forum).text().not_null())
+                    .col(ColumnDef::new(ForumPost::Author).string().not_null())
+                    .col(ColumnDef::new(ForumPost::Title).string().not_null())
+                    .col(ColumnDef::new(ForumPost::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(ForumPost::Tags)
+                            .array(ColumnType::Text)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ForumPost::Attachments)
+                            .array(ColumnType::Text)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ForumPost::Sticky)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(ForumPost::Locked)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(ForumPost::ReplyCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(ForumPost::Created).timestamp().not_null())
+                    .col(ColumnDef::new(ForumPost::Edited).timestamp())
+                    .col(
+                        ColumnDef::new(ForumPost::LastActivity)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-forum-post-forum")
+                            .from(ForumPost::Table, ForumPost::Forum)
+                            .to(Forum::Table, Forum::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-forum-post-author")
+                            .from(ForumPost::Table, ForumPost::Author)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-forum-post-forum-sticky-last-activity")
+                    .table(ForumPost::Table)
+                    .col(ForumPost::Forum)
+                    .col(ForumPost::Sticky)
+                    .col(ForumPost::LastActivity)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ForumReply::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ForumReply::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ForumReply::Post).text().not_null())
+                    .col(ColumnDef::new(ForumReply::Author).string().not_null())
+                    .col(ColumnDef::new(ForumReply::Content).text().not_null())
+                    .col(ColumnDef::new(ForumReply::Created).timestamp().not_null())
+                    .col(ColumnDef::new(ForumReply::Edited).timestamp())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-forum-reply-post")
+                            .from(ForumReply::Table, ForumReply::Post)
+                            .to(ForumPost::Table, ForumPost::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-forum-reply-author")
+                            .from(ForumReply::Table, ForumReply::Author)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-forum-reply-post-created")
+                    .table(ForumReply::Table)
+                    .col(ForumReply::Post)
+                    .col(ForumReply::Created)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ForumReaction::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ForumReaction::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ForumReaction::Post).text().not_null())
+                    .col(ColumnDef::new(ForumReaction::Reply).text())
+                    .col(ColumnDef::new(ForumReaction::User).string().not_null())
+                    .col(ColumnDef::new(ForumReaction::Emoji).string().not_null())
+                    .col(
+                        ColumnDef::new(ForumReaction::Created)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-forum-reaction-post")
+                            .from(ForumReaction::Table, ForumReaction::Post)
+                            .to(ForumPost::Table, ForumPost::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-forum-reaction-reply")
+                            .from(ForumReaction::Table, ForumReaction::Reply)
+                            .to(ForumReply::Table, ForumReply::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-forum-reaction-user")
+                            .from(ForumReaction::Table, ForumReaction::User)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-forum-reaction-post")
+                    .table(ForumReaction::Table)
+                    .col(ForumReaction::Post)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ForumReaction::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ForumReply::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ForumPost::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ForumTag::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Forum::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum Forum {
+    Table,
+    Id,
+    Planet,
+    Created,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum ForumTag {
+    Table,
+    Id,
+    Forum,
+    Name,
+    Color,
+    Created,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum ForumPost {
+    Table,
+    Id,
+    Forum,
+    Author,
+    Title,
+    Content,
+    Tags,
+    Attachments,
+    Sticky,
+    Locked,
+    ReplyCount,
+    Created,
+    Edited,
+    LastActivity,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum ForumReply {
+    Table,
+    Id,
+    Post,
+    Author,
+    Content,
+    Created,
+    Edited,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum ForumReaction {
+    Table,
+    Id,
+    Post,
+    Reply,
+    User,
+    Emoji,
+    Created,
+}
@@ -0,0 +1,174 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221121_151738_create_planets::Planet;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Chat::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Chat::Id).text().not_null().primary_key())
+                    .col(ColumnDef::new(Chat::Planet).string().not_null())
+                    .col(ColumnDef::new(Chat::Topic).string())
+                    .col(ColumnDef::new(Chat::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-chat-planet")
+                            .from(Chat::Table, Chat::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ChatMessage::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ChatMessage::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ChatMessage::Chat).text().not_null())
+                    .col(ColumnDef::new(ChatMessage::Author).string().not_null())
+                    .col(ColumnDef::new(ChatMessage::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(ChatMessage::Attachments)
+                            .array(ColumnType::Text)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ChatMessage::Pinned)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(ChatMessage::Created).timestamp().not_null())
+                    .col(ColumnDef::new(ChatMessage::Edited).timestamp())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-chat-message-chat")
+                            .from(ChatMessage::Table, ChatMessage::Chat)
+                            .to(Chat::Table, Chat::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-chat-message-author")
+                            .from(ChatMessage::Table, ChatMessage::Author)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-chat-message-chat-created")
+                    .table(ChatMessage::Table)
+                    .col(ChatMessage::Chat)
+                    .col(ChatMessage::Created)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ChatReaction::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ChatReaction::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ChatReaction::Message).text().not_null())
+                    .col(ColumnDef::new(ChatReaction::User).string().not_null())
+                    .col(ColumnDef::new(ChatReaction::Emoji).string().not_null())
+                    .col(ColumnDef::new(ChatReaction::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-chat-reaction-message")
+                            .from(ChatReaction::Table, ChatReaction::Message)
+                            .to(ChatMessage::Table, ChatMessage::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-chat-reaction-user")
+                            .from(ChatReaction::Table, ChatReaction::User)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-chat-reaction-message")
+                    .table(ChatReaction::Table)
+                    .col(ChatReaction::Message)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ChatReaction::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ChatMessage::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Chat::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum Chat {
+    Table,
+    Id,
+    Planet,
+    Topic,
+    Created,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum ChatMessage {
+    Table,
+    Id,
+    Chat,
+    Author,
+    Content,
+    Attachments,
+    Pinned,
+    Created,
+    Edited,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum ChatReaction {
+    Table,
+    Id,
+    Message,
+    User,
+    Emoji,
+    Created,
+}
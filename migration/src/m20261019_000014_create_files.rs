@@ -0,0 +1,173 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221121_151738_create_planets::Planet;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FileTree::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(FileTree::Id).text().not_null().primary_key())
+                    .col(ColumnDef::new(FileTree::Planet).string().not_null())
+                    .col(ColumnDef::new(FileTree::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-file-tree-planet")
+                            .from(FileTree::Table, FileTree::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(FileFolder::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FileFolder::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FileFolder::Tree).text().not_null())
+                    .col(ColumnDef::new(FileFolder::Parent).text())
+                    .col(ColumnDef::new(FileFolder::Owner).string().not_null())
+                    .col(ColumnDef::new(FileFolder::Name).string().not_null())
+                    .col(ColumnDef::new(FileFolder::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-file-folder-tree")
+                            .from(FileFolder::Table, FileFolder::Tree)
+                            .to(FileTree::Table, FileTree::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-file-folder-parent")
+                            .from(FileFolder::Table, FileFolder::Parent)
+                            .to(FileFolder::Table, FileFolder::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-file-folder-owner")
+                            .from(FileFolder::Table, FileFolder::Owner)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-file-folder-tree-parent")
+                    .table(FileFolder::Table)
+                    .col(FileFolder::Tree)
+                    .col(FileFolder::Parent)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(File::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(File::Id).text().not_null().primary_key())
+                    .col(ColumnDef::new(File::Tree).text().not_null())
+                    .col(ColumnDef::new(File::Folder).text())
+                    .col(ColumnDef::new(File::Owner).string().not_null())
+                    .col(ColumnDef::new(File::Name).string().not_null())
+                    .col(ColumnDef::new(File::Size).big_integer().not_null())
+                    .col(ColumnDef::new(File::MimeType).string().not_null())
+                    .col(ColumnDef::new(File::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-file-tree")
+                            .from(File::Table, File::Tree)
+                            .to(FileTree::Table, FileTree::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-file-folder")
+                            .from(File::Table, File::Folder)
+                            .to(FileFolder::Table, FileFolder::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-file-owner")
+                            .from(File::Table, File::Owner)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-file-tree-folder")
+                    .table(File::Table)
+                    .col(File::Tree)
+                    .col(File::Folder)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(File::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(FileFolder::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(FileTree::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum FileTree {
+    Table,
+    Id,
+    Planet,
+    Created,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum FileFolder {
+    Table,
+    Id,
+    Tree,
+    Parent,
+    Owner,
+    Name,
+    Created,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum File {
+    Table,
+    Id,
+    Tree,
+    Folder,
+    Owner,
+    Name,
+    Size,
+    MimeType,
+    Created,
+}
@@ -0,0 +1,180 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221121_151738_create_planets::Planet;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Wiki::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Wiki::Id).text().not_null().primary_key())
+                    .col(ColumnDef::new(Wiki::Planet).string().not_null())
+                    .col(ColumnDef::new(Wiki::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-wiki-planet")
+                            .from(Wiki::Table, Wiki::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(WikiArticle::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WikiArticle::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WikiArticle::Wiki).text().not_null())
+                    .col(ColumnDef::new(WikiArticle::Title).string().not_null())
+                    .col(ColumnDef::new(WikiArticle::Slug).string().not_null())
+                    .col(ColumnDef::new(WikiArticle::Content).text().not_null())
+                    .col(ColumnDef::new(WikiArticle::Revision).integer().not_null())
+                    .col(
+                        ColumnDef::new(WikiArticle::Links)
+                            .array(ColumnType::Text)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WikiArticle::Created).timestamp().not_null())
+                    .col(ColumnDef::new(WikiArticle::Updated).timestamp().not_null())
+                    .col(ColumnDef::new(WikiArticle::UpdatedBy).string())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-wiki-article-wiki")
+                            .from(WikiArticle::Table, WikiArticle::Wiki)
+                            .to(Wiki::Table, Wiki::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-wiki-article-updated-by")
+                            .from(WikiArticle::Table, WikiArticle::UpdatedBy)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-wiki-article-wiki-slug")
+                    .table(WikiArticle::Table)
+                    .col(WikiArticle::Wiki)
+                    .col(WikiArticle::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(WikiRevision::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WikiRevision::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WikiRevision::Article).text().not_null())
+                    .col(ColumnDef::new(WikiRevision::Number).integer().not_null())
+                    .col(ColumnDef::new(WikiRevision::Title).string().not_null())
+                    .col(ColumnDef::new(WikiRevision::Content).text().not_null())
+                    .col(ColumnDef::new(WikiRevision::Summary).string())
+                    .col(ColumnDef::new(WikiRevision::Author).string())
+                    .col(ColumnDef::new(WikiRevision::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-wiki-revision-article")
+                            .from(WikiRevision::Table, WikiRevision::Article)
+                            .to(WikiArticle::Table, WikiArticle::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-wiki-revision-author")
+                            .from(WikiRevision::Table, WikiRevision::Author)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-wiki-revision-article-number")
+                    .table(WikiRevision::Table)
+                    .col(WikiRevision::Article)
+                    .col(WikiRevision::Number)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WikiRevision::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(WikiArticle::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Wiki::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum Wiki {
+    Table,
+    Id,
+    Planet,
+    Created,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum WikiArticle {
+    Table,
+    Id,
+    Wiki,
+    Title,
+    Slug,
+    Content,
+    Revision,
+    Links,
+    Created,
+    Updated,
+    UpdatedBy,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum WikiRevision {
+    Table,
+    Id,
+    Article,
+    Number,
+    Title,
+    Content,
+    Summary,
+    Author,
+    Created,
+}
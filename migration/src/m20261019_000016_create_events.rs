@@ -0,0 +1,177 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221121_151738_create_planets::Planet;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(EventCalendar::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EventCalendar::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(EventCalendar::Planet).string().not_null())
+                    .col(
+                        ColumnDef::new(EventCalendar::Created)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-event-calendar-planet")
+                            .from(EventCalendar::Table, EventCalendar::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Event::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Event::Id).text().not_null().primary_key())
+                    .col(ColumnDef::new(Event::Calendar).text().not_null())
+                    .col(ColumnDef::new(Event::Title).string().not_null())
+                    .col(ColumnDef::new(Event::Description).text())
+                    .col(ColumnDef::new(Event::Location).string())
+                    .col(ColumnDef::new(Event::Start).timestamp().not_null())
+                    .col(ColumnDef::new(Event::End).timestamp().not_null())
+                    .col(ColumnDef::new(Event::Timezone).string().not_null())
+                    .col(ColumnDef::new(Event::Recurrence).string())
+                    .col(
+                        ColumnDef::new(Event::RecurrenceInterval)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(ColumnDef::new(Event::RecurrenceCount).integer())
+                    .col(ColumnDef::new(Event::RecurrenceUntil).timestamp())
+                    .col(ColumnDef::new(Event::Creator).string().not_null())
+                    .col(ColumnDef::new(Event::Created).timestamp().not_null())
+                    .col(ColumnDef::new(Event::Updated).timestamp())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-event-calendar")
+                            .from(Event::Table, Event::Calendar)
+                            .to(EventCalendar::Table, EventCalendar::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-event-creator")
+                            .from(Event::Table, Event::Creator)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(EventRsvp::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EventRsvp::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(EventRsvp::Event).text().not_null())
+                    .col(ColumnDef::new(EventRsvp::User).string().not_null())
+                    .col(ColumnDef::new(EventRsvp::Status).string().not_null())
+                    .col(ColumnDef::new(EventRsvp::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-event-rsvp-event")
+                            .from(EventRsvp::Table, EventRsvp::Event)
+                            .to(Event::Table, Event::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-event-rsvp-user")
+                            .from(EventRsvp::Table, EventRsvp::User)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-event-rsvp-event-user")
+                    .table(EventRsvp::Table)
+                    .col(EventRsvp::Event)
+                    .col(EventRsvp::User)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(EventRsvp::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Event::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(EventCalendar::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum EventCalendar {
+    Table,
+    Id,
+    Planet,
+    Created,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum Event {
+    Table,
+    Id,
+    Calendar,
+    Title,
+    Description,
+    Location,
+    Start,
+    End,
+    Timezone,
+    Recurrence,
+    RecurrenceInterval,
+    RecurrenceCount,
+    RecurrenceUntil,
+    Creator,
+    Created,
+    Updated,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum EventRsvp {
+    Table,
+    Id,
+    Event,
+    User,
+    Status,
+    Created,
+}
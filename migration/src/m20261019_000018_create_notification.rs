@@ -0,0 +1,103 @@
+use super::m20221115_000001_create_users::User;
+use super::m20221121_151738_create_planets::Planet;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notification::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Notification::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Notification::User).string().not_null())
+                    .col(ColumnDef::new(Notification::Kind).string().not_null())
+                    .col(ColumnDef::new(Notification::Planet).string())
+                    .col(ColumnDef::new(Notification::Component).string())
+                    .col(ColumnDef::new(Notification::Target).string())
+                    .col(ColumnDef::new(Notification::Actor).string())
+                    .col(ColumnDef::new(Notification::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(Notification::Read)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(Notification::Created).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-notification-user")
+                            .from(Notification::Table, Notification::User)
+                            .to(User::Table, User::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-notification-planet")
+                            .from(Notification::Table, Notification::Planet)
+                            .to(Planet::Table, Planet::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-notification-actor")
+                            .from(Notification::Table, Notification::Actor)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-notification-user-created")
+                    .table(Notification::Table)
+                    .col(Notification::User)
+                    .col(Notification::Created)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-notification-user-planet-read")
+                    .table(Notification::Table)
+                    .col(Notification::User)
+                    .col(Notification::Planet)
+                    .col(Notification::Read)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Notification::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum Notification {
+    Table,
+    Id,
+    User,
+    Kind,
+    Planet,
+    Component,
+    Target,
+    Actor,
+    Content,
+    Read,
+    Created,
+}
@@ -0,0 +1,200 @@
+use super::m20221115_000001_create_users::User;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Conversation::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Conversation::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Conversation::Name).string())
+                    .col(
+                        ColumnDef::new(Conversation::Group)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(Conversation::Creator).string().not_null())
+                    .col(ColumnDef::new(Conversation::Created).timestamp().not_null())
+                    .col(ColumnDef::new(Conversation::Updated).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-conversation-creator")
+                            .from(Conversation::Table, Conversation::Creator)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ConversationMember::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ConversationMember::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ConversationMember::Conversation)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ConversationMember::User).string().not_null())
+                    .col(
+                        ColumnDef::new(ConversationMember::Joined)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-conversation-member-conversation")
+                            .from(ConversationMember::Table, ConversationMember::Conversation)
+                            .to(Conversation::Table, Conversation::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-conversation-member-user")
+                            .from(ConversationMember::Table, ConversationMember::User)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-conversation-member-conversation-user")
+                    .table(ConversationMember::Table)
+                    .col(ConversationMember::Conversation)
+                    .col(ConversationMember::User)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-conversation-member-user")
+                    .table(ConversationMember::Table)
+                    .col(ConversationMember::User)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(DirectMessage::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DirectMessage::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DirectMessage::Conversation)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DirectMessage::Author).string().not_null())
+                    .col(ColumnDef::new(DirectMessage::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(DirectMessage::Created)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DirectMessage::Edited).timestamp())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-direct-message-conversation")
+                            .from(DirectMessage::Table, DirectMessage::Conversation)
+                            .to(Conversation::Table, Conversation::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-direct-message-author")
+                            .from(DirectMessage::Table, DirectMessage::Author)
+                            .to(User::Table, User::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-direct-message-conversation-created")
+                    .table(DirectMessage::Table)
+                    .col(DirectMessage::Conversation)
+                    .col(DirectMessage::Created)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DirectMessage::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ConversationMember::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Conversation::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum Conversation {
+    Table,
+    Id,
+    Name,
+    Group,
+    Creator,
+    Created,
+    Updated,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum ConversationMember {
+    Table,
+    Id,
+    Conversation,
+    User,
+    Joined,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum DirectMessage {
+    Table,
+    Id,
+    Conversation,
+    Author,
+    Content,
+    Created,
+    Edited,
+}
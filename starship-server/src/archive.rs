@@ -0,0 +1,100 @@
+//! Builds ZIP archives, such as when downloading a folder of files. Entries are stored without
+//! compression, since most uploads (images, videos, documents) are already compressed.
+
+use chrono::{Datelike, NaiveDateTime, Timelike};
+
+/// The most entries an archive can hold without the ZIP64 extensions.
+pub const MAX_ENTRIES: usize = u16::MAX as usize;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_SIGNATURE: u32 = 0x0605_4b50;
+
+/// The version of the format needed to extract the entries.
+const VERSION: u16 = 20;
+
+/// Marks the entry names as UTF-8.
+const UTF8_FLAG: u16 = 1 << 11;
+
+/// A file or folder to put in an archive. Folders have a path ending in `/` and no contents.
+pub struct ArchiveEntry {
+    pub path: String,
+    pub contents: Vec<u8>,
+    pub modified: NaiveDateTime,
+}
+
+/// Converts a time to the MS-DOS time and date stored in ZIP archives. Times before 1980 can't be
+/// represented, so they are clamped to the start of 1980.
+pub fn dos_time(time: NaiveDateTime) -> (u16, u16) {
+    if time.year() < 1980 {
+        return (0, (1 << 5) | 1);
+    }
+
+    let dos_time = (time.hour() << 11) | (time.minute() << 5) | (time.second() / 2);
+    let dos_date = ((time.year() as u32 - 1980) << 9) | (time.month() << 5) | time.day();
+
+    (dos_time as u16, dos_date as u16)
+}
+
+/// Builds a ZIP archive from a list of entries. There can be at most [`MAX_ENTRIES`] entries, and
+/// the archive must be smaller than 4 GB, which the caller is expected to check.
+pub fn zip(entries: &[ArchiveEntry]) -> Vec<u8> {
+    let mut archive = vec![];
+    let mut central_directory = vec![];
+
+    for entry in entries {
+        let offset = archive.len() as u32;
+        let name = entry.path.as_bytes();
+        let crc = crc32fast::hash(&entry.contents);
+        let size = entry.contents.len() as u32;
+        let (time, date) = dos_time(entry.modified);
+
+        archive.extend_from_slice(&LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        archive.extend_from_slice(&VERSION.to_le_bytes());
+        archive.extend_from_slice(&UTF8_FLAG.to_le_bytes());
+        // stored, without compression
+        archive.extend_from_slice(&0u16.to_le_bytes());
+        archive.extend_from_slice(&time.to_le_bytes());
+        archive.extend_from_slice(&date.to_le_bytes());
+        archive.extend_from_slice(&crc.to_le_bytes());
+        archive.extend_from_slice(&size.to_le_bytes());
+        archive.extend_from_slice(&size.to_le_bytes());
+        archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes());
+        archive.extend_from_slice(name);
+        archive.extend_from_slice(&entry.contents);
+
+        central_directory.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        central_directory.extend_from_slice(&VERSION.to_le_bytes());
+        central_directory.extend_from_slice(&VERSION.to_le_bytes());
+        central_directory.extend_from_slice(&UTF8_FLAG.to_le_bytes());
+        central_directory.extend_from_slice(&0u16.to_le_bytes());
+        central_directory.extend_from_slice(&time.to_le_bytes());
+        central_directory.extend_from_slice(&date.to_le_bytes());
+        central_directory.extend_from_slice(&crc.to_le_bytes());
+        central_directory.extend_from_slice(&size.to_le_bytes());
+        central_directory.extend_from_slice(&size.to_le_bytes());
+        central_directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        // extra field, comment, disk number, internal and external attributes
+        central_directory.extend_from_slice(&[0; 12]);
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(name);
+    }
+
+    let central_directory_offset = archive.len() as u32;
+    let central_directory_size = central_directory.len() as u32;
+    let count = entries.len() as u16;
+
+    archive.extend_from_slice(&central_directory);
+    archive.extend_from_slice(&END_SIGNATURE.to_le_bytes());
+    // this disk and the disk the central directory starts on
+    archive.extend_from_slice(&[0; 4]);
+    archive.extend_from_slice(&count.to_le_bytes());
+    archive.extend_from_slice(&count.to_le_bytes());
+    archive.extend_from_slice(&central_directory_size.to_le_bytes());
+    archive.extend_from_slice(&central_directory_offset.to_le_bytes());
+    // comment length
+    archive.extend_from_slice(&0u16.to_le_bytes());
+
+    archive
+}
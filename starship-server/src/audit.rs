@@ -0,0 +1,60 @@
+use crate::entities::audit_log::{self, AuditAction};
+use crate::errors;
+use async_graphql::Error;
+use nanoid::nanoid;
+use sea_orm::{
+    ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Select,
+};
+
+/// Records an action in a planet's audit log. `actor` is the ID of the user performing the action,
+/// or `None` if the action was performed by the server itself.
+///
+/// This accepts any connection so that entries can be written as part of a transaction.
+pub async fn log_action<C: ConnectionTrait>(
+    db: &C,
+    planet: String,
+    actor: Option<String>,
+    action: AuditAction,
+    target: Option<String>,
+    details: Option<String>,
+) -> Result<(), Error> {
+    let entry = audit_log::ActiveModel {
+        id: ActiveValue::Set(nanoid!(16)),
+        planet: ActiveValue::Set(planet),
+        actor: ActiveValue::Set(actor),
+        action: ActiveValue::Set(action),
+        target: ActiveValue::Set(target),
+        details: ActiveValue::Set(details),
+        created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+    };
+
+    audit_log::Entity::insert(entry)
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "AUDIT_LOG_INSERTION_ERROR"))?;
+
+    Ok(())
+}
+
+/// Narrows a query for audit log entries down to the ones with an action, actor and target, if
+/// they are given, newest entries first.
+pub fn filter_entries(
+    mut query: Select<audit_log::Entity>,
+    action: Option<AuditAction>,
+    actor: Option<String>,
+    target: Option<String>,
+) -> Select<audit_log::Entity> {
+    if let Some(action) = action {
+        query = query.filter(audit_log::Column::Action.eq(action));
+    }
+
+    if let Some(actor) = actor {
+        query = query.filter(audit_log::Column::Actor.eq(actor));
+    }
+
+    if let Some(target) = target {
+        query = query.filter(audit_log::Column::Target.eq(target));
+    }
+
+    query.order_by_desc(audit_log::Column::Created)
+}
@@ -0,0 +1,118 @@
+use crate::entities::{component_permission, planet, planet_component, planet_member, planet_role};
+use crate::errors;
+use crate::permissions::{checks, util};
+use async_graphql::Error;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+
+/// A component, along with everything needed to check the current user's permissions within it.
+pub struct ComponentAccess {
+    pub component: planet_component::Model,
+    pub planet: planet::Model,
+    pub member: Option<planet_member::Model>,
+    pub roles: Option<Vec<planet_role::Model>>,
+    pub overrides: Vec<component_permission::Model>,
+    /// The ID of the current user, if they are logged in.
+    pub user_id: Option<String>,
+}
+
+impl ComponentAccess {
+    /// Loads a component of a specific type from its ID. If the component doesn't exist or is of
+    /// another type, a not found error is returned.
+    pub async fn load<C: ConnectionTrait>(
+        component_id: String,
+        component_type: &str,
+        user_id: Option<String>,
+        db: &C,
+    ) -> Result<Self, Error> {
+        let component = planet_component::Entity::find_by_id(component_id)
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "RETRIEVAL_ERROR"))?
+            .filter(|component| component.r#type == component_type)
+            .ok_or(errors::create_not_found_error())?;
+
+        Self::from_component(component, user_id, db).await
+    }
+
+    /// Loads the component of a specific type that owns some component data, such as a forum.
+    pub async fn load_by_data<C: ConnectionTrait>(
+        data_id: String,
+        component_type: &str,
+        user_id: Option<String>,
+        db: &C,
+    ) -> Result<Self, Error> {
+        let component = planet_component::Entity::find()
+            .filter(
+                planet_component::Column::ComponentId
+                    .eq(data_id)
+                    .and(planet_component::Column::Type.eq(component_type)),
+            )
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_not_found_error())?;
+
+        Self::from_component(component, user_id, db).await
+    }
+
+    async fn from_component<C: ConnectionTrait>(
+        component: planet_component::Model,
+        user_id: Option<String>,
+        db: &C,
+    ) -> Result<Self, Error> {
+        let planet = util::get_planet(component.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), component.planet.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        let overrides = util::get_component_overrides(component.id.clone(), db).await?;
+
+        Ok(ComponentAccess {
+            component,
+            planet,
+            member,
+            roles,
+            overrides,
+            user_id,
+        })
+    }
+
+    /// Checks whether or not the current user holds a permission within the component.
+    pub fn has_permission(&self, permission: &str) -> bool {
+        checks::has_permission(
+            permission,
+            &self.planet,
+            self.member.clone(),
+            self.roles.clone(),
+            Some(&self.overrides),
+        )
+    }
+
+    /// Checks for a permission within the component and returns an error if it isn't held.
+    pub fn check_permission(&self, permission: &str) -> Result<(), Error> {
+        util::check_component_permission(
+            permission,
+            &self.planet,
+            self.member.clone(),
+            self.roles.clone(),
+            &self.overrides,
+        )
+    }
+
+    /// Records that the current user did something in the component, such as posting a message.
+    /// See `util::record_activity`.
+    pub async fn record_activity<C: ConnectionTrait>(&self, db: &C) -> Result<(), Error> {
+        match &self.member {
+            Some(member) => util::record_activity(member, db).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Checks for the `.own` variant of a permission if the current user is `owner`, and the
+    /// `.others` variant otherwise.
+    pub fn check_ownership_permission(&self, permission: &str, owner: &str) -> Result<(), Error> {
+        self.check_permission(&checks::ownership_permission(
+            permission,
+            owner,
+            self.user_id.as_deref(),
+        ))
+    }
+}
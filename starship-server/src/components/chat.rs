@@ -0,0 +1,254 @@
+use super::access::ComponentAccess;
+use super::component::{Cleanup, Component};
+use super::forum::ReactionCount;
+use crate::entities::graphql::member_connection::escape_like;
+use crate::entities::{chat, chat_message, chat_reaction};
+use crate::errors;
+use crate::permissions::constants::PermissionCategory;
+use async_graphql::Error;
+use async_trait::async_trait;
+use nanoid::nanoid;
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, ModelTrait,
+    QueryFilter, QueryOrder, Select,
+};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+/// The largest number of files that can be attached to a message.
+const MAX_MESSAGE_ATTACHMENTS: usize = 10;
+
+/// A chat channel where members can send messages to each other.
+pub struct ChatComponent;
+
+/// Checks that the current user can send a message with `attachments` to a chat. Attachments
+/// require the `chat.messages.attach` permission. If the message can't be sent, an error ready for
+/// presentation to the client is returned.
+pub fn check_can_send(access: &ComponentAccess, attachments: &[String]) -> Result<(), Error> {
+    access.check_permission("chat.view")?;
+    access.check_permission("chat.messages.send")?;
+
+    if !attachments.is_empty() {
+        access.check_permission("chat.messages.attach")?;
+        validate_attachments(attachments)?;
+    }
+
+    Ok(())
+}
+
+/// Builds the query searching a chat's messages for some text, ignoring case, with the most recent
+/// messages first. Requires the `chat.search` permission. Empty searches match nothing, so no
+/// query is returned for them.
+pub fn search_query(
+    access: &ComponentAccess,
+    chat_id: &str,
+    query: &str,
+) -> Result<Option<Select<chat_message::Entity>>, Error> {
+    access.check_permission("chat.search")?;
+
+    if query.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let pattern = format!("%{}%", escape_like(query.trim()));
+
+    Ok(Some(
+        chat_message::Entity::find()
+            .filter(chat_message::Column::Chat.eq(chat_id))
+            .filter(Expr::cust_with_values(
+                r#""chat_message"."content" ILIKE $1"#,
+                [pattern],
+            ))
+            .order_by_desc(chat_message::Column::Created)
+            .order_by_desc(chat_message::Column::Id),
+    ))
+}
+
+/// Checks that the content of a message is valid. If it isn't, an error ready for presentation to
+/// the client is returned.
+pub fn validate_content(content: &str, has_attachments: bool) -> Result<(), Error> {
+    if content.trim().is_empty() && !has_attachments {
+        return Err(errors::create_user_input_error(
+            "Messages cannot be empty.",
+            "CONTENT_EMPTY",
+        ));
+    }
+
+    if content.len() > 4000 {
+        return Err(errors::create_user_input_error(
+            "Messages cannot be longer than 4000 characters.",
+            "CONTENT_TOO_LONG",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that a list of attachments is valid. Attachments are links to files. If they aren't
+/// valid, an error ready for presentation to the client is returned.
+pub fn validate_attachments(attachments: &[String]) -> Result<(), Error> {
+    if attachments.len() > MAX_MESSAGE_ATTACHMENTS {
+        return Err(errors::create_user_input_error(
+            "Messages cannot have more than 10 attachments.",
+            "TOO_MANY_ATTACHMENTS",
+        ));
+    }
+
+    for attachment in attachments {
+        if attachment.len() > 2048
+            || !(attachment.starts_with("https://") || attachment.starts_with("http://"))
+        {
+            return Err(errors::create_user_input_error(
+                "Attachments must be links to files.",
+                "INVALID_ATTACHMENT",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that a topic is valid, returning it trimmed, or `None` if it is empty. If it isn't
+/// valid, an error ready for presentation to the client is returned.
+pub fn validate_topic(topic: Option<String>) -> Result<Option<String>, Error> {
+    let topic = topic
+        .map(|topic| topic.trim().to_string())
+        .filter(|topic| !topic.is_empty());
+
+    if topic.as_ref().is_some_and(|topic| topic.len() > 512) {
+        return Err(errors::create_user_input_error(
+            "Topics cannot be longer than 512 characters.",
+            "TOPIC_TOO_LONG",
+        ));
+    }
+
+    Ok(topic)
+}
+
+/// Counts the reactions to a message.
+pub async fn reaction_counts(
+    message: &str,
+    user_id: Option<&str>,
+    db: &DatabaseConnection,
+) -> Result<Vec<ReactionCount>, Error> {
+    let reactions = chat_reaction::Entity::find()
+        .filter(chat_reaction::Column::Message.eq(message))
+        .order_by_asc(chat_reaction::Column::Created)
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_REACTIONS_ERROR"))?;
+
+    let mut counts: BTreeMap<String, ReactionCount> = BTreeMap::new();
+
+    for reaction in reactions {
+        let count = counts
+            .entry(reaction.emoji.clone())
+            .or_insert(ReactionCount {
+                emoji: reaction.emoji,
+                count: 0,
+                reacted: false,
+            });
+
+        count.count += 1;
+        count.reacted |= user_id == Some(reaction.user.as_str());
+    }
+
+    Ok(counts.into_values().collect())
+}
+
+#[async_trait]
+impl Component for ChatComponent {
+    fn name(&self) -> &'static str {
+        "chat"
+    }
+
+    fn permission_category(&self) -> Option<PermissionCategory> {
+        Some(PermissionCategory::Chat)
+    }
+
+    async fn create(
+        &self,
+        db: &DatabaseTransaction,
+        planet: &str,
+        _owner: &str,
+    ) -> Result<String, Error> {
+        let chat = chat::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            planet: ActiveValue::Set(planet.to_string()),
+            topic: ActiveValue::Set(None),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        let insertion = chat::Entity::insert(chat)
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "CHAT_INSERTION_ERROR"))?;
+
+        Ok(insertion.last_insert_id)
+    }
+
+    async fn delete(&self, db: &DatabaseTransaction, id: &str) -> Result<Option<Cleanup>, Error> {
+        let messages = Query::select()
+            .column(chat_message::Column::Id)
+            .from(chat_message::Entity)
+            .and_where(chat_message::Column::Chat.eq(id))
+            .to_owned();
+
+        chat_reaction::Entity::delete_many()
+            .filter(chat_reaction::Column::Message.in_subquery(messages))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REACTIONS_ERROR"))?;
+
+        chat_message::Entity::delete_many()
+            .filter(chat_message::Column::Chat.eq(id))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_MESSAGES_ERROR"))?;
+
+        chat::Entity::delete_by_id(id.to_string())
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_CHAT_ERROR"))?;
+
+        Ok(None)
+    }
+
+    async fn export(&self, db: &DatabaseConnection, id: &str) -> Result<String, Error> {
+        let chat = chat::Entity::find_by_id(id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "CHAT_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "CHAT_MISSING_ERROR",
+            ))?;
+
+        let messages = chat
+            .find_related(chat_message::Entity)
+            .order_by_asc(chat_message::Column::Created)
+            .order_by_asc(chat_message::Column::Id)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_MESSAGES_ERROR"))?;
+
+        let document = json!({
+            "topic": chat.topic,
+            "messages": messages
+                .iter()
+                .map(|message| json!({
+                    "id": message.id,
+                    "author": message.author,
+                    "content": message.content,
+                    "attachments": message.attachments,
+                    "pinned": message.pinned,
+                    "created": message.created.to_string(),
+                    "edited": message.edited.map(|edited| edited.to_string()),
+                }))
+                .collect::<Vec<_>>(),
+        });
+
+        Ok(document.to_string())
+    }
+}
@@ -0,0 +1,48 @@
+use crate::permissions::constants::{self, Permission, PermissionCategory};
+use async_graphql::Error;
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use sea_orm::{DatabaseConnection, DatabaseTransaction};
+
+/// Work left over from deleting a component that can only be done once the deletion has been
+/// committed, such as removing files from storage.
+pub type Cleanup = BoxFuture<'static, ()>;
+
+/// A type of component that can be added to a planet. Each type stores its own data, which is
+/// referred to by the `component_id` of a planet component.
+#[async_trait]
+pub trait Component: Send + Sync {
+    /// The name of the component type, as stored in the `type` of planet components.
+    fn name(&self) -> &'static str;
+
+    /// The category of the permissions that apply to this type of component, if any.
+    fn permission_category(&self) -> Option<PermissionCategory>;
+
+    /// Creates the data for a new component, returning the ID of the created data. This runs in
+    /// the same transaction that adds the component to its planet.
+    async fn create(
+        &self,
+        db: &DatabaseTransaction,
+        planet: &str,
+        owner: &str,
+    ) -> Result<String, Error>;
+
+    /// Deletes all the data of a component. This runs in the same transaction that removes the
+    /// component from its planet, so anything outside of the database is left in place and removed
+    /// by the returned cleanup, which should only be run once the transaction has been committed.
+    async fn delete(&self, db: &DatabaseTransaction, id: &str) -> Result<Option<Cleanup>, Error>;
+
+    /// Exports all the data of a component as a JSON document.
+    async fn export(&self, db: &DatabaseConnection, id: &str) -> Result<String, Error>;
+
+    /// The permissions that can be overridden on this type of component.
+    fn permissions(&self) -> Vec<&'static Permission> {
+        match self.permission_category() {
+            Some(category) => constants::PERMISSIONS
+                .iter()
+                .filter(|permission| permission.category == category)
+                .collect(),
+            None => vec![],
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use super::component::{Cleanup, Component};
+use crate::permissions::constants::PermissionCategory;
+use async_graphql::Error;
+use async_trait::async_trait;
+use sea_orm::{DatabaseConnection, DatabaseTransaction};
+
+/// A component without any data, used as a placeholder.
+pub struct DummyComponent;
+
+#[async_trait]
+impl Component for DummyComponent {
+    fn name(&self) -> &'static str {
+        "dummy"
+    }
+
+    fn permission_category(&self) -> Option<PermissionCategory> {
+        None
+    }
+
+    async fn create(
+        &self,
+        _db: &DatabaseTransaction,
+        _planet: &str,
+        _owner: &str,
+    ) -> Result<String, Error> {
+        Ok("dummy".to_string())
+    }
+
+    async fn delete(&self, _db: &DatabaseTransaction, _id: &str) -> Result<Option<Cleanup>, Error> {
+        Ok(None)
+    }
+
+    async fn export(&self, _db: &DatabaseConnection, _id: &str) -> Result<String, Error> {
+        Ok("{}".to_string())
+    }
+}
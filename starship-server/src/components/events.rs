@@ -0,0 +1,507 @@
+use super::component::{Cleanup, Component};
+use crate::entities::event::Recurrence;
+use crate::entities::{event, event_calendar, event_rsvp};
+use crate::errors;
+use crate::permissions::constants::PermissionCategory;
+use async_graphql::{Error, InputObject};
+use async_trait::async_trait;
+use chrono::{Datelike, Duration, LocalResult, Months, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use nanoid::nanoid;
+use sea_orm::sea_query::Query;
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, ModelTrait,
+    QueryFilter, QueryOrder,
+};
+use serde_json::json;
+
+/// The longest an event can last.
+const MAX_EVENT_DAYS: i64 = 31;
+
+/// The largest number of times a recurring event can repeat.
+const MAX_RECURRENCE_COUNT: i32 = 1000;
+
+/// The most repeats of a recurring event that will be checked when listing its occurrences.
+const MAX_RECURRENCE_STEPS: i64 = 10_000;
+
+/// A calendar of events that members can RSVP to.
+pub struct EventsComponent;
+
+/// The details of an event, used when creating or editing it.
+#[derive(InputObject, Debug, Clone)]
+pub struct EventInput {
+    pub title: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    /// When the event starts, in UTC.
+    pub start: NaiveDateTime,
+    /// When the event ends, in UTC.
+    pub end: NaiveDateTime,
+    /// The IANA name of the timezone the event is planned in, such as `Europe/London`.
+    pub timezone: String,
+    pub recurrence: Option<RecurrenceInput>,
+}
+
+/// How an event repeats. Occurrences repeat at the same local time in the event's timezone, so
+/// they stay at the same time of day when its clocks change. Monthly and yearly events skip months
+/// that don't contain the day the event started on.
+#[derive(InputObject, Debug, Clone)]
+pub struct RecurrenceInput {
+    pub frequency: Recurrence,
+    /// How many days, weeks, months or years are between each occurrence.
+    #[graphql(default = 1)]
+    pub interval: i32,
+    /// The total number of occurrences, including the first.
+    pub count: Option<i32>,
+    /// The time the last occurrence can start at, in UTC.
+    pub until: Option<NaiveDateTime>,
+}
+
+/// Checks that an event's details are valid, returning them with the text trimmed. If they
+/// aren't, an error ready for presentation to the client is returned.
+pub fn validate_event(input: EventInput) -> Result<EventInput, Error> {
+    let title = input.title.trim().to_string();
+
+    if title.is_empty() || title.len() > 128 {
+        return Err(errors::create_user_input_error(
+            "Titles must be between 1 and 128 characters long.",
+            "INVALID_TITLE",
+        ));
+    }
+
+    let description = input
+        .description
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+
+    if description
+        .as_ref()
+        .is_some_and(|description| description.len() > 8000)
+    {
+        return Err(errors::create_user_input_error(
+            "Descriptions cannot be longer than 8000 characters.",
+            "DESCRIPTION_TOO_LONG",
+        ));
+    }
+
+    let location = input
+        .location
+        .map(|location| location.trim().to_string())
+        .filter(|location| !location.is_empty());
+
+    if location
+        .as_ref()
+        .is_some_and(|location| location.len() > 256)
+    {
+        return Err(errors::create_user_input_error(
+            "Locations cannot be longer than 256 characters.",
+            "LOCATION_TOO_LONG",
+        ));
+    }
+
+    if input.end <= input.start {
+        return Err(errors::create_user_input_error(
+            "Events must end after they start.",
+            "INVALID_TIMES",
+        ));
+    }
+
+    if input.end - input.start > Duration::days(MAX_EVENT_DAYS) {
+        return Err(errors::create_user_input_error(
+            "Events cannot be longer than 31 days.",
+            "EVENT_TOO_LONG",
+        ));
+    }
+
+    let timezone = input.timezone.trim().to_string();
+
+    if !is_valid_timezone(&timezone) {
+        return Err(errors::create_user_input_error(
+            "That timezone isn't valid.",
+            "INVALID_TIMEZONE",
+        ));
+    }
+
+    if let Some(recurrence) = &input.recurrence {
+        if !(1..=99).contains(&recurrence.interval) {
+            return Err(errors::create_user_input_error(
+                "Events must repeat at an interval between 1 and 99.",
+                "INVALID_INTERVAL",
+            ));
+        }
+
+        if recurrence
+            .count
+            .is_some_and(|count| !(1..=MAX_RECURRENCE_COUNT).contains(&count))
+        {
+            return Err(errors::create_user_input_error(
+                "Events can repeat between 1 and 1000 times.",
+                "INVALID_COUNT",
+            ));
+        }
+
+        if recurrence.until.is_some_and(|until| until < input.start) {
+            return Err(errors::create_user_input_error(
+                "Events must stop repeating after they start.",
+                "INVALID_UNTIL",
+            ));
+        }
+    }
+
+    Ok(EventInput {
+        title,
+        description,
+        location,
+        timezone,
+        ..input
+    })
+}
+
+/// Checks whether or not a timezone is a known IANA timezone name, such as `UTC` or
+/// `America/Argentina/Buenos_Aires`.
+pub fn is_valid_timezone(timezone: &str) -> bool {
+    timezone.parse::<Tz>().is_ok()
+}
+
+/// Gets the timezone an event is planned in. Events saved before timezones were checked may have
+/// unknown timezones, which are treated as UTC.
+fn event_timezone(event: &event::Model) -> Tz {
+    event.timezone.parse().unwrap_or(Tz::UTC)
+}
+
+/// Converts a UTC time to the local time in a timezone.
+fn to_local(time: NaiveDateTime, timezone: Tz) -> NaiveDateTime {
+    timezone.from_utc_datetime(&time).naive_local()
+}
+
+/// Converts a local time in a timezone to UTC. Times that happen twice when the clocks go back are
+/// the first of the two, and times skipped when the clocks go forward use the offset from before
+/// the change, as in RFC 5545.
+fn to_utc(local: NaiveDateTime, timezone: Tz) -> Option<NaiveDateTime> {
+    match timezone.from_local_datetime(&local) {
+        LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => Some(time.naive_utc()),
+        LocalResult::None => timezone
+            .from_local_datetime(&(local + Duration::hours(1)))
+            .earliest()
+            .map(|time| time.naive_utc()),
+    }
+}
+
+/// Gets the start time of the `n`th repeat of an event, if that repeat exists. Repeats are counted
+/// in the event's local time. Monthly and yearly repeats that would fall on a day the month doesn't
+/// have don't exist.
+fn nth_start(
+    start: NaiveDateTime,
+    timezone: Tz,
+    frequency: Recurrence,
+    n: i64,
+) -> Option<NaiveDateTime> {
+    let local = to_local(start, timezone);
+
+    let months = match frequency {
+        Recurrence::Daily => return to_utc(local.checked_add_signed(Duration::days(n))?, timezone),
+        Recurrence::Weekly => {
+            return to_utc(local.checked_add_signed(Duration::weeks(n))?, timezone)
+        }
+        Recurrence::Monthly => n,
+        Recurrence::Yearly => n * 12,
+    };
+
+    let repeat = local
+        .checked_add_months(Months::new(u32::try_from(months).ok()?))
+        .filter(|date| date.day() == local.day())?;
+
+    to_utc(repeat, timezone)
+}
+
+/// Lists the times an event takes place that overlap with a range of time, as pairs of start and
+/// end times. At most `limit` occurrences are returned.
+pub fn occurrences(
+    event: &event::Model,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+    limit: usize,
+) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    let duration = event.end - event.start;
+
+    let Some(frequency) = event.recurrence else {
+        return if event.start < to && event.end > from && limit > 0 {
+            vec![(event.start, event.end)]
+        } else {
+            vec![]
+        };
+    };
+
+    let timezone = event_timezone(event);
+    let interval = i64::from(event.recurrence_interval.max(1));
+
+    // daily and weekly events repeat at a fixed period in local time, so the repeats that end
+    // before the range can be skipped without checking each of them. Clock changes move repeats
+    // by less than a period, so no repeat that overlaps the range is skipped
+    let period = match frequency {
+        Recurrence::Daily => Some(Duration::days(interval)),
+        Recurrence::Weekly => Some(Duration::weeks(interval)),
+        Recurrence::Monthly | Recurrence::Yearly => None,
+    };
+
+    let mut step = match period {
+        Some(period) if from - duration > event.start => {
+            (from - duration - event.start).num_seconds() / period.num_seconds()
+        }
+        _ => 0,
+    };
+
+    // the number of repeats before this one, which only differs from the step when repeats on
+    // days that don't exist have been skipped
+    let mut index = step;
+    let mut found = vec![];
+
+    for _ in 0..MAX_RECURRENCE_STEPS {
+        if found.len() >= limit
+            || event
+                .recurrence_count
+                .is_some_and(|count| index >= i64::from(count))
+        {
+            break;
+        }
+
+        let Some(start) = nth_start(event.start, timezone, frequency, step * interval) else {
+            step += 1;
+            continue;
+        };
+
+        if start >= to || event.recurrence_until.is_some_and(|until| start > until) {
+            break;
+        }
+
+        if start + duration > from {
+            found.push((start, start + duration));
+        }
+
+        step += 1;
+        index += 1;
+    }
+
+    found
+}
+
+/// Escapes text for use as an iCalendar property value.
+fn escape_ics(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\r', '\n'], "\\n")
+}
+
+/// Formats a UTC time as an iCalendar date-time.
+fn format_ics_time(time: NaiveDateTime) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Formats a UTC time as the value of an iCalendar date-time property, such as `DTSTART`, in an
+/// event's timezone. Times in UTC are written as UTC date-times, and other times are written as
+/// local date-times with a `TZID` parameter.
+fn format_ics_property(name: &str, time: NaiveDateTime, timezone: Tz) -> String {
+    if timezone == Tz::UTC {
+        return format!("{name}:{}", format_ics_time(time));
+    }
+
+    format!(
+        "{name};TZID={}:{}",
+        timezone.name(),
+        to_local(time, timezone).format("%Y%m%dT%H%M%S")
+    )
+}
+
+/// Adds a content line to an iCalendar document, folding it so that no line is longer than 75
+/// bytes.
+fn push_ics_line(document: &mut String, line: &str) {
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            document.push_str("\r\n ");
+            length = 1;
+        }
+
+        document.push(c);
+        length += c.len_utf8();
+    }
+
+    document.push_str("\r\n");
+}
+
+/// Creates an iCalendar (RFC 5545) document containing a calendar's events. Start and end times
+/// are written in each event's timezone, which clients look up by its IANA name, and recurring
+/// events are written with a recurrence rule.
+pub fn to_ics(name: &str, events: &[event::Model], now: NaiveDateTime) -> String {
+    let mut document = String::new();
+
+    push_ics_line(&mut document, "BEGIN:VCALENDAR");
+    push_ics_line(&mut document, "VERSION:2.0");
+    push_ics_line(&mut document, "PRODID:-//Starship//Events//EN");
+    push_ics_line(&mut document, "CALSCALE:GREGORIAN");
+    push_ics_line(&mut document, &format!("X-WR-CALNAME:{}", escape_ics(name)));
+
+    for event in events {
+        push_ics_line(&mut document, "BEGIN:VEVENT");
+        push_ics_line(&mut document, &format!("UID:{}@starship", event.id));
+        push_ics_line(&mut document, &format!("DTSTAMP:{}", format_ics_time(now)));
+        let timezone = event_timezone(event);
+
+        push_ics_line(
+            &mut document,
+            &format_ics_property("DTSTART", event.start, timezone),
+        );
+        push_ics_line(
+            &mut document,
+            &format_ics_property("DTEND", event.end, timezone),
+        );
+        push_ics_line(
+            &mut document,
+            &format!("SUMMARY:{}", escape_ics(&event.title)),
+        );
+
+        if let Some(description) = &event.description {
+            push_ics_line(
+                &mut document,
+                &format!("DESCRIPTION:{}", escape_ics(description)),
+            );
+        }
+
+        if let Some(location) = &event.location {
+            push_ics_line(&mut document, &format!("LOCATION:{}", escape_ics(location)));
+        }
+
+        if let Some(recurrence) = event.recurrence {
+            let frequency = match recurrence {
+                Recurrence::Daily => "DAILY",
+                Recurrence::Weekly => "WEEKLY",
+                Recurrence::Monthly => "MONTHLY",
+                Recurrence::Yearly => "YEARLY",
+            };
+
+            let mut rule = format!(
+                "RRULE:FREQ={};INTERVAL={}",
+                frequency, event.recurrence_interval
+            );
+
+            if let Some(count) = event.recurrence_count {
+                rule.push_str(&format!(";COUNT={count}"));
+            }
+
+            if let Some(until) = event.recurrence_until {
+                rule.push_str(&format!(";UNTIL={}", format_ics_time(until)));
+            }
+
+            push_ics_line(&mut document, &rule);
+        }
+
+        push_ics_line(&mut document, "END:VEVENT");
+    }
+
+    push_ics_line(&mut document, "END:VCALENDAR");
+
+    document
+}
+
+#[async_trait]
+impl Component for EventsComponent {
+    fn name(&self) -> &'static str {
+        "events"
+    }
+
+    fn permission_category(&self) -> Option<PermissionCategory> {
+        Some(PermissionCategory::Events)
+    }
+
+    async fn create(
+        &self,
+        db: &DatabaseTransaction,
+        planet: &str,
+        _owner: &str,
+    ) -> Result<String, Error> {
+        let calendar = event_calendar::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            planet: ActiveValue::Set(planet.to_string()),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        let insertion = event_calendar::Entity::insert(calendar)
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "CALENDAR_INSERTION_ERROR"))?;
+
+        Ok(insertion.last_insert_id)
+    }
+
+    async fn delete(&self, db: &DatabaseTransaction, id: &str) -> Result<Option<Cleanup>, Error> {
+        let events = Query::select()
+            .column(event::Column::Id)
+            .from(event::Entity)
+            .and_where(event::Column::Calendar.eq(id))
+            .to_owned();
+
+        event_rsvp::Entity::delete_many()
+            .filter(event_rsvp::Column::Event.in_subquery(events))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_RSVPS_ERROR"))?;
+
+        event::Entity::delete_many()
+            .filter(event::Column::Calendar.eq(id))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_EVENTS_ERROR"))?;
+
+        event_calendar::Entity::delete_by_id(id.to_string())
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_CALENDAR_ERROR"))?;
+
+        Ok(None)
+    }
+
+    async fn export(&self, db: &DatabaseConnection, id: &str) -> Result<String, Error> {
+        let calendar = event_calendar::Entity::find_by_id(id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "CALENDAR_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "CALENDAR_MISSING_ERROR",
+            ))?;
+
+        let events = calendar
+            .find_related(event::Entity)
+            .order_by_asc(event::Column::Start)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_EVENTS_ERROR"))?;
+
+        let document = json!({
+            "events": events
+                .iter()
+                .map(|event| json!({
+                    "id": event.id,
+                    "title": event.title,
+                    "description": event.description,
+                    "location": event.location,
+                    "start": event.start.to_string(),
+                    "end": event.end.to_string(),
+                    "timezone": event.timezone,
+                    "recurrence": event.recurrence.map(|recurrence| json!({
+                        "frequency": format!("{recurrence:?}").to_lowercase(),
+                        "interval": event.recurrence_interval,
+                        "count": event.recurrence_count,
+                        "until": event.recurrence_until.map(|until| until.to_string()),
+                    })),
+                    "creator": event.creator,
+                }))
+                .collect::<Vec<_>>(),
+        });
+
+        Ok(document.to_string())
+    }
+}
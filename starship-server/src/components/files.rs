@@ -0,0 +1,387 @@
+use super::component::{Cleanup, Component};
+use crate::entities::{file, file_folder, file_tree, user};
+use crate::errors;
+use crate::permissions::constants::PermissionCategory;
+use crate::storage::Storage;
+use async_graphql::Error;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use log::warn;
+use nanoid::nanoid;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction,
+    EntityTrait, ModelTrait, QueryFilter, QueryOrder, UpdateMany,
+};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// The largest file that can be uploaded, in bytes.
+pub const MAX_FILE_SIZE: usize = 100_000_000;
+
+/// The most bytes of files a folder can hold to be downloaded as an archive. Archives are built in
+/// memory, so this is kept well below the 4 GB an archive can hold.
+pub const MAX_ARCHIVE_SIZE: i64 = 1_000_000_000;
+
+/// A tree of folders that members can upload files to.
+pub struct FilesComponent {
+    storage: Arc<dyn Storage>,
+}
+
+impl FilesComponent {
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        FilesComponent { storage }
+    }
+}
+
+/// Gets the key the contents of a file are stored under.
+pub fn storage_key(file: &file::Model) -> String {
+    format!("{}/{}", file.tree, file.id)
+}
+
+/// Checks that the name of a file or folder is valid, returning it trimmed. If it isn't, an error
+/// ready for presentation to the client is returned.
+pub fn validate_name(name: &str) -> Result<String, Error> {
+    let name = name.trim();
+
+    if name.is_empty() || name.len() > 255 {
+        return Err(errors::create_user_input_error(
+            "Names must be between 1 and 255 characters long.",
+            "INVALID_NAME",
+        ));
+    }
+
+    if name.contains(['/', '\\']) || name == "." || name == ".." {
+        return Err(errors::create_user_input_error(
+            "Names cannot contain slashes.",
+            "INVALID_NAME",
+        ));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Builds the update that takes up `size` more bytes of a user's storage, out of the `quota` each
+/// user has across every planet. The update only applies if the user has room for them, or if
+/// their cap is waived.
+pub fn reserve_space_query(user_id: &str, size: i64, quota: i64) -> UpdateMany<user::Entity> {
+    user::Entity::update_many()
+        .col_expr(
+            user::Column::BytesUsed,
+            Expr::col(user::Column::BytesUsed).add(size),
+        )
+        .filter(user::Column::Id.eq(user_id))
+        .filter(Expr::cust_with_values(
+            r#"("cap_waived" OR "bytes_used" + $1 <= $2)"#,
+            [size, quota],
+        ))
+}
+
+/// Takes up `size` more bytes of a user's storage. If the user doesn't have room for them, an
+/// error ready for presentation to the client is returned.
+pub async fn reserve_space<C: ConnectionTrait>(
+    user_id: &str,
+    size: i64,
+    quota: i64,
+    db: &C,
+) -> Result<(), Error> {
+    let result = reserve_space_query(user_id, size, quota)
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "UPDATE_USER_ERROR"))?;
+
+    if result.rows_affected == 0 {
+        return Err(errors::create_user_input_error(
+            "You don't have enough storage space left to upload that.",
+            "QUOTA_EXCEEDED",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Gives `size` bytes of storage back to a user.
+pub async fn release_space<C: ConnectionTrait>(
+    user_id: &str,
+    size: i64,
+    db: &C,
+) -> Result<(), Error> {
+    user::Entity::update_many()
+        .col_expr(
+            user::Column::BytesUsed,
+            Expr::cust_with_values(r#"GREATEST("bytes_used" - $1, 0)"#, [size]),
+        )
+        .filter(user::Column::Id.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "UPDATE_USER_ERROR"))?;
+
+    Ok(())
+}
+
+/// Gets a folder in a file tree, returning a not found error if it doesn't exist or is in another
+/// tree.
+pub async fn get_folder(
+    tree: &str,
+    id: &str,
+    db: &DatabaseConnection,
+) -> Result<file_folder::Model, Error> {
+    file_folder::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FOLDER_RETRIEVAL_ERROR"))?
+        .filter(|folder| folder.tree == tree)
+        .ok_or(errors::create_not_found_error())
+}
+
+/// Gets the IDs of a folder and every folder inside of it, at any depth.
+pub async fn folder_with_descendants(
+    folder: &file_folder::Model,
+    db: &DatabaseConnection,
+) -> Result<Vec<String>, Error> {
+    let mut ids = vec![folder.id.clone()];
+    let mut level = vec![folder.id.clone()];
+
+    while !level.is_empty() {
+        level = file_folder::Entity::find()
+            .filter(file_folder::Column::Parent.is_in(level))
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FOLDERS_ERROR"))?
+            .into_iter()
+            .map(|folder| folder.id)
+            .collect();
+
+        ids.extend(level.iter().cloned());
+    }
+
+    Ok(ids)
+}
+
+/// Where a folder or file goes in an archive of a folder.
+pub struct ArchivePath<'a> {
+    pub path: String,
+    pub modified: NaiveDateTime,
+    /// The file at this path, or `None` if the path is a folder.
+    pub file: Option<&'a file::Model>,
+}
+
+/// Works out where a folder, every folder inside of it and their files go in an archive of the
+/// folder. Folders come before anything inside of them, with paths ending in `/`. Files with the
+/// same name in the same folder are numbered, so that extracting the archive doesn't overwrite any
+/// of them. Folders and files that aren't inside of `root` are left out.
+pub fn archive_layout<'a>(
+    root: &file_folder::Model,
+    folders: &[file_folder::Model],
+    files: &'a [file::Model],
+) -> Vec<ArchivePath<'a>> {
+    let mut folder_paths: HashMap<String, String> = HashMap::new();
+    let mut layout = vec![];
+    let mut level = vec![(root.id.clone(), format!("{}/", root.name), root.created)];
+
+    while !level.is_empty() {
+        let mut next_level = vec![];
+
+        for (id, path, created) in level {
+            for child in folders
+                .iter()
+                .filter(|folder| folder.parent.as_ref() == Some(&id))
+            {
+                next_level.push((
+                    child.id.clone(),
+                    format!("{path}{}/", child.name),
+                    child.created,
+                ));
+            }
+
+            layout.push(ArchivePath {
+                path: path.clone(),
+                modified: created,
+                file: None,
+            });
+            folder_paths.insert(id, path);
+        }
+
+        level = next_level;
+    }
+
+    let mut taken: HashSet<String> = layout.iter().map(|entry| entry.path.clone()).collect();
+
+    for file in files {
+        let Some(folder_path) = file.folder.as_ref().and_then(|id| folder_paths.get(id)) else {
+            continue;
+        };
+
+        let mut path = format!("{folder_path}{}", file.name);
+        let mut copy = 1;
+
+        while taken.contains(&path) || taken.contains(&format!("{path}/")) {
+            copy += 1;
+
+            path = match file.name.rsplit_once('.') {
+                Some((stem, extension)) if !stem.is_empty() => {
+                    format!("{folder_path}{stem} ({copy}).{extension}")
+                }
+                _ => format!("{folder_path}{} ({copy})", file.name),
+            };
+        }
+
+        taken.insert(path.clone());
+        layout.push(ArchivePath {
+            path,
+            modified: file.created,
+            file: Some(file),
+        });
+    }
+
+    layout
+}
+
+/// Deletes files from the database, returning the space they used to their owners. Their contents
+/// are left in storage until [`remove_contents`] is called, so that this can run in a transaction.
+pub async fn delete_files<C: ConnectionTrait>(files: &[file::Model], db: &C) -> Result<(), Error> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let mut freed: HashMap<String, i64> = HashMap::new();
+
+    for file in files {
+        *freed.entry(file.owner.clone()).or_default() += file.size;
+    }
+
+    file::Entity::delete_many()
+        .filter(file::Column::Id.is_in(files.iter().map(|file| file.id.clone())))
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "DELETE_FILES_ERROR"))?;
+
+    for (owner, size) in freed {
+        release_space(&owner, size, db).await?;
+    }
+
+    Ok(())
+}
+
+/// Removes the contents of deleted files from storage.
+pub async fn remove_contents(files: &[file::Model], storage: &dyn Storage) {
+    // the files are already gone from the database, so contents that fail to be removed are only
+    // wasted space
+    for file in files {
+        if let Err(err) = storage.delete(&storage_key(file)).await {
+            warn!("failed to delete {} from storage: {err}", file.id);
+        }
+    }
+}
+
+#[async_trait]
+impl Component for FilesComponent {
+    fn name(&self) -> &'static str {
+        "files"
+    }
+
+    fn permission_category(&self) -> Option<PermissionCategory> {
+        Some(PermissionCategory::Files)
+    }
+
+    async fn create(
+        &self,
+        db: &DatabaseTransaction,
+        planet: &str,
+        _owner: &str,
+    ) -> Result<String, Error> {
+        let tree = file_tree::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            planet: ActiveValue::Set(planet.to_string()),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        let insertion = file_tree::Entity::insert(tree)
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "TREE_INSERTION_ERROR"))?;
+
+        Ok(insertion.last_insert_id)
+    }
+
+    async fn delete(&self, db: &DatabaseTransaction, id: &str) -> Result<Option<Cleanup>, Error> {
+        let files = file::Entity::find()
+            .filter(file::Column::Tree.eq(id))
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FILES_ERROR"))?;
+
+        delete_files(&files, db).await?;
+
+        file_folder::Entity::delete_many()
+            .filter(file_folder::Column::Tree.eq(id))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_FOLDERS_ERROR"))?;
+
+        file_tree::Entity::delete_by_id(id.to_string())
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_TREE_ERROR"))?;
+
+        let storage = self.storage.clone();
+
+        Ok(Some(Box::pin(async move {
+            remove_contents(&files, storage.as_ref()).await;
+        })))
+    }
+
+    async fn export(&self, db: &DatabaseConnection, id: &str) -> Result<String, Error> {
+        let tree = file_tree::Entity::find_by_id(id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "TREE_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "TREE_MISSING_ERROR",
+            ))?;
+
+        let folders = tree
+            .find_related(file_folder::Entity)
+            .order_by_asc(file_folder::Column::Created)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FOLDERS_ERROR"))?;
+
+        let files = tree
+            .find_related(file::Entity)
+            .order_by_asc(file::Column::Created)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FILES_ERROR"))?;
+
+        // the contents of files are left out, since they can be downloaded separately
+        let document = json!({
+            "folders": folders
+                .iter()
+                .map(|folder| json!({
+                    "id": folder.id,
+                    "parent": folder.parent,
+                    "owner": folder.owner,
+                    "name": folder.name,
+                    "created": folder.created.to_string(),
+                }))
+                .collect::<Vec<_>>(),
+            "files": files
+                .iter()
+                .map(|file| json!({
+                    "id": file.id,
+                    "folder": file.folder,
+                    "owner": file.owner,
+                    "name": file.name,
+                    "size": file.size,
+                    "mimeType": file.mime_type,
+                    "created": file.created.to_string(),
+                }))
+                .collect::<Vec<_>>(),
+        });
+
+        Ok(document.to_string())
+    }
+}
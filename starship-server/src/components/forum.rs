@@ -0,0 +1,367 @@
+use super::access::ComponentAccess;
+use super::component::{Cleanup, Component};
+use crate::entities::{forum, forum_post, forum_reaction, forum_reply, forum_tag};
+use crate::errors;
+use crate::permissions::constants::PermissionCategory;
+use async_graphql::{Error, SimpleObject};
+use async_trait::async_trait;
+use nanoid::nanoid;
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, ModelTrait,
+    QueryFilter, QueryOrder, QuerySelect, Select,
+};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+/// The largest number of tags a post can have.
+const MAX_POST_TAGS: usize = 5;
+
+/// The largest number of files that can be attached to a post.
+const MAX_POST_ATTACHMENTS: usize = 10;
+
+/// The most users that are notified of a new reply to a thread they replied to.
+pub const MAX_PARTICIPANT_NOTIFICATIONS: u64 = 50;
+
+/// A forum where members can start threads and reply to them.
+pub struct ForumComponent;
+
+/// How many users reacted to a post or reply with an emoji.
+#[derive(SimpleObject)]
+pub struct ReactionCount {
+    pub emoji: String,
+    pub count: u64,
+    /// Whether or not the current user reacted with this emoji.
+    pub reacted: bool,
+}
+
+/// Checks that a title is valid, returning it trimmed. If it isn't, an error ready for
+/// presentation to the client is returned.
+pub fn validate_title(title: &str) -> Result<String, Error> {
+    let title = title.trim();
+
+    if title.is_empty() {
+        return Err(errors::create_user_input_error(
+            "Posts must have a title.",
+            "TITLE_EMPTY",
+        ));
+    }
+
+    if title.len() > 256 {
+        return Err(errors::create_user_input_error(
+            "Titles cannot be longer than 256 characters.",
+            "TITLE_TOO_LONG",
+        ));
+    }
+
+    Ok(title.to_string())
+}
+
+/// Checks that the content of a post or reply is valid. If it isn't, an error ready for
+/// presentation to the client is returned.
+pub fn validate_content(content: &str) -> Result<(), Error> {
+    if content.trim().is_empty() {
+        return Err(errors::create_user_input_error(
+            "Posts and replies cannot be empty.",
+            "CONTENT_EMPTY",
+        ));
+    }
+
+    if content.len() > 20000 {
+        return Err(errors::create_user_input_error(
+            "Posts and replies cannot be longer than 20000 characters.",
+            "CONTENT_TOO_LONG",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that a list of attachments is valid. Attachments are links to files. If they aren't
+/// valid, an error ready for presentation to the client is returned.
+pub fn validate_attachments(attachments: &[String]) -> Result<(), Error> {
+    if attachments.len() > MAX_POST_ATTACHMENTS {
+        return Err(errors::create_user_input_error(
+            "Posts cannot have more than 10 attachments.",
+            "TOO_MANY_ATTACHMENTS",
+        ));
+    }
+
+    for attachment in attachments {
+        if attachment.len() > 2048
+            || !(attachment.starts_with("https://") || attachment.starts_with("http://"))
+        {
+            return Err(errors::create_user_input_error(
+                "Attachments must be links to files.",
+                "INVALID_ATTACHMENT",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that every tag belongs to a forum, returning the tags without duplicates. If they
+/// don't, an error ready for presentation to the client is returned.
+pub async fn validate_tags(
+    forum_id: &str,
+    mut tags: Vec<String>,
+    db: &DatabaseConnection,
+) -> Result<Vec<String>, Error> {
+    tags.sort();
+    tags.dedup();
+
+    if tags.len() > MAX_POST_TAGS {
+        return Err(errors::create_user_input_error(
+            "Posts cannot have more than 5 tags.",
+            "TOO_MANY_TAGS",
+        ));
+    }
+
+    if tags.is_empty() {
+        return Ok(tags);
+    }
+
+    let found = forum_tag::Entity::find()
+        .filter(
+            forum_tag::Column::Forum
+                .eq(forum_id)
+                .and(forum_tag::Column::Id.is_in(tags.clone())),
+        )
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_TAGS_ERROR"))?;
+
+    if found.len() != tags.len() {
+        return Err(errors::create_user_input_error(
+            "One of those tags doesn't exist.",
+            "INVALID_TAG",
+        ));
+    }
+
+    Ok(tags)
+}
+
+/// Checks that the current user can reply to a thread. Locked threads can only be replied to with
+/// the `forum.posts.lock.ignore` permission.
+pub fn check_can_reply(access: &ComponentAccess, post: &forum_post::Model) -> Result<(), Error> {
+    access.check_permission("forum.post.reply")?;
+
+    if post.locked && !access.has_permission("forum.posts.lock.ignore") {
+        return Err(errors::create_user_input_error(
+            "This thread is locked.",
+            "LOCKED",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Builds the query for the threads of a forum. Sticky threads come first, followed by the threads
+/// with the most recent activity. If `tag` is provided, only threads with that tag are included.
+pub fn thread_query(forum_id: &str, tag: Option<String>) -> Select<forum_post::Entity> {
+    let mut query = forum_post::Entity::find().filter(forum_post::Column::Forum.eq(forum_id));
+
+    if let Some(tag) = tag {
+        query = query.filter(Expr::cust_with_values(
+            r#"$1 = ANY("forum_post"."tags")"#,
+            [tag],
+        ));
+    }
+
+    query
+        .order_by_desc(forum_post::Column::Sticky)
+        .order_by_desc(forum_post::Column::LastActivity)
+        .order_by_desc(forum_post::Column::Id)
+}
+
+/// Builds the query for one reply from each user that replied to a thread, other than the thread's
+/// author, who is notified of replies separately.
+pub fn participants_query(post: &forum_post::Model) -> Select<forum_reply::Entity> {
+    forum_reply::Entity::find()
+        .distinct_on([forum_reply::Column::Author])
+        .filter(forum_reply::Column::Post.eq(post.id.clone()))
+        .filter(forum_reply::Column::Author.ne(post.author.clone()))
+        .order_by_asc(forum_reply::Column::Author)
+        .limit(MAX_PARTICIPANT_NOTIFICATIONS)
+}
+
+/// Checks that an emoji used as a reaction is valid. If it isn't, an error ready for presentation
+/// to the client is returned.
+pub fn validate_emoji(emoji: &str) -> Result<(), Error> {
+    if emoji.is_empty() || emoji.len() > 64 {
+        return Err(errors::create_user_input_error(
+            "That emoji isn't valid.",
+            "INVALID_EMOJI",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Counts the reactions to a post, or to one of its replies if `reply` is provided.
+pub async fn reaction_counts(
+    post: &str,
+    reply: Option<&str>,
+    user_id: Option<&str>,
+    db: &DatabaseConnection,
+) -> Result<Vec<ReactionCount>, Error> {
+    let reply_condition = match reply {
+        Some(reply) => forum_reaction::Column::Reply.eq(reply),
+        None => forum_reaction::Column::Reply.is_null(),
+    };
+
+    let reactions = forum_reaction::Entity::find()
+        .filter(forum_reaction::Column::Post.eq(post))
+        .filter(reply_condition)
+        .order_by_asc(forum_reaction::Column::Created)
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_REACTIONS_ERROR"))?;
+
+    let mut counts: BTreeMap<String, ReactionCount> = BTreeMap::new();
+
+    for reaction in reactions {
+        let count = counts
+            .entry(reaction.emoji.clone())
+            .or_insert(ReactionCount {
+                emoji: reaction.emoji,
+                count: 0,
+                reacted: false,
+            });
+
+        count.count += 1;
+        count.reacted |= user_id == Some(reaction.user.as_str());
+    }
+
+    Ok(counts.into_values().collect())
+}
+
+#[async_trait]
+impl Component for ForumComponent {
+    fn name(&self) -> &'static str {
+        "forum"
+    }
+
+    fn permission_category(&self) -> Option<PermissionCategory> {
+        Some(PermissionCategory::Forum)
+    }
+
+    async fn create(
+        &self,
+        db: &DatabaseTransaction,
+        planet: &str,
+        _owner: &str,
+    ) -> Result<String, Error> {
+        let forum = forum::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            planet: ActiveValue::Set(planet.to_string()),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        let insertion = forum::Entity::insert(forum)
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FORUM_INSERTION_ERROR"))?;
+
+        Ok(insertion.last_insert_id)
+    }
+
+    async fn delete(&self, db: &DatabaseTransaction, id: &str) -> Result<Option<Cleanup>, Error> {
+        let posts = Query::select()
+            .column(forum_post::Column::Id)
+            .from(forum_post::Entity)
+            .and_where(forum_post::Column::Forum.eq(id))
+            .to_owned();
+
+        forum_reaction::Entity::delete_many()
+            .filter(forum_reaction::Column::Post.in_subquery(posts.clone()))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REACTIONS_ERROR"))?;
+
+        forum_reply::Entity::delete_many()
+            .filter(forum_reply::Column::Post.in_subquery(posts))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REPLIES_ERROR"))?;
+
+        forum_post::Entity::delete_many()
+            .filter(forum_post::Column::Forum.eq(id))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_POSTS_ERROR"))?;
+
+        forum_tag::Entity::delete_many()
+            .filter(forum_tag::Column::Forum.eq(id))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_TAGS_ERROR"))?;
+
+        forum::Entity::delete_by_id(id.to_string())
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_FORUM_ERROR"))?;
+
+        Ok(None)
+    }
+
+    async fn export(&self, db: &DatabaseConnection, id: &str) -> Result<String, Error> {
+        let forum = forum::Entity::find_by_id(id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FORUM_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "FORUM_MISSING_ERROR",
+            ))?;
+
+        let tags = forum
+            .find_related(forum_tag::Entity)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_TAGS_ERROR"))?;
+
+        let posts = forum
+            .find_related(forum_post::Entity)
+            .order_by_asc(forum_post::Column::Created)
+            .find_with_related(forum_reply::Entity)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_POSTS_ERROR"))?;
+
+        let document = json!({
+            "tags": tags
+                .iter()
+                .map(|tag| json!({ "id": tag.id, "name": tag.name, "color": tag.color }))
+                .collect::<Vec<_>>(),
+            "posts": posts
+                .iter()
+                .map(|(post, replies)| json!({
+                    "id": post.id,
+                    "author": post.author,
+                    "title": post.title,
+                    "content": post.content,
+                    "tags": post.tags,
+                    "attachments": post.attachments,
+                    "sticky": post.sticky,
+                    "locked": post.locked,
+                    "created": post.created.to_string(),
+                    "edited": post.edited.map(|edited| edited.to_string()),
+                    "replies": replies
+                        .iter()
+                        .map(|reply| json!({
+                            "id": reply.id,
+                            "author": reply.author,
+                            "content": reply.content,
+                            "created": reply.created.to_string(),
+                            "edited": reply.edited.map(|edited| edited.to_string()),
+                        }))
+                        .collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+        });
+
+        Ok(document.to_string())
+    }
+}
@@ -1 +1,11 @@
-pub mod index;
+pub mod access;
+pub mod chat;
+pub mod component;
+pub mod dummy;
+pub mod events;
+pub mod files;
+pub mod forum;
+pub mod page;
+pub mod registry;
+pub mod tree;
+pub mod wiki;
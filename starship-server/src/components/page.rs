@@ -0,0 +1,130 @@
+use super::access::ComponentAccess;
+use super::component::{Cleanup, Component};
+use crate::entities::page;
+use crate::errors;
+use crate::permissions::constants::PermissionCategory;
+use async_graphql::Error;
+use async_trait::async_trait;
+use nanoid::nanoid;
+use sea_orm::{ActiveValue, DatabaseConnection, DatabaseTransaction, EntityTrait};
+use std::rc::Rc;
+use toolbox_types::document::ProjectDocument;
+use toolbox_types::project::{Project, Type};
+use toolbox_types::tree::page::Page;
+
+/// The largest serialized document that can be saved to a page, in bytes.
+const MAX_CONTENT_LENGTH: usize = 1_000_000;
+
+/// A page built with Toolbox. Each page stores a serialized Toolbox project.
+pub struct PageComponent;
+
+/// Creates the document for a new page, containing a single empty page.
+fn default_content() -> String {
+    let project = Project::create("Page".to_string(), Type::Component);
+    let _ = Page::create("Home".to_string(), Rc::downgrade(&project));
+
+    let document = ProjectDocument::from_project(&project.borrow());
+
+    // serializing a document can't fail, since every key is a string
+    serde_json::to_string(&document).unwrap()
+}
+
+/// Checks that some content is a valid Toolbox project, returning it in its normalized form. If it
+/// isn't, an error ready for presentation to the client is returned.
+pub fn validate_content(content: &str) -> Result<String, Error> {
+    if content.len() > MAX_CONTENT_LENGTH {
+        return Err(errors::create_user_input_error(
+            "Pages cannot be larger than 1 MB.",
+            "CONTENT_TOO_LONG",
+        ));
+    }
+
+    let invalid_error =
+        || errors::create_user_input_error("That page isn't a valid project.", "INVALID_CONTENT");
+
+    let document: ProjectDocument = serde_json::from_str(content).map_err(|_| invalid_error())?;
+
+    // loading the project checks that the tree in the document is valid
+    let project = document.into_project().map_err(|_| invalid_error())?;
+    let document = ProjectDocument::from_project(&project.borrow());
+
+    serde_json::to_string(&document).map_err(|_| invalid_error())
+}
+
+/// Gets a page component's page, checking that the current user holds a permission within the
+/// component. If the component isn't a page, a not found error is returned.
+pub async fn get_page(
+    component_id: String,
+    user_id: Option<String>,
+    permission: &str,
+    db: &DatabaseConnection,
+) -> Result<page::Model, Error> {
+    let access = ComponentAccess::load(component_id, "page", user_id, db).await?;
+    access.check_permission(permission)?;
+
+    page::Entity::find_by_id(access.component.component_id)
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "PAGE_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_internal_server_error(
+            None,
+            "PAGE_MISSING_ERROR",
+        ))
+}
+
+#[async_trait]
+impl Component for PageComponent {
+    fn name(&self) -> &'static str {
+        "page"
+    }
+
+    fn permission_category(&self) -> Option<PermissionCategory> {
+        Some(PermissionCategory::Page)
+    }
+
+    async fn create(
+        &self,
+        db: &DatabaseTransaction,
+        planet: &str,
+        owner: &str,
+    ) -> Result<String, Error> {
+        let now = chrono::offset::Utc::now().naive_utc();
+
+        let page = page::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            planet: ActiveValue::Set(planet.to_string()),
+            content: ActiveValue::Set(default_content()),
+            created: ActiveValue::Set(now),
+            updated: ActiveValue::Set(now),
+            updated_by: ActiveValue::Set(Some(owner.to_string())),
+        };
+
+        let insertion = page::Entity::insert(page)
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "PAGE_INSERTION_ERROR"))?;
+
+        Ok(insertion.last_insert_id)
+    }
+
+    async fn delete(&self, db: &DatabaseTransaction, id: &str) -> Result<Option<Cleanup>, Error> {
+        page::Entity::delete_by_id(id.to_string())
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_PAGE_ERROR"))?;
+
+        Ok(None)
+    }
+
+    async fn export(&self, db: &DatabaseConnection, id: &str) -> Result<String, Error> {
+        page::Entity::find_by_id(id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "PAGE_RETRIEVAL_ERROR"))?
+            .map(|page| page.content)
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "PAGE_MISSING_ERROR",
+            ))
+    }
+}
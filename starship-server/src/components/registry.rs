@@ -0,0 +1,61 @@
+use super::chat::ChatComponent;
+use super::component::Component;
+use super::dummy::DummyComponent;
+use super::events::EventsComponent;
+use super::files::FilesComponent;
+use super::forum::ForumComponent;
+use super::page::PageComponent;
+use super::wiki::WikiComponent;
+use crate::errors;
+use crate::storage::Storage;
+use async_graphql::Error;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The component types supported by the server, keyed by name.
+#[derive(Clone, Default)]
+pub struct ComponentRegistry {
+    components: HashMap<&'static str, Arc<dyn Component>>,
+}
+
+impl ComponentRegistry {
+    /// Creates a registry containing every component type built into the server. Components that
+    /// store files keep them in `storage`.
+    pub fn with_builtin_components(storage: Arc<dyn Storage>) -> Self {
+        let mut registry = ComponentRegistry::default();
+        registry.register(ChatComponent);
+        registry.register(DummyComponent);
+        registry.register(EventsComponent);
+        registry.register(FilesComponent::new(storage));
+        registry.register(ForumComponent);
+        registry.register(PageComponent);
+        registry.register(WikiComponent);
+        registry
+    }
+
+    /// Adds a component type to the registry, replacing any type with the same name.
+    pub fn register(&mut self, component: impl Component + 'static) {
+        self.components
+            .insert(component.name(), Arc::new(component));
+    }
+
+    /// Gets a component type by name. If the type isn't registered, an error ready for
+    /// presentation to the client is returned.
+    pub fn get(&self, name: &str) -> Result<Arc<dyn Component>, Error> {
+        self.components.get(name).cloned().ok_or_else(|| {
+            errors::create_user_input_error("That component type isn't valid.", "INVALID_TYPE")
+        })
+    }
+
+    /// The names of every registered component type, in alphabetical order.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .components
+            .keys()
+            .map(|name| (*name).to_string())
+            .collect();
+
+        names.sort();
+        names
+    }
+}
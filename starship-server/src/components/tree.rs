@@ -0,0 +1,44 @@
+use crate::entities::planet_component;
+use std::collections::HashMap;
+
+/// Checks whether or not moving a component into `parent` would make it an ancestor of itself.
+/// `components` is every component on the planet.
+pub fn would_create_cycle(
+    component: &str,
+    parent: &str,
+    components: &[planet_component::Model],
+) -> bool {
+    let parents: HashMap<&str, Option<&str>> = components
+        .iter()
+        .map(|component| (component.id.as_str(), component.parent_id.as_deref()))
+        .collect();
+
+    let mut current = Some(parent);
+
+    // the number of steps is limited in case the tree already contains a cycle
+    for _ in 0..=components.len() {
+        match current {
+            Some(id) if id == component => return true,
+            Some(id) => current = parents.get(id).copied().flatten(),
+            None => return false,
+        }
+    }
+
+    true
+}
+
+/// Places a component among its new siblings at `position`, returning the IDs of the siblings and
+/// the component in their new order. Positions past the end place the component last.
+pub fn reorder(
+    component: &str,
+    position: usize,
+    mut siblings: Vec<planet_component::Model>,
+) -> Vec<String> {
+    siblings.retain(|sibling| sibling.id != component);
+    siblings.sort_by(|a, b| a.position.cmp(&b.position).then_with(|| a.id.cmp(&b.id)));
+
+    let mut order: Vec<String> = siblings.into_iter().map(|sibling| sibling.id).collect();
+    order.insert(position.min(order.len()), component.to_string());
+
+    order
+}
@@ -0,0 +1,398 @@
+use super::component::{Cleanup, Component};
+use crate::entities::{wiki, wiki_article, wiki_revision};
+use crate::errors;
+use crate::permissions::constants::PermissionCategory;
+use async_graphql::{Enum, Error, SimpleObject};
+use async_trait::async_trait;
+use nanoid::nanoid;
+use sea_orm::sea_query::Query;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    DatabaseTransaction, EntityTrait, ModelTrait, QueryFilter, QueryOrder,
+};
+use serde_json::json;
+
+/// The longest article that can be saved, in bytes.
+const MAX_CONTENT_LENGTH: usize = 200_000;
+
+/// The largest number of line comparisons made when diffing two revisions. Larger diffs fall back
+/// to replacing every changed line.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// A wiki of Markdown articles. Every edit to an article is kept as a revision, and articles can
+/// link to each other with `[[Title]]` or `[[Title|label]]`.
+pub struct WikiComponent;
+
+/// Whether a line in a diff was added, removed, or is the same in both revisions.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+/// A line in the diff between two revisions of an article.
+#[derive(SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub content: String,
+}
+
+/// Creates the slug for an article from its title. Slugs are lowercase, and only contain letters,
+/// numbers and hyphens.
+pub fn slugify(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Finds the slugs of the articles linked to from some content, in the order they first appear.
+/// Links are written as `[[Title]]`, or `[[Title|label]]` to show different text.
+pub fn parse_links(content: &str) -> Vec<String> {
+    let mut links: Vec<String> = vec![];
+
+    for (index, _) in content.match_indices("[[") {
+        let rest = &content[index + 2..];
+
+        let Some(end) = rest.find("]]") else {
+            break;
+        };
+
+        let target = rest[..end].split('|').next().unwrap_or_default();
+
+        if target.contains('[') {
+            continue;
+        }
+
+        let slug = slugify(target);
+
+        if !slug.is_empty() && !links.contains(&slug) {
+            links.push(slug);
+        }
+    }
+
+    links
+}
+
+/// Checks that an article title is valid, returning it trimmed. If it isn't, an error ready for
+/// presentation to the client is returned.
+pub fn validate_title(title: &str) -> Result<String, Error> {
+    let title = title.trim();
+
+    if slugify(title).is_empty() {
+        return Err(errors::create_user_input_error(
+            "Titles must contain at least one letter or number.",
+            "INVALID_TITLE",
+        ));
+    }
+
+    if title.len() > 128 {
+        return Err(errors::create_user_input_error(
+            "Titles cannot be longer than 128 characters.",
+            "TITLE_TOO_LONG",
+        ));
+    }
+
+    Ok(title.to_string())
+}
+
+/// Checks that the content of an article is valid. If it isn't, an error ready for presentation to
+/// the client is returned.
+pub fn validate_content(content: &str) -> Result<(), Error> {
+    if content.len() > MAX_CONTENT_LENGTH {
+        return Err(errors::create_user_input_error(
+            "Articles cannot be larger than 200 KB.",
+            "CONTENT_TOO_LONG",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that an edit summary is valid, returning it trimmed, or `None` if it is empty. If it
+/// isn't valid, an error ready for presentation to the client is returned.
+pub fn validate_summary(summary: Option<String>) -> Result<Option<String>, Error> {
+    let summary = summary
+        .map(|summary| summary.trim().to_string())
+        .filter(|summary| !summary.is_empty());
+
+    if summary.as_ref().is_some_and(|summary| summary.len() > 256) {
+        return Err(errors::create_user_input_error(
+            "Summaries cannot be longer than 256 characters.",
+            "SUMMARY_TOO_LONG",
+        ));
+    }
+
+    Ok(summary)
+}
+
+/// Compares two texts line by line. Lines that only appear in `old` are removed, and lines that
+/// only appear in `new` are added.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let line = |kind, content: &str| DiffLine {
+        kind,
+        content: content.to_string(),
+    };
+
+    let mut diff: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .map(|content| line(DiffKind::Unchanged, content))
+        .collect();
+
+    if old_middle.len() * new_middle.len() > MAX_DIFF_CELLS {
+        diff.extend(
+            old_middle
+                .iter()
+                .map(|content| line(DiffKind::Removed, content)),
+        );
+        diff.extend(
+            new_middle
+                .iter()
+                .map(|content| line(DiffKind::Added, content)),
+        );
+    } else {
+        // lengths[i][j] is the length of the longest common subsequence of old_middle[i..] and
+        // new_middle[j..]
+        let mut lengths = vec![vec![0usize; new_middle.len() + 1]; old_middle.len() + 1];
+
+        for i in (0..old_middle.len()).rev() {
+            for j in (0..new_middle.len()).rev() {
+                lengths[i][j] = if old_middle[i] == new_middle[j] {
+                    lengths[i + 1][j + 1] + 1
+                } else {
+                    lengths[i + 1][j].max(lengths[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+
+        while i < old_middle.len() || j < new_middle.len() {
+            if i < old_middle.len() && j < new_middle.len() && old_middle[i] == new_middle[j] {
+                diff.push(line(DiffKind::Unchanged, old_middle[i]));
+                i += 1;
+                j += 1;
+            } else if i < old_middle.len()
+                && (j == new_middle.len() || lengths[i + 1][j] >= lengths[i][j + 1])
+            {
+                diff.push(line(DiffKind::Removed, old_middle[i]));
+                i += 1;
+            } else {
+                diff.push(line(DiffKind::Added, new_middle[j]));
+                j += 1;
+            }
+        }
+    }
+
+    diff.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|content| line(DiffKind::Unchanged, content)),
+    );
+
+    diff
+}
+
+/// Checks that no other article in a wiki uses a slug. If one does, an error ready for
+/// presentation to the client is returned.
+pub async fn check_slug_available(
+    wiki: &str,
+    slug: &str,
+    article: Option<&str>,
+    db: &DatabaseConnection,
+) -> Result<(), Error> {
+    let mut query = wiki_article::Entity::find()
+        .filter(wiki_article::Column::Wiki.eq(wiki))
+        .filter(wiki_article::Column::Slug.eq(slug));
+
+    if let Some(article) = article {
+        query = query.filter(wiki_article::Column::Id.ne(article));
+    }
+
+    let existing = query
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_ARTICLE_ERROR"))?;
+
+    if existing.is_some() {
+        return Err(errors::create_user_input_error(
+            "An article with that title already exists.",
+            "TITLE_TAKEN",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Saves a new revision of an article, updating the article to match it. The title and content
+/// must already be validated.
+pub async fn save_revision<C: ConnectionTrait>(
+    article: wiki_article::Model,
+    title: String,
+    content: String,
+    summary: Option<String>,
+    author: String,
+    db: &C,
+) -> Result<wiki_article::Model, Error> {
+    let now = chrono::offset::Utc::now().naive_utc();
+    let number = article.revision + 1;
+
+    let revision = wiki_revision::ActiveModel {
+        id: ActiveValue::Set(nanoid!(16)),
+        article: ActiveValue::Set(article.id.clone()),
+        number: ActiveValue::Set(number),
+        title: ActiveValue::Set(title.clone()),
+        content: ActiveValue::Set(content.clone()),
+        summary: ActiveValue::Set(summary),
+        author: ActiveValue::Set(Some(author.clone())),
+        created: ActiveValue::Set(now),
+    };
+
+    wiki_revision::Entity::insert(revision)
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "REVISION_INSERTION_ERROR"))?;
+
+    let mut active_article: wiki_article::ActiveModel = article.into();
+    active_article.slug = ActiveValue::Set(slugify(&title));
+    active_article.title = ActiveValue::Set(title);
+    active_article.links = ActiveValue::Set(parse_links(&content));
+    active_article.content = ActiveValue::Set(content);
+    active_article.revision = ActiveValue::Set(number);
+    active_article.updated = ActiveValue::Set(now);
+    active_article.updated_by = ActiveValue::Set(Some(author));
+
+    active_article
+        .update(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+}
+
+#[async_trait]
+impl Component for WikiComponent {
+    fn name(&self) -> &'static str {
+        "wiki"
+    }
+
+    fn permission_category(&self) -> Option<PermissionCategory> {
+        Some(PermissionCategory::Wiki)
+    }
+
+    async fn create(
+        &self,
+        db: &DatabaseTransaction,
+        planet: &str,
+        _owner: &str,
+    ) -> Result<String, Error> {
+        let wiki = wiki::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            planet: ActiveValue::Set(planet.to_string()),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        let insertion = wiki::Entity::insert(wiki)
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "WIKI_INSERTION_ERROR"))?;
+
+        Ok(insertion.last_insert_id)
+    }
+
+    async fn delete(&self, db: &DatabaseTransaction, id: &str) -> Result<Option<Cleanup>, Error> {
+        let articles = Query::select()
+            .column(wiki_article::Column::Id)
+            .from(wiki_article::Entity)
+            .and_where(wiki_article::Column::Wiki.eq(id))
+            .to_owned();
+
+        wiki_revision::Entity::delete_many()
+            .filter(wiki_revision::Column::Article.in_subquery(articles))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REVISIONS_ERROR"))?;
+
+        wiki_article::Entity::delete_many()
+            .filter(wiki_article::Column::Wiki.eq(id))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_ARTICLES_ERROR"))?;
+
+        wiki::Entity::delete_by_id(id.to_string())
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_WIKI_ERROR"))?;
+
+        Ok(None)
+    }
+
+    async fn export(&self, db: &DatabaseConnection, id: &str) -> Result<String, Error> {
+        let wiki = wiki::Entity::find_by_id(id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "WIKI_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "WIKI_MISSING_ERROR",
+            ))?;
+
+        let articles = wiki
+            .find_related(wiki_article::Entity)
+            .order_by_asc(wiki_article::Column::Slug)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_ARTICLES_ERROR"))?;
+
+        let mut exported = vec![];
+
+        for article in articles {
+            let revisions = article
+                .find_related(wiki_revision::Entity)
+                .order_by_asc(wiki_revision::Column::Number)
+                .all(db)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_REVISIONS_ERROR"))?;
+
+            exported.push(json!({
+                "id": article.id,
+                "title": article.title,
+                "slug": article.slug,
+                "content": article.content,
+                "revisions": revisions
+                    .iter()
+                    .map(|revision| json!({
+                        "number": revision.number,
+                        "title": revision.title,
+                        "content": revision.content,
+                        "summary": revision.summary,
+                        "author": revision.author,
+                        "created": revision.created.to_string(),
+                    }))
+                    .collect::<Vec<_>>(),
+            }));
+        }
+
+        Ok(json!({ "articles": exported }).to_string())
+    }
+}
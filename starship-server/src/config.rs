@@ -0,0 +1,364 @@
+//! The server's configuration, read once at startup from a TOML file and the environment.
+//!
+//! Every setting can be given in the file, and most can be overridden by an environment variable,
+//! which is useful for secrets and containers. The file is read from `starship.toml` unless
+//! `CONFIG_PATH` points somewhere else. See `starship.example.toml` for every setting.
+
+use serde::Deserialize;
+use std::fmt;
+use std::{env, fs, io};
+
+/// The file the configuration is read from if `CONFIG_PATH` isn't set.
+const DEFAULT_CONFIG_PATH: &str = "starship.toml";
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub auth: AuthConfig,
+    pub cors: CorsConfig,
+    pub limits: LimitsConfig,
+    pub features: FeaturesConfig,
+    pub storage: StorageConfig,
+    pub paths: PathsConfig,
+    pub smtp: Option<SmtpConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// The name the server reports to clients.
+    pub name: String,
+    /// The IP address the server binds to. Overridden by `IP_ADDR`.
+    pub ip_addr: String,
+    /// The port the server binds to. Overridden by `PORT`.
+    pub port: u16,
+    /// The URL clients reach the server at, without a trailing slash. Overridden by `PUBLIC_URL`.
+    pub public_url: String,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            name: "starship-server-rs".to_string(),
+            ip_addr: "127.0.0.1".to_string(),
+            port: 8000,
+            public_url: "http://localhost:8000".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// A PostgreSQL database URL. Overridden by `DATABASE_URL`. Required.
+    pub url: String,
+    /// The most connections the server keeps open to the database at once.
+    pub max_connections: u32,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        DatabaseConfig {
+            url: String::new(),
+            max_connections: 10,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// The secret used for signing tokens. Overridden by `SECRET`. Required.
+    pub secret: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsConfig {
+    /// The origins browsers can make requests from, such as `https://starship.example`. Every
+    /// origin is allowed if this is empty. Overridden by `CORS_ORIGINS`, separated by commas.
+    pub origins: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// The most complex a GraphQL query can be.
+    pub query_complexity: usize,
+    /// The most deeply nested a GraphQL query can be.
+    pub query_depth: usize,
+    /// The number of bytes each user can store, unless their cap is waived. Overridden by
+    /// `USER_STORAGE_QUOTA`.
+    pub user_storage_quota: i64,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        LimitsConfig {
+            query_complexity: 1000,
+            query_depth: 10,
+            user_storage_quota: 1_000_000_000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeaturesConfig {
+    /// Whether or not new users can register.
+    pub registration: bool,
+    /// Whether or not users can send each other direct messages.
+    pub direct_messages: bool,
+    /// Whether or not GraphQL subscriptions are served over WebSockets.
+    pub subscriptions: bool,
+    /// Whether or not the GraphiQL playground is served.
+    pub playground: bool,
+    /// Whether or not Prometheus metrics are served at `/metrics`. Off by default, since anyone who
+    /// can reach the server can read them.
+    pub metrics: bool,
+}
+
+impl Default for FeaturesConfig {
+    fn default() -> Self {
+        FeaturesConfig {
+            registration: true,
+            direct_messages: true,
+            subscriptions: true,
+            playground: true,
+            metrics: false,
+        }
+    }
+}
+
+impl FeaturesConfig {
+    /// The names of the optional features that are turned on, as reported to clients.
+    pub fn enabled(&self) -> Vec<String> {
+        [
+            ("registration", self.registration),
+            ("direct_messages", self.direct_messages),
+            ("subscriptions", self.subscriptions),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name.to_string())
+        .collect()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+    /// The directory uploaded files are stored in. Overridden by `STORAGE_PATH`.
+    pub path: String,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        StorageConfig {
+            path: "storage".to_string(),
+        }
+    }
+}
+
+/// The public URLs clients load resources from. The server doesn't serve emojis, profile pictures
+/// or banners itself, so those are left empty unless they are set. The GraphQL endpoint is served
+/// from under the server's public URL if it isn't set.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathsConfig {
+    pub emoji_url: Option<String>,
+    pub pfp_url: Option<String>,
+    pub banner_url: Option<String>,
+    pub graphql_endpoint: Option<String>,
+}
+
+/// The mail server used to send verification emails. Users must verify their email before logging
+/// in when this is set.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpConfig {
+    /// The mail server's host name. Setting `SMTP_HOST` sets this.
+    pub host: String,
+}
+
+/// The public URLs clients load resources from, with the GraphQL endpoint filled in.
+pub struct ResolvedPaths {
+    pub emoji_url: Option<String>,
+    pub pfp_url: Option<String>,
+    pub banner_url: Option<String>,
+    pub graphql_endpoint: String,
+}
+
+/// A problem with the configuration that stops the server from starting.
+#[derive(Debug)]
+pub struct ConfigError(String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+impl Config {
+    /// Loads the configuration from its file and the environment, and checks that it is valid.
+    /// A missing file is only an error if `CONFIG_PATH` was set.
+    pub fn load() -> Result<Config, ConfigError> {
+        let (path, explicit) = match env::var("CONFIG_PATH") {
+            Ok(path) => (path, true),
+            Err(_) => (DEFAULT_CONFIG_PATH.to_string(), false),
+        };
+
+        let mut config = match fs::read_to_string(&path) {
+            Ok(contents) => Config::from_toml(&contents)
+                .map_err(|err| ConfigError(format!("{path}: {}", err.0)))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !explicit => Config::default(),
+            Err(err) => return Err(ConfigError(format!("couldn't read {path}: {err}"))),
+        };
+
+        config.apply_overrides(|name| env::var(name).ok())?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// Parses a configuration file. The result still needs to be validated.
+    pub fn from_toml(contents: &str) -> Result<Config, ConfigError> {
+        toml::from_str(contents).map_err(|err| ConfigError(err.message().to_string()))
+    }
+
+    /// Overrides settings with the environment variables that are set, using `lookup` to find
+    /// each variable's value.
+    pub fn apply_overrides(
+        &mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<(), ConfigError> {
+        if let Some(ip_addr) = lookup("IP_ADDR") {
+            self.server.ip_addr = ip_addr;
+        }
+
+        if let Some(port) = lookup("PORT") {
+            self.server.port = port
+                .parse()
+                .map_err(|_| ConfigError("PORT must be a port number".to_string()))?;
+        }
+
+        if let Some(public_url) = lookup("PUBLIC_URL") {
+            self.server.public_url = public_url;
+        }
+
+        if let Some(url) = lookup("DATABASE_URL") {
+            self.database.url = url;
+        }
+
+        if let Some(secret) = lookup("SECRET") {
+            self.auth.secret = secret;
+        }
+
+        if let Some(origins) = lookup("CORS_ORIGINS") {
+            self.cors.origins = origins
+                .split(',')
+                .map(str::trim)
+                .filter(|origin| !origin.is_empty())
+                .map(ToString::to_string)
+                .collect();
+        }
+
+        if let Some(quota) = lookup("USER_STORAGE_QUOTA") {
+            self.limits.user_storage_quota = quota.parse().map_err(|_| {
+                ConfigError("USER_STORAGE_QUOTA must be a number of bytes".to_string())
+            })?;
+        }
+
+        if let Some(path) = lookup("STORAGE_PATH") {
+            self.storage.path = path;
+        }
+
+        if let Some(host) = lookup("SMTP_HOST") {
+            self.smtp = Some(SmtpConfig { host });
+        }
+
+        Ok(())
+    }
+
+    /// Checks that every setting has a usable value.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.database.url.is_empty() {
+            return Err(ConfigError(
+                "database.url (or DATABASE_URL) must be set".to_string(),
+            ));
+        }
+
+        if self.auth.secret.is_empty() {
+            return Err(ConfigError(
+                "auth.secret (or SECRET) must be set".to_string(),
+            ));
+        }
+
+        if self.database.max_connections == 0 {
+            return Err(ConfigError(
+                "database.max_connections must be above 0".to_string(),
+            ));
+        }
+
+        if self.server.port == 0 {
+            return Err(ConfigError("server.port cannot be 0".to_string()));
+        }
+
+        if !is_http_url(&self.server.public_url) || self.server.public_url.ends_with('/') {
+            return Err(ConfigError(
+                "server.public_url must be an http(s) URL without a trailing slash".to_string(),
+            ));
+        }
+
+        if let Some(origin) = self.cors.origins.iter().find(|origin| !is_http_url(origin)) {
+            return Err(ConfigError(format!(
+                "CORS origin {origin} must be an http(s) URL"
+            )));
+        }
+
+        if self.limits.query_complexity == 0 || self.limits.query_depth == 0 {
+            return Err(ConfigError(
+                "limits.query_complexity and limits.query_depth must be above 0".to_string(),
+            ));
+        }
+
+        if self.limits.user_storage_quota < 0 {
+            return Err(ConfigError(
+                "limits.user_storage_quota cannot be negative".to_string(),
+            ));
+        }
+
+        if self.storage.path.is_empty() {
+            return Err(ConfigError("storage.path cannot be empty".to_string()));
+        }
+
+        if self.smtp.as_ref().is_some_and(|smtp| smtp.host.is_empty()) {
+            return Err(ConfigError("smtp.host cannot be empty".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Gets the public URLs clients load resources from, filling in the GraphQL endpoint if it
+    /// isn't set.
+    pub fn paths(&self) -> ResolvedPaths {
+        ResolvedPaths {
+            emoji_url: self.paths.emoji_url.clone(),
+            pfp_url: self.paths.pfp_url.clone(),
+            banner_url: self.paths.banner_url.clone(),
+            graphql_endpoint: self
+                .paths
+                .graphql_endpoint
+                .clone()
+                .unwrap_or_else(|| format!("{}/graphql", self.server.public_url)),
+        }
+    }
+}
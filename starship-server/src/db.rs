@@ -1,14 +1,43 @@
+use crate::config::DatabaseConfig;
+use crate::errors;
+use async_graphql::Error;
 use log::error;
-use sea_orm::{prelude::*, ConnectionTrait, Database, DatabaseBackend};
-use std::env;
+use sea_orm::{
+    prelude::*, DatabaseTransaction, RuntimeErr, SqlxPostgresConnector, TransactionTrait,
+};
+use sqlx::postgres::{PgPool, PgPoolOptions};
 
-pub async fn set_up() -> Result<DatabaseConnection, DbErr> {
-    let db = Database::connect(env::var("DATABASE_URL").expect("fatal: no database url")).await?;
-
-    if db.get_database_backend() != DatabaseBackend::Postgres {
+/// Connects to the database. The connection pool is returned alongside the connection so that its
+/// usage can be reported.
+pub async fn set_up(config: &DatabaseConfig) -> Result<(DatabaseConnection, PgPool), DbErr> {
+    if !SqlxPostgresConnector::accepts(&config.url) {
         error!("Starship only supports PostgreSQL at this time. Consider using PostgreSQL.");
         panic!("fatal: unsupported sql server, impossible to continue")
     }
 
-    Ok(db)
+    let pool = PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .connect(&config.url)
+        .await
+        .map_err(|err| DbErr::Conn(RuntimeErr::SqlxError(err)))?;
+
+    Ok((
+        SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone()),
+        pool,
+    ))
+}
+
+/// Starts a transaction, for mutations that make several changes that need to happen together,
+/// such as a change and its audit log entry.
+pub async fn begin(db: &DatabaseConnection) -> Result<DatabaseTransaction, Error> {
+    db.begin()
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "TRANSACTION_BEGIN_ERROR"))
+}
+
+/// Commits a transaction started with [`begin`].
+pub async fn commit(txn: DatabaseTransaction) -> Result<(), Error> {
+    txn.commit()
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "TRANSACTION_COMMIT_ERROR"))
 }
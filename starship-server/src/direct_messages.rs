@@ -0,0 +1,87 @@
+use crate::config::Config;
+use crate::entities::{conversation, conversation_member, user};
+use crate::errors;
+use async_graphql::{Context, Error};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+
+/// The most users a conversation can have, including whoever started it.
+pub const MAX_CONVERSATION_MEMBERS: usize = 10;
+
+/// Checks that direct messages are turned on for this server.
+pub fn check_enabled(ctx: &Context<'_>) -> Result<(), Error> {
+    if ctx.data::<Config>().unwrap().features.direct_messages {
+        Ok(())
+    } else {
+        Err(errors::create_forbidden_error(
+            Some("Direct messages are disabled on this server."),
+            "DIRECT_MESSAGES_DISABLED",
+        ))
+    }
+}
+
+/// Checks that a group conversation's name is valid, returning it without surrounding whitespace.
+/// Empty names are treated as no name. If it isn't valid, an error ready for presentation to the
+/// client is returned.
+pub fn validate_name(name: Option<String>) -> Result<Option<String>, Error> {
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    if name.as_ref().is_some_and(|name| name.len() > 100) {
+        return Err(errors::create_user_input_error(
+            "Conversation names cannot be longer than 100 characters.",
+            "NAME_TOO_LONG",
+        ));
+    }
+
+    Ok(name)
+}
+
+/// Checks whether or not any of a set of users has blocked a user.
+pub fn blocked_by_any(user_id: &str, users: &[user::Model]) -> bool {
+    users
+        .iter()
+        .any(|user| user.blocked.iter().any(|blocked| blocked == user_id))
+}
+
+/// Gets the conversation with an ID, along with its members, if the user is one of them. Anyone
+/// else gets a not found error, so that conversations can't be discovered by their ID.
+pub async fn get_conversation(
+    id: String,
+    user_id: &str,
+    db: &DatabaseConnection,
+) -> Result<(conversation::Model, Vec<conversation_member::Model>), Error> {
+    let (conversation, members) = conversation::Entity::find_by_id(id)
+        .find_with_related(conversation_member::Entity)
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_CONVERSATION_ERROR"))?
+        .into_iter()
+        .next()
+        .ok_or(errors::create_not_found_error())?;
+
+    if !members.iter().any(|member| member.user == user_id) {
+        return Err(errors::create_not_found_error());
+    }
+
+    Ok((conversation, members))
+}
+
+/// Gets the users a user shares a conversation with, not including the user.
+pub async fn other_members(
+    members: &[conversation_member::Model],
+    user_id: &str,
+    db: &DatabaseConnection,
+) -> Result<Vec<user::Model>, Error> {
+    let ids: Vec<String> = members
+        .iter()
+        .filter(|member| member.user != user_id)
+        .map(|member| member.user.clone())
+        .collect();
+
+    user::Entity::find()
+        .filter(user::Column::Id.is_in(ids))
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_MEMBERS_ERROR"))
+}
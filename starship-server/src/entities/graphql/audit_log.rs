@@ -0,0 +1,71 @@
+use super::super::audit_log::{AuditAction, Model};
+use super::super::planet;
+use super::super::user;
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+
+#[Object(
+    name = "AuditLogEntry",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 5)]
+    async fn planet(&self, ctx: &Context<'_>) -> Result<planet::Model, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .planets
+            .load_one(self.planet.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_PLANET_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "PLANET_MISSING_ERROR",
+            ))
+    }
+
+    /// The user that performed the action. This is empty for actions performed by the server.
+    #[graphql(complexity = 5)]
+    async fn actor(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        match &self.actor {
+            Some(id) => loaders
+                .users
+                .load_one(id.clone())
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_ACTOR_ERROR")),
+            None => Ok(None),
+        }
+    }
+
+    #[graphql(complexity = 0)]
+    async fn action(&self) -> AuditAction {
+        self.action
+    }
+
+    /// The ID of the object the action was performed on.
+    #[graphql(complexity = 0)]
+    async fn target(&self) -> Option<ID> {
+        self.target.clone().map(ID)
+    }
+
+    #[graphql(complexity = 0)]
+    async fn details(&self) -> &Option<String> {
+        &self.details
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+}
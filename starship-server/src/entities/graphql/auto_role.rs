@@ -0,0 +1,62 @@
+use super::super::auto_role::{AutoRoleTrigger, Model};
+use super::super::planet_role;
+use super::super::user;
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+
+#[Object(
+    name = "AutoRole",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 5)]
+    async fn role(&self, ctx: &Context<'_>) -> Result<planet_role::Model, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .roles
+            .load_one(self.role.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_ROLE_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "ROLE_MISSING_ERROR",
+            ))
+    }
+
+    #[graphql(complexity = 0)]
+    async fn trigger(&self) -> AutoRoleTrigger {
+        self.trigger
+    }
+
+    /// How many days members must have been on the planet for, if the rule is based on that.
+    #[graphql(complexity = 0)]
+    async fn days(&self) -> Option<i32> {
+        self.days
+    }
+
+    #[graphql(complexity = 5)]
+    async fn created_by(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.created_by.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+}
@@ -0,0 +1,221 @@
+use super::super::{chat, chat_message};
+use super::cursor::{cursor_condition, TimestampCursor};
+use crate::components::access::ComponentAccess;
+use crate::components::chat::search_query;
+use crate::errors;
+use crate::sessions::Session;
+use async_graphql::connection::{self, Connection, Edge};
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, Order, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Select,
+};
+
+/// The number of messages returned if neither `first` nor `last` is provided.
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// The largest number of messages that can be requested at once.
+const MAX_PAGE_SIZE: usize = 100;
+
+impl From<&chat_message::Model> for TimestampCursor {
+    fn from(message: &chat_message::Model) -> Self {
+        TimestampCursor {
+            created: message.created,
+            id: message.id.clone(),
+        }
+    }
+}
+
+/// Calculates the complexity of a message connection field from the requested page size.
+fn messages_complexity(first: Option<i32>, last: Option<i32>, child_complexity: usize) -> usize {
+    let size = first.or(last).map_or(DEFAULT_PAGE_SIZE, |size| {
+        size.clamp(0, MAX_PAGE_SIZE as i32) as usize
+    });
+
+    5 * size + size * child_complexity
+}
+
+/// Builds the query for a page of a chat's messages between two cursors. One more message than
+/// `limit` is retrieved, so that [`messages_page`] can tell whether there are more to come.
+pub fn messages_query(
+    chat_id: &str,
+    after: Option<TimestampCursor>,
+    before: Option<TimestampCursor>,
+    limit: usize,
+    backwards: bool,
+) -> Select<chat_message::Entity> {
+    let mut query = chat_message::Entity::find().filter(chat_message::Column::Chat.eq(chat_id));
+
+    if let Some(after) = after {
+        query = query.filter(cursor_condition(
+            chat_message::Column::Created,
+            chat_message::Column::Id,
+            after,
+            true,
+            true,
+        ));
+    }
+
+    if let Some(before) = before {
+        query = query.filter(cursor_condition(
+            chat_message::Column::Created,
+            chat_message::Column::Id,
+            before,
+            true,
+            false,
+        ));
+    }
+
+    // when paginating backwards, the messages closest to the cursor are retrieved by reversing the
+    // order, and put back in order by `messages_page`
+    let query_order = if backwards { Order::Desc } else { Order::Asc };
+
+    query
+        .order_by(chat_message::Column::Created, query_order.clone())
+        .order_by(chat_message::Column::Id, query_order)
+        .limit(limit as u64 + 1)
+}
+
+/// Turns the messages retrieved by [`messages_query`] into a page of a connection, oldest first.
+pub fn messages_page(
+    mut messages: Vec<chat_message::Model>,
+    limit: usize,
+    backwards: bool,
+    has_after: bool,
+    has_before: bool,
+) -> Connection<TimestampCursor, chat_message::Model> {
+    let has_more = messages.len() > limit;
+    messages.truncate(limit);
+
+    let mut connection = if backwards {
+        messages.reverse();
+        Connection::new(has_more, has_before)
+    } else {
+        Connection::new(has_after, has_more)
+    };
+
+    connection.edges.extend(
+        messages
+            .into_iter()
+            .map(|message| Edge::new(TimestampCursor::from(&message), message)),
+    );
+
+    connection
+}
+
+#[Object(name = "Chat", rename_fields = "camelCase", rename_args = "camelCase")]
+impl chat::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn topic(&self) -> &Option<String> {
+        &self.topic
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// Retrieves the chat's messages as a Relay connection, oldest first. To load the most recent
+    /// messages, request the `last` messages, and page back through the history with `before`.
+    #[graphql(complexity = "messages_complexity(first, last, child_complexity)")]
+    async fn messages(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<TimestampCursor, chat_message::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        connection::query(
+            after,
+            before,
+            first,
+            last,
+            |after: Option<TimestampCursor>, before: Option<TimestampCursor>, first, last| async move {
+                let backwards = last.is_some();
+                let limit = first
+                    .or(last)
+                    .unwrap_or(DEFAULT_PAGE_SIZE)
+                    .min(MAX_PAGE_SIZE);
+
+                let has_after = after.is_some();
+                let has_before = before.is_some();
+
+                let messages = messages_query(&self.id, after, before, limit, backwards)
+                    .all(db)
+                    .await
+                    .map_err(|_| {
+                        errors::create_internal_server_error(None, "FIND_MESSAGES_ERROR")
+                    })?;
+
+                Ok::<_, Error>(messages_page(
+                    messages, limit, backwards, has_after, has_before,
+                ))
+            },
+        )
+        .await
+    }
+
+    /// Finds a message in this chat from its ID.
+    #[graphql(complexity = 5)]
+    async fn message(&self, ctx: &Context<'_>, id: ID) -> Result<chat_message::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(chat_message::Entity)
+            .filter(chat_message::Column::Id.eq(id.to_string()))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_MESSAGE_ERROR"))?
+            .ok_or(errors::create_not_found_error())
+    }
+
+    /// Retrieves the chat's pinned messages, most recent first.
+    #[graphql(complexity = 20)]
+    async fn pinned_messages(&self, ctx: &Context<'_>) -> Result<Vec<chat_message::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(chat_message::Entity)
+            .filter(chat_message::Column::Pinned.eq(true))
+            .order_by_desc(chat_message::Column::Created)
+            .order_by_desc(chat_message::Column::Id)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_MESSAGES_ERROR"))
+    }
+
+    /// Searches the chat's messages for some text, ignoring case. The most recent messages are
+    /// listed first. Requires the `chat.search` permission.
+    #[graphql(complexity = "10 * size as usize + size as usize * child_complexity")]
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        size: u64,
+        page: u64,
+    ) -> Result<Vec<chat_message::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let access = ComponentAccess::load_by_data(self.id.clone(), "chat", user_id, db).await?;
+
+        let Some(query) = search_query(&access, &self.id, &query)? else {
+            return Ok(vec![]);
+        };
+
+        query
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "SEARCH_MESSAGES_ERROR"))
+    }
+}
@@ -0,0 +1,56 @@
+use super::super::component_permission::Model;
+use super::super::planet_member;
+use super::super::planet_role;
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+#[Object(
+    name = "ComponentPermissionOverride",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    /// The role the override applies to. If neither a role nor a member is set, the override
+    /// applies to everyone.
+    #[graphql(complexity = 5)]
+    async fn role(&self, ctx: &Context<'_>) -> Result<Option<planet_role::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        match &self.role {
+            Some(id) => loaders
+                .roles
+                .load_one(id.clone())
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_ROLE_ERROR")),
+            None => Ok(None),
+        }
+    }
+
+    /// The member the override applies to. If neither a role nor a member is set, the override
+    /// applies to everyone.
+    #[graphql(complexity = 5)]
+    async fn member(&self, ctx: &Context<'_>) -> Result<Option<planet_member::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        match &self.member {
+            Some(id) => planet_member::Entity::find_by_id(id.clone())
+                .one(db)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_MEMBER_ERROR")),
+            None => Ok(None),
+        }
+    }
+
+    #[graphql(complexity = 0)]
+    async fn permissions(&self) -> &Vec<String> {
+        &self.permissions
+    }
+}
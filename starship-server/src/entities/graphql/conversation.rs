@@ -0,0 +1,219 @@
+use super::super::{conversation, conversation_member, direct_message, user};
+use super::cursor::{cursor_condition, TimestampCursor};
+use crate::errors;
+use crate::loaders::Loaders;
+use crate::sessions::Session;
+use async_graphql::connection::{self, Connection, Edge};
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::Query;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, Order, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+
+/// The number of messages returned if neither `first` nor `last` is provided.
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// The largest number of messages that can be requested at once.
+const MAX_PAGE_SIZE: usize = 100;
+
+impl From<&direct_message::Model> for TimestampCursor {
+    fn from(message: &direct_message::Model) -> Self {
+        TimestampCursor {
+            created: message.created,
+            id: message.id.clone(),
+        }
+    }
+}
+
+/// Calculates the complexity of a message connection field from the requested page size.
+fn messages_complexity(first: Option<i32>, last: Option<i32>, child_complexity: usize) -> usize {
+    let size = first.or(last).map_or(DEFAULT_PAGE_SIZE, |size| {
+        size.clamp(0, MAX_PAGE_SIZE as i32) as usize
+    });
+
+    5 * size + size * child_complexity
+}
+
+/// Gets the users the current user has blocked, whose messages are hidden from them.
+fn blocked_users(ctx: &Context<'_>) -> Vec<String> {
+    ctx.data::<Session>()
+        .ok()
+        .and_then(|session| session.user.as_ref())
+        .map(|user| user.blocked.clone())
+        .unwrap_or_default()
+}
+
+#[Object(
+    name = "Conversation",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl conversation::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    /// The conversation's name. Only group conversations can be named.
+    #[graphql(complexity = 0)]
+    async fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    /// Whether this is a group conversation, rather than a one-to-one conversation.
+    #[graphql(complexity = 0)]
+    async fn group(&self) -> bool {
+        self.group
+    }
+
+    #[graphql(complexity = 5)]
+    async fn creator(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.creator.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))
+    }
+
+    /// The users in the conversation, including the current user.
+    #[graphql(complexity = 20)]
+    async fn members(&self, ctx: &Context<'_>) -> Result<Vec<user::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let memberships = Query::select()
+            .column(conversation_member::Column::User)
+            .from(conversation_member::Entity)
+            .and_where(conversation_member::Column::Conversation.eq(self.id.clone()))
+            .to_owned();
+
+        user::Entity::find()
+            .filter(user::Column::Id.in_subquery(memberships))
+            .order_by_asc(user::Column::Username)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_MEMBERS_ERROR"))
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// When the last message was sent to the conversation.
+    #[graphql(complexity = 0)]
+    async fn updated_at(&self) -> NaiveDateTime {
+        self.updated
+    }
+
+    /// Retrieves the conversation's messages as a Relay connection, oldest first. To load the most
+    /// recent messages, request the `last` messages, and page back through the history with
+    /// `before`. Messages from users you have blocked aren't included.
+    #[graphql(complexity = "messages_complexity(first, last, child_complexity)")]
+    async fn messages(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<TimestampCursor, direct_message::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let blocked = blocked_users(ctx);
+
+        connection::query(
+            after,
+            before,
+            first,
+            last,
+            |after: Option<TimestampCursor>, before: Option<TimestampCursor>, first, last| async move {
+                let backwards = last.is_some();
+                let limit = first
+                    .or(last)
+                    .unwrap_or(DEFAULT_PAGE_SIZE)
+                    .min(MAX_PAGE_SIZE);
+
+                let mut query = self
+                    .find_related(direct_message::Entity)
+                    .filter(direct_message::Column::Author.is_not_in(blocked));
+
+                let has_after = after.is_some();
+                let has_before = before.is_some();
+
+                if let Some(after) = after {
+                    query = query.filter(cursor_condition(
+                        direct_message::Column::Created,
+                        direct_message::Column::Id,
+                        after,
+                        true,
+                        true,
+                    ));
+                }
+
+                if let Some(before) = before {
+                    query = query.filter(cursor_condition(
+                        direct_message::Column::Created,
+                        direct_message::Column::Id,
+                        before,
+                        true,
+                        false,
+                    ));
+                }
+
+                // when paginating backwards, the messages closest to the cursor are retrieved by
+                // reversing the order, and put back in order afterwards
+                let query_order = if backwards { Order::Desc } else { Order::Asc };
+
+                let mut messages = query
+                    .order_by(direct_message::Column::Created, query_order.clone())
+                    .order_by(direct_message::Column::Id, query_order)
+                    .limit(limit as u64 + 1)
+                    .all(db)
+                    .await
+                    .map_err(|_| {
+                        errors::create_internal_server_error(None, "FIND_MESSAGES_ERROR")
+                    })?;
+
+                let has_more = messages.len() > limit;
+                messages.truncate(limit);
+
+                let mut connection = if backwards {
+                    messages.reverse();
+                    Connection::new(has_more, has_before)
+                } else {
+                    Connection::new(has_after, has_more)
+                };
+
+                connection.edges.extend(
+                    messages
+                        .into_iter()
+                        .map(|message| Edge::new(TimestampCursor::from(&message), message)),
+                );
+
+                Ok::<_, Error>(connection)
+            },
+        )
+        .await
+    }
+
+    /// The most recent message in the conversation that isn't from a user you have blocked.
+    #[graphql(complexity = 5)]
+    async fn last_message(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<direct_message::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(direct_message::Entity)
+            .filter(direct_message::Column::Author.is_not_in(blocked_users(ctx)))
+            .order_by_desc(direct_message::Column::Created)
+            .order_by_desc(direct_message::Column::Id)
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_MESSAGE_ERROR"))
+    }
+}
@@ -0,0 +1,55 @@
+use async_graphql::connection::CursorType;
+use chrono::NaiveDateTime;
+use sea_orm::{ColumnTrait, Condition};
+
+const CURSOR_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+/// Identifies a row's position in a list sorted by creation date, so that pages stay stable while
+/// rows are added and removed.
+pub struct TimestampCursor {
+    pub created: NaiveDateTime,
+    pub id: String,
+}
+
+impl CursorType for TimestampCursor {
+    type Error = &'static str;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        let (created, id) = s.split_once('|').ok_or("invalid cursor")?;
+        let created = NaiveDateTime::parse_from_str(created, CURSOR_DATE_FORMAT)
+            .map_err(|_| "invalid cursor")?;
+
+        Ok(TimestampCursor {
+            created,
+            id: id.to_string(),
+        })
+    }
+
+    fn encode_cursor(&self) -> String {
+        format!("{}|{}", self.created.format(CURSOR_DATE_FORMAT), self.id)
+    }
+}
+
+/// Creates a condition matching rows listed after (or before, if `after` is false) the cursor
+/// when the rows are sorted by the `created` and then `id` columns.
+pub fn cursor_condition<C: ColumnTrait>(
+    created: C,
+    id: C,
+    cursor: TimestampCursor,
+    ascending: bool,
+    after: bool,
+) -> Condition {
+    let later = ascending == after;
+
+    let (created_condition, id_condition) = if later {
+        (created.gt(cursor.created), id.gt(cursor.id))
+    } else {
+        (created.lt(cursor.created), id.lt(cursor.id))
+    };
+
+    Condition::any().add(created_condition).add(
+        Condition::all()
+            .add(created.eq(cursor.created))
+            .add(id_condition),
+    )
+}
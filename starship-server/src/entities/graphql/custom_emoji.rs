@@ -2,9 +2,9 @@ use super::super::custom_emoji::Model;
 use super::super::planet;
 use super::super::user;
 use crate::errors;
+use crate::loaders::Loaders;
 use async_graphql::types::ID;
 use async_graphql::{Context, Error, Object};
-use sea_orm::{DatabaseConnection, ModelTrait};
 
 #[Object(
     name = "CustomEmoji",
@@ -19,10 +19,11 @@ impl Model {
 
     #[graphql(complexity = 5)]
     async fn owner(&self, ctx: &Context<'_>) -> Result<user::Model, Error> {
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
-        self.find_related(user::Entity)
-            .one(db)
+        loaders
+            .users
+            .load_one(self.owner.clone())
             .await
             .map_err(|_| errors::create_internal_server_error(None, "FIND_OWNER_ERROR"))?
             .ok_or(errors::create_internal_server_error(
@@ -33,12 +34,12 @@ impl Model {
 
     #[graphql(complexity = 5)]
     async fn planet(&self, ctx: &Context<'_>) -> Result<Option<planet::Model>, Error> {
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
-        match self.planet.clone() {
-            Some(_id) => self
-                .find_related(planet::Entity)
-                .one(db)
+        match &self.planet {
+            Some(id) => loaders
+                .planets
+                .load_one(id.clone())
                 .await
                 .map_err(|_| errors::create_internal_server_error(None, "FIND_PLANET_ERROR"))?
                 .ok_or(errors::create_internal_server_error(
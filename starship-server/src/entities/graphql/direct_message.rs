@@ -0,0 +1,50 @@
+use super::super::{direct_message, user};
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+
+#[Object(
+    name = "DirectMessage",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl direct_message::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn conversation_id(&self) -> ID {
+        ID(self.conversation.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn content(&self) -> &String {
+        &self.content
+    }
+
+    #[graphql(complexity = 5)]
+    async fn author(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.author.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// When the message was last edited, if it has been edited.
+    #[graphql(complexity = 0)]
+    async fn edited_at(&self) -> Option<NaiveDateTime> {
+        self.edited
+    }
+}
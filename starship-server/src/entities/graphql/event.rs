@@ -0,0 +1,179 @@
+use super::super::event::{self, Recurrence};
+use super::super::event_rsvp::{self, RsvpStatus};
+use super::super::user;
+use crate::components::events::occurrences;
+use crate::errors;
+use crate::loaders::Loaders;
+use crate::sessions::Session;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object, SimpleObject};
+use chrono::NaiveDateTime;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+
+/// How many users gave each response to an event.
+#[derive(SimpleObject)]
+pub struct RsvpCounts {
+    pub going: u64,
+    pub maybe: u64,
+    pub not_going: u64,
+}
+
+#[Object(name = "Event", rename_fields = "camelCase", rename_args = "camelCase")]
+impl event::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn title(&self) -> &String {
+        &self.title
+    }
+
+    #[graphql(complexity = 0)]
+    async fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    #[graphql(complexity = 0)]
+    async fn location(&self) -> &Option<String> {
+        &self.location
+    }
+
+    /// When the event first starts, in UTC.
+    #[graphql(complexity = 0)]
+    async fn start(&self) -> NaiveDateTime {
+        self.start
+    }
+
+    /// When the event first ends, in UTC.
+    #[graphql(complexity = 0)]
+    async fn end(&self) -> NaiveDateTime {
+        self.end
+    }
+
+    /// The IANA name of the timezone the event is planned in.
+    #[graphql(complexity = 0)]
+    async fn timezone(&self) -> &String {
+        &self.timezone
+    }
+
+    /// How often the event repeats, if it is recurring.
+    #[graphql(complexity = 0)]
+    async fn recurrence(&self) -> Option<Recurrence> {
+        self.recurrence
+    }
+
+    #[graphql(complexity = 0)]
+    async fn recurrence_interval(&self) -> i32 {
+        self.recurrence_interval
+    }
+
+    #[graphql(complexity = 0)]
+    async fn recurrence_count(&self) -> Option<i32> {
+        self.recurrence_count
+    }
+
+    #[graphql(complexity = 0)]
+    async fn recurrence_until(&self) -> Option<NaiveDateTime> {
+        self.recurrence_until
+    }
+
+    /// When the event next starts, or the current occurrence started if it is happening now.
+    #[graphql(complexity = 0)]
+    async fn next_occurrence(&self) -> Option<NaiveDateTime> {
+        let now = chrono::offset::Utc::now().naive_utc();
+
+        occurrences(self, now, NaiveDateTime::MAX, 1)
+            .first()
+            .map(|(start, _)| *start)
+    }
+
+    #[graphql(complexity = 5)]
+    async fn creator(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.creator.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// When the event was last edited, if it has been edited.
+    #[graphql(complexity = 0)]
+    async fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated
+    }
+
+    /// Retrieves the responses to the event, oldest first, optionally only with one status.
+    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    async fn rsvps(
+        &self,
+        ctx: &Context<'_>,
+        size: u64,
+        page: u64,
+        status: Option<RsvpStatus>,
+    ) -> Result<Vec<event_rsvp::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let mut query = self.find_related(event_rsvp::Entity);
+
+        if let Some(status) = status {
+            query = query.filter(event_rsvp::Column::Status.eq(status));
+        }
+
+        query
+            .order_by_asc(event_rsvp::Column::Created)
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_RSVPS_ERROR"))
+    }
+
+    #[graphql(complexity = 15)]
+    async fn rsvp_counts(&self, ctx: &Context<'_>) -> Result<RsvpCounts, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let count = |status: RsvpStatus| {
+            self.find_related(event_rsvp::Entity)
+                .filter(event_rsvp::Column::Status.eq(status))
+                .count(db)
+        };
+
+        let error = |_| errors::create_internal_server_error(None, "COUNT_RSVPS_ERROR");
+
+        Ok(RsvpCounts {
+            going: count(RsvpStatus::Going).await.map_err(error)?,
+            maybe: count(RsvpStatus::Maybe).await.map_err(error)?,
+            not_going: count(RsvpStatus::NotGoing).await.map_err(error)?,
+        })
+    }
+
+    /// The current user's response to the event, if they have responded.
+    #[graphql(complexity = 5)]
+    async fn my_rsvp(&self, ctx: &Context<'_>) -> Result<Option<RsvpStatus>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+
+        let Some(user) = &session.user else {
+            return Ok(None);
+        };
+
+        let rsvp = self
+            .find_related(event_rsvp::Entity)
+            .filter(event_rsvp::Column::User.eq(user.id.clone()))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_RSVP_ERROR"))?;
+
+        Ok(rsvp.map(|rsvp| rsvp.status))
+    }
+}
@@ -0,0 +1,129 @@
+use super::super::{event, event_calendar};
+use crate::components::events::occurrences;
+use crate::errors;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object, SimpleObject};
+use chrono::{Duration, NaiveDateTime};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+
+/// The longest range of time occurrences can be listed for, in days.
+const MAX_OCCURRENCE_RANGE_DAYS: i64 = 366;
+
+/// The largest number of occurrences that can be listed at once.
+const MAX_OCCURRENCES: usize = 500;
+
+/// A time that an event takes place. Recurring events have an occurrence for each time they
+/// repeat.
+#[derive(SimpleObject)]
+pub struct EventOccurrence {
+    pub event: event::Model,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+#[Object(
+    name = "EventCalendar",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl event_calendar::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// Retrieves the calendar's events, ordered by when they first start.
+    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        size: u64,
+        page: u64,
+    ) -> Result<Vec<event::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(event::Entity)
+            .order_by_asc(event::Column::Start)
+            .order_by_asc(event::Column::Id)
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_EVENTS_ERROR"))
+    }
+
+    /// Finds an event in this calendar from its ID.
+    #[graphql(complexity = 5)]
+    async fn event(&self, ctx: &Context<'_>, id: ID) -> Result<event::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(event::Entity)
+            .filter(event::Column::Id.eq(id.to_string()))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_EVENT_ERROR"))?
+            .ok_or(errors::create_not_found_error())
+    }
+
+    /// Lists the times the calendar's events take place between two times, with recurring events
+    /// expanded into each time they repeat. The range can be at most 366 days long.
+    #[graphql(complexity = 200)]
+    async fn occurrences(
+        &self,
+        ctx: &Context<'_>,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<EventOccurrence>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        if to <= from || to - from > Duration::days(MAX_OCCURRENCE_RANGE_DAYS) {
+            return Err(errors::create_user_input_error(
+                "Occurrences can only be listed for up to 366 days at a time.",
+                "INVALID_RANGE",
+            ));
+        }
+
+        // recurring events can have occurrences in the range as long as they start before it ends
+        let events = self
+            .find_related(event::Entity)
+            .filter(event::Column::Start.lt(to))
+            .filter(
+                Condition::any()
+                    .add(event::Column::Recurrence.is_not_null())
+                    .add(event::Column::End.gt(from)),
+            )
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_EVENTS_ERROR"))?;
+
+        let mut found: Vec<EventOccurrence> = events
+            .into_iter()
+            .flat_map(|event| {
+                occurrences(&event, from, to, MAX_OCCURRENCES)
+                    .into_iter()
+                    .map(move |(start, end)| EventOccurrence {
+                        event: event.clone(),
+                        start,
+                        end,
+                    })
+            })
+            .collect();
+
+        found.sort_by(|a, b| (a.start, &a.event.id).cmp(&(b.start, &b.event.id)));
+        found.truncate(MAX_OCCURRENCES);
+
+        Ok(found)
+    }
+
+    /// The link to this calendar's iCalendar feed, which calendar apps can subscribe to.
+    #[graphql(complexity = 0)]
+    async fn feed_url(&self) -> String {
+        format!("/events/{}/calendar.ics", self.id)
+    }
+}
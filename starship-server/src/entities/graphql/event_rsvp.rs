@@ -0,0 +1,59 @@
+use super::super::event_rsvp::{self, RsvpStatus};
+use super::super::{event, user};
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::{DatabaseConnection, ModelTrait};
+
+#[Object(
+    name = "EventRsvp",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl event_rsvp::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 5)]
+    async fn event(&self, ctx: &Context<'_>) -> Result<event::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(event::Entity)
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_EVENT_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "EVENT_MISSING_ERROR",
+            ))
+    }
+
+    #[graphql(complexity = 5)]
+    async fn user(&self, ctx: &Context<'_>) -> Result<user::Model, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.user.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "USER_MISSING_ERROR",
+            ))
+    }
+
+    #[graphql(complexity = 0)]
+    async fn status(&self) -> RsvpStatus {
+        self.status
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+}
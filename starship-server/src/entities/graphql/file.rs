@@ -0,0 +1,65 @@
+use super::super::{file, file_folder, user};
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::{DatabaseConnection, ModelTrait};
+
+#[Object(name = "File", rename_fields = "camelCase", rename_args = "camelCase")]
+impl file::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// The size of the file in bytes.
+    #[graphql(complexity = 0)]
+    async fn size(&self) -> i64 {
+        self.size
+    }
+
+    #[graphql(complexity = 0)]
+    async fn mime_type(&self) -> &String {
+        &self.mime_type
+    }
+
+    #[graphql(complexity = 5)]
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.owner.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))
+    }
+
+    /// The folder the file is inside of, if it isn't at the top of the tree.
+    #[graphql(complexity = 5)]
+    async fn folder(&self, ctx: &Context<'_>) -> Result<Option<file_folder::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(file_folder::Entity)
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FOLDER_ERROR"))
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// The path the file can be downloaded from, relative to the server. Downloading requires the
+    /// `files.files.download` permission.
+    #[graphql(complexity = 0)]
+    async fn download_url(&self) -> String {
+        format!("/files/download/{}", self.id)
+    }
+}
@@ -0,0 +1,79 @@
+use super::super::{file, file_folder, user};
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter, QueryOrder};
+
+#[Object(
+    name = "FileFolder",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl file_folder::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn name(&self) -> &String {
+        &self.name
+    }
+
+    #[graphql(complexity = 5)]
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.owner.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))
+    }
+
+    /// The folder this folder is inside of, if it isn't at the top of the tree.
+    #[graphql(complexity = 5)]
+    async fn parent(&self, ctx: &Context<'_>) -> Result<Option<file_folder::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        match &self.parent {
+            Some(parent) => file_folder::Entity::find_by_id(parent.clone())
+                .one(db)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_FOLDER_ERROR")),
+            None => Ok(None),
+        }
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// Retrieves the folders inside of this folder, in alphabetical order.
+    #[graphql(complexity = 20)]
+    async fn folders(&self, ctx: &Context<'_>) -> Result<Vec<file_folder::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        file_folder::Entity::find()
+            .filter(file_folder::Column::Parent.eq(self.id.clone()))
+            .order_by_asc(file_folder::Column::Name)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FOLDERS_ERROR"))
+    }
+
+    /// Retrieves the files inside of this folder, in alphabetical order.
+    #[graphql(complexity = 20)]
+    async fn files(&self, ctx: &Context<'_>) -> Result<Vec<file::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(file::Entity)
+            .order_by_asc(file::Column::Name)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FILES_ERROR"))
+    }
+}
@@ -0,0 +1,137 @@
+use super::super::{file, file_folder, file_tree};
+use super::member_connection::escape_like;
+use crate::components::access::ComponentAccess;
+use crate::errors;
+use crate::sessions::Session;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+
+#[Object(
+    name = "FileTree",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl file_tree::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// Retrieves the folders inside of a folder, or at the top of the tree if `parentId` isn't
+    /// provided, in alphabetical order.
+    #[graphql(complexity = 20)]
+    async fn folders(
+        &self,
+        ctx: &Context<'_>,
+        parent_id: Option<ID>,
+    ) -> Result<Vec<file_folder::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let parent_condition = match parent_id {
+            Some(parent_id) => file_folder::Column::Parent.eq(parent_id.to_string()),
+            None => file_folder::Column::Parent.is_null(),
+        };
+
+        self.find_related(file_folder::Entity)
+            .filter(parent_condition)
+            .order_by_asc(file_folder::Column::Name)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FOLDERS_ERROR"))
+    }
+
+    /// Retrieves the files inside of a folder, or at the top of the tree if `folderId` isn't
+    /// provided, in alphabetical order.
+    #[graphql(complexity = 20)]
+    async fn files(
+        &self,
+        ctx: &Context<'_>,
+        folder_id: Option<ID>,
+    ) -> Result<Vec<file::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let folder_condition = match folder_id {
+            Some(folder_id) => file::Column::Folder.eq(folder_id.to_string()),
+            None => file::Column::Folder.is_null(),
+        };
+
+        self.find_related(file::Entity)
+            .filter(folder_condition)
+            .order_by_asc(file::Column::Name)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FILES_ERROR"))
+    }
+
+    /// Finds a folder in this tree from its ID.
+    #[graphql(complexity = 5)]
+    async fn folder(&self, ctx: &Context<'_>, id: ID) -> Result<file_folder::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(file_folder::Entity)
+            .filter(file_folder::Column::Id.eq(id.to_string()))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FOLDER_ERROR"))?
+            .ok_or(errors::create_not_found_error())
+    }
+
+    /// Finds a file in this tree from its ID.
+    #[graphql(complexity = 5)]
+    async fn file(&self, ctx: &Context<'_>, id: ID) -> Result<file::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(file::Entity)
+            .filter(file::Column::Id.eq(id.to_string()))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FILE_ERROR"))?
+            .ok_or(errors::create_not_found_error())
+    }
+
+    /// Searches every file in the tree by name, ignoring case. Requires the `files.search`
+    /// permission.
+    #[graphql(complexity = "10 * size as usize + size as usize * child_complexity")]
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        size: u64,
+        page: u64,
+    ) -> Result<Vec<file::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let access = ComponentAccess::load_by_data(self.id.clone(), "files", user_id, db).await?;
+        access.check_permission("files.search")?;
+
+        if query.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let pattern = format!("%{}%", escape_like(query.trim()));
+
+        self.find_related(file::Entity)
+            .filter(Expr::cust_with_values(
+                r#""file"."name" ILIKE $1"#,
+                [pattern],
+            ))
+            .order_by_asc(file::Column::Name)
+            .order_by_asc(file::Column::Id)
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "SEARCH_FILES_ERROR"))
+    }
+}
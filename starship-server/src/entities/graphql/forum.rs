@@ -0,0 +1,65 @@
+use super::super::{forum, forum_post, forum_tag};
+use crate::components::forum::thread_query;
+use crate::errors;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+
+#[Object(name = "Forum", rename_fields = "camelCase", rename_args = "camelCase")]
+impl forum::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// Retrieves the forum's threads. Sticky threads come first, followed by the threads with the
+    /// most recent activity. If `tag` is provided, only threads with that tag are listed.
+    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    async fn posts(
+        &self,
+        ctx: &Context<'_>,
+        size: u64,
+        page: u64,
+        tag: Option<ID>,
+    ) -> Result<Vec<forum_post::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        thread_query(&self.id, tag.map(|tag| tag.to_string()))
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_POSTS_ERROR"))
+    }
+
+    /// Finds a thread in this forum from its ID.
+    #[graphql(complexity = 5)]
+    async fn post(&self, ctx: &Context<'_>, id: ID) -> Result<forum_post::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(forum_post::Entity)
+            .filter(forum_post::Column::Id.eq(id.to_string()))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_POST_ERROR"))?
+            .ok_or(errors::create_not_found_error())
+    }
+
+    #[graphql(complexity = 5)]
+    async fn tags(&self, ctx: &Context<'_>) -> Result<Vec<forum_tag::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(forum_tag::Entity)
+            .order_by_asc(forum_tag::Column::Name)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_TAGS_ERROR"))
+    }
+}
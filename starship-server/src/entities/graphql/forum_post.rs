@@ -0,0 +1,123 @@
+use super::super::{forum_post, forum_reply, forum_tag, user};
+use crate::components::forum::{reaction_counts, ReactionCount};
+use crate::errors;
+use crate::loaders::Loaders;
+use crate::sessions::Session;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter,
+    QueryOrder,
+};
+
+#[Object(
+    name = "ForumPost",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl forum_post::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn title(&self) -> &String {
+        &self.title
+    }
+
+    #[graphql(complexity = 0)]
+    async fn content(&self) -> &String {
+        &self.content
+    }
+
+    #[graphql(complexity = 5)]
+    async fn author(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.author.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))
+    }
+
+    #[graphql(complexity = 5)]
+    async fn tags(&self, ctx: &Context<'_>) -> Result<Vec<forum_tag::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        forum_tag::Entity::find()
+            .filter(forum_tag::Column::Id.is_in(self.tags.clone()))
+            .order_by_asc(forum_tag::Column::Name)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_TAGS_ERROR"))
+    }
+
+    /// Links to the files attached to the post.
+    #[graphql(complexity = 0)]
+    async fn attachments(&self) -> &Vec<String> {
+        &self.attachments
+    }
+
+    #[graphql(complexity = 0)]
+    async fn sticky(&self) -> bool {
+        self.sticky
+    }
+
+    /// Whether or not replies can be posted without the `forum.posts.lock.ignore` permission.
+    #[graphql(complexity = 0)]
+    async fn locked(&self) -> bool {
+        self.locked
+    }
+
+    #[graphql(complexity = 0)]
+    async fn reply_count(&self) -> i32 {
+        self.reply_count
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    #[graphql(complexity = 0)]
+    async fn edited_at(&self) -> Option<NaiveDateTime> {
+        self.edited
+    }
+
+    /// When the post was created or last replied to.
+    #[graphql(complexity = 0)]
+    async fn last_activity_at(&self) -> NaiveDateTime {
+        self.last_activity
+    }
+
+    /// Retrieves the post's replies, oldest first.
+    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    async fn replies(
+        &self,
+        ctx: &Context<'_>,
+        size: u64,
+        page: u64,
+    ) -> Result<Vec<forum_reply::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(forum_reply::Entity)
+            .order_by_asc(forum_reply::Column::Created)
+            .order_by_asc(forum_reply::Column::Id)
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_REPLIES_ERROR"))
+    }
+
+    #[graphql(complexity = 5)]
+    async fn reactions(&self, ctx: &Context<'_>) -> Result<Vec<ReactionCount>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.as_str());
+
+        reaction_counts(&self.id, None, user_id, db).await
+    }
+}
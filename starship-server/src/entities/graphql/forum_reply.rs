@@ -0,0 +1,56 @@
+use super::super::{forum_reply, user};
+use crate::components::forum::{reaction_counts, ReactionCount};
+use crate::errors;
+use crate::loaders::Loaders;
+use crate::sessions::Session;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::DatabaseConnection;
+
+#[Object(
+    name = "ForumReply",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl forum_reply::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn content(&self) -> &String {
+        &self.content
+    }
+
+    #[graphql(complexity = 5)]
+    async fn author(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.author.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    #[graphql(complexity = 0)]
+    async fn edited_at(&self) -> Option<NaiveDateTime> {
+        self.edited
+    }
+
+    #[graphql(complexity = 5)]
+    async fn reactions(&self, ctx: &Context<'_>) -> Result<Vec<ReactionCount>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.as_str());
+
+        reaction_counts(&self.post, Some(&self.id), user_id, db).await
+    }
+}
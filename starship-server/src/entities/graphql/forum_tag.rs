@@ -0,0 +1,31 @@
+use super::super::forum_tag;
+use async_graphql::types::ID;
+use async_graphql::Object;
+use chrono::NaiveDateTime;
+
+#[Object(
+    name = "ForumTag",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl forum_tag::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn name(&self) -> &String {
+        &self.name
+    }
+
+    #[graphql(complexity = 0)]
+    async fn color(&self) -> &String {
+        &self.color
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+}
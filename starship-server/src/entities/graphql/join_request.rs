@@ -0,0 +1,77 @@
+use super::super::join_request::{JoinRequestStatus, Model};
+use super::super::user;
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+
+#[Object(
+    name = "JoinRequest",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    /// The ID of the planet the user asked to join. The planet itself isn't included, as the user
+    /// may not be able to see it yet.
+    #[graphql(complexity = 0)]
+    async fn planet_id(&self) -> ID {
+        ID(self.planet.clone())
+    }
+
+    #[graphql(complexity = 5)]
+    async fn user(&self, ctx: &Context<'_>) -> Result<user::Model, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.user.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "USER_MISSING_ERROR",
+            ))
+    }
+
+    /// The user's answer to the planet's join question.
+    #[graphql(complexity = 0)]
+    async fn answer(&self) -> &Option<String> {
+        &self.answer
+    }
+
+    #[graphql(complexity = 0)]
+    async fn status(&self) -> JoinRequestStatus {
+        self.status
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// The user that approved or denied the request.
+    #[graphql(complexity = 5)]
+    async fn reviewed_by(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        match &self.reviewed_by {
+            Some(id) => loaders
+                .users
+                .load_one(id.clone())
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_REVIEWER_ERROR")),
+            None => Ok(None),
+        }
+    }
+
+    #[graphql(complexity = 0)]
+    async fn reviewed_at(&self) -> Option<NaiveDateTime> {
+        self.reviewed
+    }
+}
@@ -0,0 +1,186 @@
+use super::super::planet_member;
+use super::cursor::{cursor_condition, TimestampCursor};
+use crate::errors;
+use async_graphql::connection::{self, Connection, Edge};
+use async_graphql::{Enum, Error};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, JoinType, Order, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, Select,
+};
+
+/// The number of members returned if neither `first` nor `last` is provided.
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+/// The largest number of members that can be requested at once.
+const MAX_PAGE_SIZE: usize = 100;
+
+/// The order members are listed in.
+#[derive(Enum, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MemberOrder {
+    /// Most recently joined members first.
+    #[default]
+    NewestFirst,
+    /// Earliest joined members first.
+    OldestFirst,
+}
+
+impl From<&planet_member::Model> for TimestampCursor {
+    fn from(member: &planet_member::Model) -> Self {
+        TimestampCursor {
+            created: member.created,
+            id: member.id.clone(),
+        }
+    }
+}
+
+/// The filters applied to a list of a planet's members.
+pub struct MemberFilter {
+    pub planet: String,
+    /// Matches members whose username or nickname contains this string, ignoring case.
+    pub search: Option<String>,
+    pub role: Option<String>,
+    pub banned: Option<bool>,
+    pub order: MemberOrder,
+}
+
+/// Escapes the wildcard characters in a string so it can be used in a LIKE pattern.
+pub fn escape_like(search: &str) -> String {
+    search
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Calculates the complexity of a member connection field from the requested page size.
+pub fn complexity(first: Option<i32>, last: Option<i32>, child_complexity: usize) -> usize {
+    let size = first.or(last).map_or(DEFAULT_PAGE_SIZE, |size| {
+        size.clamp(0, MAX_PAGE_SIZE as i32) as usize
+    });
+
+    5 * size + size * child_complexity
+}
+
+/// Creates a query for the members of a planet that match a filter, without any ordering.
+pub fn filter_members(filter: &MemberFilter) -> Select<planet_member::Entity> {
+    let mut query = planet_member::Entity::find()
+        .filter(planet_member::Column::Planet.eq(filter.planet.clone()));
+
+    if let Some(search) = &filter.search {
+        let pattern = format!("%{}%", escape_like(search));
+
+        query = query
+            .join(JoinType::InnerJoin, planet_member::Relation::User.def())
+            .filter(
+                Condition::any()
+                    .add(Expr::cust_with_values(
+                        r#""user"."username" ILIKE $1"#,
+                        [pattern.clone()],
+                    ))
+                    .add(Expr::cust_with_values(
+                        r#""planet_member"."nickname" ILIKE $1"#,
+                        [pattern],
+                    )),
+            );
+    }
+
+    // the role is bound as an array, so that the containment check can use the index on roles
+    if let Some(role) = &filter.role {
+        query = query.filter(Expr::cust_with_values(
+            r#""planet_member"."roles" @> $1"#,
+            [vec![role.clone()]],
+        ));
+    }
+
+    if let Some(banned) = filter.banned {
+        query = query.filter(planet_member::Column::Banned.eq(banned));
+    }
+
+    query
+}
+
+/// Retrieves a page of a planet's members as a Relay connection.
+pub async fn members_connection(
+    db: &DatabaseConnection,
+    filter: MemberFilter,
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+) -> Result<Connection<TimestampCursor, planet_member::Model>, Error> {
+    connection::query(
+        after,
+        before,
+        first,
+        last,
+        |after: Option<TimestampCursor>, before: Option<TimestampCursor>, first, last| async move {
+            let backwards = last.is_some();
+            let limit = first
+                .or(last)
+                .unwrap_or(DEFAULT_PAGE_SIZE)
+                .min(MAX_PAGE_SIZE);
+
+            let ascending = filter.order == MemberOrder::OldestFirst;
+
+            let mut query = filter_members(&filter);
+
+            let has_after = after.is_some();
+            let has_before = before.is_some();
+
+            if let Some(after) = after {
+                query = query.filter(cursor_condition(
+                    planet_member::Column::Created,
+                    planet_member::Column::Id,
+                    after,
+                    ascending,
+                    true,
+                ));
+            }
+
+            if let Some(before) = before {
+                query = query.filter(cursor_condition(
+                    planet_member::Column::Created,
+                    planet_member::Column::Id,
+                    before,
+                    ascending,
+                    false,
+                ));
+            }
+
+            // when paginating backwards, the members closest to the cursor are retrieved by
+            // reversing the order, and put back in order afterwards
+            let query_order = if ascending != backwards {
+                Order::Asc
+            } else {
+                Order::Desc
+            };
+
+            let mut members = query
+                .order_by(planet_member::Column::Created, query_order.clone())
+                .order_by(planet_member::Column::Id, query_order)
+                .limit(limit as u64 + 1)
+                .all(db)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_MEMBERS_ERROR"))?;
+
+            let has_more = members.len() > limit;
+            members.truncate(limit);
+
+            let mut connection = if backwards {
+                members.reverse();
+                Connection::new(has_more, has_before)
+            } else {
+                Connection::new(has_after, has_more)
+            };
+
+            connection.edges.extend(
+                members
+                    .into_iter()
+                    .map(|member| Edge::new(TimestampCursor::from(&member), member)),
+            );
+
+            Ok::<_, Error>(connection)
+        },
+    )
+    .await
+}
@@ -1,6 +1,31 @@
+mod audit_log;
+mod auto_role;
+pub mod chat;
+mod chat_message;
+mod component_permission;
+mod conversation;
+pub mod cursor;
 mod custom_emoji;
+mod direct_message;
+mod event;
+mod event_calendar;
+mod event_rsvp;
+mod file;
+mod file_folder;
+mod file_tree;
+mod forum;
+mod forum_post;
+mod forum_reply;
+mod forum_tag;
+mod join_request;
+pub mod member_connection;
+mod notification;
+mod page;
 mod planet;
 mod planet_component;
 mod planet_member;
 mod planet_role;
 mod user;
+mod wiki;
+mod wiki_article;
+mod wiki_revision;
@@ -0,0 +1,83 @@
+use super::super::notification::{Model, NotificationKind};
+use super::super::planet;
+use super::super::user;
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+
+#[Object(
+    name = "Notification",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn kind(&self) -> NotificationKind {
+        self.kind
+    }
+
+    /// The planet the notification came from, if any.
+    #[graphql(complexity = 5)]
+    async fn planet(&self, ctx: &Context<'_>) -> Result<Option<planet::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        match &self.planet {
+            Some(id) => loaders
+                .planets
+                .load_one(id.clone())
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_PLANET_ERROR")),
+            None => Ok(None),
+        }
+    }
+
+    /// The ID of the component the notification came from, if any.
+    #[graphql(complexity = 0)]
+    async fn component_id(&self) -> Option<ID> {
+        self.component.clone().map(ID)
+    }
+
+    /// The ID of the object the notification is about, such as a message.
+    #[graphql(complexity = 0)]
+    async fn target(&self) -> Option<ID> {
+        self.target.clone().map(ID)
+    }
+
+    /// The user that caused the notification, if any.
+    #[graphql(complexity = 5)]
+    async fn actor(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        match &self.actor {
+            Some(id) => loaders
+                .users
+                .load_one(id.clone())
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_ACTOR_ERROR")),
+            None => Ok(None),
+        }
+    }
+
+    /// An excerpt of the content that caused the notification.
+    #[graphql(complexity = 0)]
+    async fn content(&self) -> &String {
+        &self.content
+    }
+
+    #[graphql(complexity = 0)]
+    async fn read(&self) -> bool {
+        self.read
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+}
@@ -0,0 +1,46 @@
+use super::super::page::Model;
+use super::super::user;
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+
+#[Object(name = "Page", rename_fields = "camelCase", rename_args = "camelCase")]
+impl Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    /// The page's Toolbox project, serialized as JSON.
+    #[graphql(complexity = 0)]
+    async fn content(&self) -> &String {
+        &self.content
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    #[graphql(complexity = 0)]
+    async fn updated_at(&self) -> NaiveDateTime {
+        self.updated
+    }
+
+    /// The user who last saved the page.
+    #[graphql(complexity = 5)]
+    async fn updated_by(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        match &self.updated_by {
+            Some(id) => loaders
+                .users
+                .load_one(id.clone())
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR")),
+            None => Ok(None),
+        }
+    }
+}
@@ -1,10 +1,22 @@
+use super::super::audit_log;
+use super::super::auto_role;
 use super::super::custom_emoji;
+use super::super::join_request;
+use super::super::notification::{self, NotificationKind};
 use super::super::planet::Model;
 use super::super::planet_component;
 use super::super::planet_member;
 use super::super::planet_role;
 use super::super::user;
+use super::cursor::TimestampCursor;
+use super::member_connection::{self, MemberFilter, MemberOrder};
+use crate::audit;
 use crate::errors;
+use crate::loaders::Loaders;
+use crate::permissions::checks::EffectivePermission;
+use crate::permissions::util;
+use crate::sessions::Session;
+use async_graphql::connection::Connection;
 use async_graphql::types::ID;
 use async_graphql::{Context, Error, Object};
 use chrono::NaiveDateTime;
@@ -36,10 +48,11 @@ impl Model {
 
     #[graphql(complexity = 5)]
     async fn owner(&self, ctx: &Context<'_>) -> Result<user::Model, Error> {
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
-        user::Entity::find_by_id(self.owner.clone())
-            .one(db)
+        loaders
+            .users
+            .load_one(self.owner.clone())
             .await
             .map_err(|_| errors::create_internal_server_error(None, "FIND_OWNER_ERROR"))?
             .ok_or(errors::create_internal_server_error(
@@ -64,6 +77,7 @@ impl Model {
 
         planet_component::Entity::find()
             .filter(planet_component::Column::Planet.eq(self.id.clone()))
+            .order_by_asc(planet_component::Column::Position)
             .all(db)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "FIND_COMPONENTS_ERROR"))
@@ -74,15 +88,12 @@ impl Model {
         &self,
         ctx: &Context<'_>,
     ) -> Result<Option<planet_component::Model>, Error> {
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
         match &self.home {
-            Some(id) => planet_component::Entity::find_by_id(id.clone())
-                .one(db)
-                .await
-                .map_err(|_| {
-                    errors::create_internal_server_error(None, "FIND_HOME_COMPONENT_ERROR")
-                }),
+            Some(id) => loaders.components.load_one(id.clone()).await.map_err(|_| {
+                errors::create_internal_server_error(None, "FIND_HOME_COMPONENT_ERROR")
+            }),
             None => Ok(None),
         }
     }
@@ -102,21 +113,131 @@ impl Model {
         self.partnered
     }
 
-    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    /// Retrieves the planet's members. Members can be searched by username or nickname, and
+    /// filtered by role or by whether or not they are banned.
+    #[graphql(complexity = "member_connection::complexity(first, last, child_complexity)")]
+    #[allow(clippy::too_many_arguments)]
     async fn members(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+        search: Option<String>,
+        role_id: Option<ID>,
+        banned: Option<bool>,
+        #[graphql(default)] order: MemberOrder,
+    ) -> Result<Connection<TimestampCursor, planet_member::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        // who is banned is only visible to the members that can ban
+        if banned.is_some() {
+            let session = ctx.data::<Session>().unwrap();
+            let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+            let member = util::get_planet_member(user_id, self.id.clone(), db).await?;
+            let roles = util::get_member_roles(member.clone(), db).await?;
+            util::check_permission("planet.member.ban", self, member, roles)?;
+        }
+
+        let filter = MemberFilter {
+            planet: self.id.clone(),
+            search,
+            role: role_id.map(|id| id.to_string()),
+            banned,
+            order,
+        };
+
+        member_connection::members_connection(db, filter, after, before, first, last).await
+    }
+
+    /// Retrieves the planet's banned members, most recently banned first.
+    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    async fn bans(
         &self,
         ctx: &Context<'_>,
         size: u64,
         page: u64,
     ) -> Result<Vec<planet_member::Model>, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let member = util::get_planet_member(user_id, self.id.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission("planet.member.ban", self, member, roles)?;
+
+        // expired bans are lifted by a background job, so leave out any it hasn't got to yet
+        let now = chrono::offset::Utc::now().naive_utc();
 
         self.find_related(planet_member::Entity)
-            .order_by_desc(planet_member::Column::Created)
+            .filter(planet_member::Column::Banned.eq(true))
+            .filter(
+                planet_member::Column::BanExpires
+                    .is_null()
+                    .or(planet_member::Column::BanExpires.gt(now)),
+            )
+            .order_by_desc(planet_member::Column::BannedAt)
             .paginate(db, size)
             .fetch_page(page)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "FIND_MEMBERS_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_BANS_ERROR"))
+    }
+
+    /// Retrieves the rules that give roles to the planet's members automatically.
+    #[graphql(complexity = 10)]
+    async fn auto_roles(&self, ctx: &Context<'_>) -> Result<Vec<auto_role::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let member = util::get_planet_member(user_id, self.id.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission("planet.roles.auto_assign", self, member, roles)?;
+
+        self.find_related(auto_role::Entity)
+            .order_by_asc(auto_role::Column::Created)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_AUTO_ROLES_ERROR"))
+    }
+
+    /// Retrieves the requests to join the planet that haven't been reviewed yet, oldest first.
+    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    async fn join_requests(
+        &self,
+        ctx: &Context<'_>,
+        size: u64,
+        page: u64,
+    ) -> Result<Vec<join_request::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let member = util::get_planet_member(user_id, self.id.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission("planet.join_requests.manage", self, member, roles)?;
+
+        self.find_related(join_request::Entity)
+            .filter(join_request::Column::Status.eq(join_request::JoinRequestStatus::Pending))
+            .order_by_asc(join_request::Column::Created)
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_JOIN_REQUESTS_ERROR"))
+    }
+
+    /// Whether or not users can ask to join this planet while it is private.
+    #[graphql(complexity = 0)]
+    async fn join_requests_enabled(&self) -> bool {
+        self.join_requests_enabled
+    }
+
+    /// The question users are asked when they request to join this planet.
+    #[graphql(complexity = 0)]
+    async fn join_question(&self) -> &Option<String> {
+        &self.join_question
     }
 
     #[graphql(complexity = "5")]
@@ -156,6 +277,91 @@ impl Model {
             .map_err(|_| errors::create_internal_server_error(None, "FIND_EMOJIS_ERROR"))
     }
 
-    // TODO: unread
-    // TODO: mentioned
+    /// The permissions the current user holds or is explicitly denied on this planet.
+    #[graphql(complexity = 10)]
+    async fn my_permissions(&self, ctx: &Context<'_>) -> Result<Vec<EffectivePermission>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let member = util::get_planet_member(user_id, self.id.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+
+        Ok(util::effective_permissions(self, member, roles, None))
+    }
+
+    /// Retrieves the planet's audit log, newest entries first.
+    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    async fn audit_log(
+        &self,
+        ctx: &Context<'_>,
+        size: u64,
+        page: u64,
+        action: Option<audit_log::AuditAction>,
+        actor: Option<ID>,
+        target: Option<ID>,
+    ) -> Result<Vec<audit_log::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let member = util::get_planet_member(user_id, self.id.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission("planet.audit_log.view", self, member, roles)?;
+
+        audit::filter_entries(
+            self.find_related(audit_log::Entity),
+            action,
+            actor.map(|actor| actor.to_string()),
+            target.map(|target| target.to_string()),
+        )
+        .paginate(db, size)
+        .fetch_page(page)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_AUDIT_LOG_ERROR"))
+    }
+
+    /// Whether or not the current user has unread notifications from this planet.
+    #[graphql(complexity = 5)]
+    async fn unread(&self, ctx: &Context<'_>) -> Result<bool, Error> {
+        self.has_unread_notifications(ctx, None).await
+    }
+
+    /// Whether or not the current user has been mentioned in this planet since they last read
+    /// their notifications from it.
+    #[graphql(complexity = 5)]
+    async fn mentioned(&self, ctx: &Context<'_>) -> Result<bool, Error> {
+        self.has_unread_notifications(ctx, Some(NotificationKind::Mention))
+            .await
+    }
+}
+
+impl Model {
+    async fn has_unread_notifications(
+        &self,
+        ctx: &Context<'_>,
+        kind: Option<NotificationKind>,
+    ) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+
+        let Some(user) = session.user.as_ref() else {
+            return Ok(false);
+        };
+
+        let mut query = self
+            .find_related(notification::Entity)
+            .filter(notification::Column::User.eq(user.id.clone()))
+            .filter(notification::Column::Read.eq(false));
+
+        if let Some(kind) = kind {
+            query = query.filter(notification::Column::Kind.eq(kind));
+        }
+
+        query
+            .one(db)
+            .await
+            .map(|notification| notification.is_some())
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_NOTIFICATIONS_ERROR"))
+    }
 }
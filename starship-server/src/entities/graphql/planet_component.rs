@@ -1,10 +1,17 @@
+use super::super::component_permission;
 use super::super::planet;
 use super::super::planet_component;
+use crate::components::registry::ComponentRegistry;
 use crate::errors;
+use crate::loaders::Loaders;
+use crate::permissions::checks::EffectivePermission;
+use crate::permissions::constants::Permission;
+use crate::permissions::util;
+use crate::sessions::Session;
 use async_graphql::types::ID;
 use async_graphql::{Context, Error, Object};
 use chrono::NaiveDateTime;
-use sea_orm::{DatabaseConnection, EntityTrait, ModelTrait};
+use sea_orm::DatabaseConnection;
 
 #[Object(
     name = "PlanetComponent",
@@ -39,10 +46,11 @@ impl planet_component::Model {
 
     #[graphql(complexity = 5)]
     async fn planet(&self, ctx: &Context<'_>) -> Result<planet::Model, Error> {
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
-        self.find_related(planet::Entity)
-            .one(db)
+        loaders
+            .planets
+            .load_one(self.planet.clone())
             .await
             .map_err(|_| errors::create_internal_server_error(None, "FIND_PLANET_ERROR"))?
             .ok_or(errors::create_internal_server_error(
@@ -53,15 +61,15 @@ impl planet_component::Model {
 
     #[graphql(complexity = 5)]
     async fn parent(&self, ctx: &Context<'_>) -> Result<Option<planet_component::Model>, Error> {
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
-        if let Some(parent) = self.parent_id.clone() {
-            planet_component::Entity::find_by_id(parent)
-                .one(db)
+        match &self.parent_id {
+            Some(id) => loaders
+                .components
+                .load_one(id.clone())
                 .await
-                .map_err(|_| errors::create_internal_server_error(None, "FIND_PARENT_ERROR"))
-        } else {
-            Ok(None)
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_PARENT_ERROR")),
+            None => Ok(None),
         }
     }
 
@@ -69,4 +77,90 @@ impl planet_component::Model {
     async fn position(&self) -> i32 {
         self.position
     }
+
+    /// The components nested inside of this component, in order.
+    #[graphql(complexity = 10)]
+    async fn children(&self, ctx: &Context<'_>) -> Result<Vec<planet_component::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        Ok(loaders
+            .children
+            .load_one(self.id.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_CHILDREN_ERROR"))?
+            .unwrap_or_default())
+    }
+
+    /// The permission overrides set on this component. Requires the
+    /// `planet.component.edit_permissions` permission.
+    #[graphql(complexity = 10)]
+    async fn permission_overrides(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<component_permission::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let planet = util::get_planet(self.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id, self.planet.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission("planet.component.edit_permissions", &planet, member, roles)?;
+
+        util::get_component_overrides(self.id.clone(), db).await
+    }
+
+    /// The permissions the current user holds in this component, after the component's overrides
+    /// have been applied.
+    #[graphql(complexity = 10)]
+    async fn my_permissions(&self, ctx: &Context<'_>) -> Result<Vec<EffectivePermission>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let planet = util::get_planet(self.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id, self.planet.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        let overrides = util::get_component_overrides(self.id.clone(), db).await?;
+
+        Ok(util::effective_permissions(
+            &planet,
+            member,
+            roles,
+            Some(&overrides),
+        ))
+    }
+
+    /// The permissions that can be overridden on this component, based on its type.
+    #[graphql(complexity = 0)]
+    async fn available_permissions(&self, ctx: &Context<'_>) -> Result<Vec<Permission>, Error> {
+        let registry = ctx.data::<ComponentRegistry>().unwrap();
+
+        Ok(registry
+            .get(&self.r#type)?
+            .permissions()
+            .into_iter()
+            .copied()
+            .collect())
+    }
+
+    /// Exports the contents of this component as a JSON document. Requires the
+    /// `planet.component.export` permission.
+    #[graphql(complexity = 100)]
+    async fn export(&self, ctx: &Context<'_>) -> Result<String, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let registry = ctx.data::<ComponentRegistry>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let planet = util::get_planet(self.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id, self.planet.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission("planet.component.export", &planet, member, roles)?;
+
+        registry
+            .get(&self.r#type)?
+            .export(db, &self.component_id)
+            .await
+    }
 }
@@ -3,10 +3,14 @@ use super::super::planet_member::Model;
 use super::super::planet_role;
 use super::super::user;
 use crate::errors;
+use crate::loaders::{load_ordered, Loaders};
+use crate::permissions::checks::EffectivePermission;
+use crate::permissions::util;
+use crate::sessions::Session;
 use async_graphql::types::ID;
 use async_graphql::{Context, Error, Object};
 use chrono::NaiveDateTime;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter};
+use sea_orm::DatabaseConnection;
 
 #[Object(
     name = "PlanetMember",
@@ -21,10 +25,11 @@ impl Model {
 
     #[graphql(complexity = 5)]
     async fn planet(&self, ctx: &Context<'_>) -> Result<planet::Model, Error> {
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
-        self.find_related(planet::Entity)
-            .one(db)
+        loaders
+            .planets
+            .load_one(self.planet.clone())
             .await
             .map_err(|_| errors::create_internal_server_error(None, "FIND_PLANET_ERROR"))?
             .ok_or(errors::create_internal_server_error(
@@ -35,12 +40,40 @@ impl Model {
 
     #[graphql(complexity = 5)]
     async fn user(&self, ctx: &Context<'_>) -> Result<user::Model, Error> {
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.user.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "USER_MISSING_ERROR",
+            ))
+    }
+
+    #[graphql(complexity = 0)]
+    async fn nickname(&self) -> &Option<String> {
+        &self.nickname
+    }
+
+    /// The name the member should be displayed as: their nickname if they have one, otherwise
+    /// their username.
+    #[graphql(complexity = 5)]
+    async fn display_name(&self, ctx: &Context<'_>) -> Result<String, Error> {
+        if let Some(nickname) = &self.nickname {
+            return Ok(nickname.clone());
+        }
 
-        self.find_related(user::Entity)
-            .one(db)
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        loaders
+            .users
+            .load_one(self.user.clone())
             .await
             .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR"))?
+            .map(|user| user.username)
             .ok_or(errors::create_internal_server_error(
                 None,
                 "USER_MISSING_ERROR",
@@ -49,11 +82,9 @@ impl Model {
 
     #[graphql(complexity = 5)]
     async fn roles(&self, ctx: &Context<'_>) -> Result<Vec<planet_role::Model>, Error> {
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
-        planet_role::Entity::find()
-            .filter(planet_role::Column::Id.is_in(self.roles.clone()))
-            .all(db)
+        load_ordered(&loaders.roles, &self.roles)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "FIND_ROLES_ERROR"))
     }
@@ -63,13 +94,109 @@ impl Model {
         &self.permissions
     }
 
+    /// The permissions this member holds or is explicitly denied after all of their roles and
+    /// overrides have been applied.
+    #[graphql(complexity = 10)]
+    async fn effective_permissions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<EffectivePermission>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let planet = util::get_planet(self.planet.clone(), db).await?;
+        let roles = util::get_member_roles(Some(self.clone()), db).await?;
+
+        Ok(util::effective_permissions(
+            &planet,
+            Some(self.clone()),
+            roles,
+            None,
+        ))
+    }
+
     #[graphql(complexity = 0)]
     async fn created_at(&self) -> NaiveDateTime {
         self.created
     }
 
-    #[graphql(complexity = 0)]
-    async fn banned(&self) -> bool {
-        self.banned
+    /// Whether or not the member is banned. Only visible to the member and to members that can
+    /// ban; everyone else sees `false`.
+    #[graphql(complexity = 5)]
+    async fn banned(&self, ctx: &Context<'_>) -> Result<bool, Error> {
+        if !self.banned {
+            return Ok(false);
+        }
+
+        let session = ctx.data::<Session>().unwrap();
+
+        if session
+            .user
+            .as_ref()
+            .is_some_and(|user| user.id == self.user)
+        {
+            return Ok(true);
+        }
+
+        self.can_view_ban(ctx).await
+    }
+
+    /// Why the member was banned. Only visible to members that can ban.
+    #[graphql(complexity = 5)]
+    async fn ban_reason(&self, ctx: &Context<'_>) -> Result<Option<String>, Error> {
+        Ok(self
+            .can_view_ban(ctx)
+            .await?
+            .then(|| self.ban_reason.clone())
+            .flatten())
+    }
+
+    /// When the member's ban will be lifted. Permanent bans have no expiry date. Only visible to
+    /// members that can ban.
+    #[graphql(complexity = 5)]
+    async fn ban_expires(&self, ctx: &Context<'_>) -> Result<Option<NaiveDateTime>, Error> {
+        Ok(self
+            .can_view_ban(ctx)
+            .await?
+            .then_some(self.ban_expires)
+            .flatten())
+    }
+
+    /// The moderator that banned this member. Only visible to members that can ban.
+    #[graphql(complexity = 10)]
+    async fn banned_by(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        if !self.can_view_ban(ctx).await? {
+            return Ok(None);
+        }
+
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        match &self.banned_by {
+            Some(id) => loaders
+                .users
+                .load_one(id.clone())
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_BANNED_BY_ERROR")),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Model {
+    /// Checks whether or not the current user can see the details of this member's ban. The
+    /// current user's membership is only looked up once per planet for each request.
+    async fn can_view_ban(&self, ctx: &Context<'_>) -> Result<bool, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        let membership = loaders
+            .memberships
+            .load_one(self.planet.clone())
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_MEMBERSHIP_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "PLANET_MISSING_ERROR",
+            ))?;
+
+        Ok(membership.check_permission("planet.member.ban").is_ok())
     }
 }
@@ -1,12 +1,14 @@
 use super::super::planet;
 use super::super::planet_member;
 use super::super::planet_role::Model;
+use super::cursor::TimestampCursor;
+use super::member_connection::{self, MemberFilter, MemberOrder};
 use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::connection::Connection;
 use async_graphql::types::ID;
 use async_graphql::{Context, Error, Object};
-use sea_orm::{
-    DatabaseBackend, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait, Statement,
-};
+use sea_orm::DatabaseConnection;
 
 #[Object(
     name = "PlanetRole",
@@ -36,10 +38,11 @@ impl Model {
 
     #[graphql(complexity = 5)]
     async fn planet(&self, ctx: &Context<'_>) -> Result<planet::Model, Error> {
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
-        self.find_related(planet::Entity)
-            .one(db)
+        loaders
+            .planets
+            .load_one(self.planet.clone())
             .await
             .map_err(|_| errors::create_internal_server_error(None, "FIND_PLANET_ERROR"))?
             .ok_or(errors::create_internal_server_error(
@@ -58,24 +61,47 @@ impl Model {
         self.default
     }
 
-    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    /// Retrieves the members that have this role. Members can be searched by username or
+    /// nickname, and filtered by whether or not they are banned.
+    #[graphql(complexity = "member_connection::complexity(first, last, child_complexity)")]
+    #[allow(clippy::too_many_arguments)]
     async fn members(
         &self,
         ctx: &Context<'_>,
-        size: u64,
-        page: u64,
-    ) -> Result<Vec<planet_member::Model>, Error> {
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+        search: Option<String>,
+        banned: Option<bool>,
+        #[graphql(default)] order: MemberOrder,
+    ) -> Result<Connection<TimestampCursor, planet_member::Model>, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
 
-        planet_member::Entity::find()
-            .from_raw_sql(Statement::from_sql_and_values(
-                DatabaseBackend::Postgres,
-                r#"SELECT * FROM planet_member WHERE $1=ANY(roles) ORDER BY planet_member.created DESC"#,
-                [self.id.clone().into()],
-            ))
-            .paginate(db, size)
-            .fetch_page(page)
-            .await
-            .map_err(|_| errors::create_internal_server_error(None, "FIND_MEMBERS_ERROR"))
+        // who is banned is only visible to the members that can ban
+        if banned.is_some() {
+            let loaders = ctx.data::<Loaders>().unwrap();
+
+            loaders
+                .memberships
+                .load_one(self.planet.clone())
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_MEMBERSHIP_ERROR"))?
+                .ok_or(errors::create_internal_server_error(
+                    None,
+                    "PLANET_MISSING_ERROR",
+                ))?
+                .check_permission("planet.member.ban")?;
+        }
+
+        let filter = MemberFilter {
+            planet: self.planet.clone(),
+            search,
+            role: Some(self.id.clone()),
+            banned,
+            order,
+        };
+
+        member_connection::members_connection(db, filter, after, before, first, last).await
     }
 }
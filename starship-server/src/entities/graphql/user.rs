@@ -1,14 +1,18 @@
 use super::super::custom_emoji;
+use super::super::notification;
 use super::super::planet;
 use super::super::planet_member;
-use super::super::user;
 use super::super::user::Model;
 use crate::errors;
+use crate::loaders::{load_ordered, Loaders};
+use crate::presence::{Presence, PresenceStatus};
 use crate::sessions::Session;
 use async_graphql::types::ID;
 use async_graphql::{Context, Error, Object};
 use chrono::NaiveDateTime;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
 
 impl Model {
     fn user_id_is_same(&self, ctx: &Context<'_>, name: &str) -> Result<(), Error> {
@@ -37,6 +41,26 @@ impl Model {
             ))
         }
     }
+
+    fn is_current_user(&self, ctx: &Context<'_>) -> bool {
+        ctx.data::<Session>()
+            .ok()
+            .and_then(|session| session.user.as_ref())
+            .is_some_and(|user| user.id == self.id)
+    }
+
+    /// Gets the user's presence as seen by whoever is asking. Invisible users appear offline to
+    /// everyone but themselves.
+    fn visible_presence(&self, ctx: &Context<'_>) -> PresenceStatus {
+        if self.invisible && !self.is_current_user(ctx) {
+            return PresenceStatus::Offline;
+        }
+
+        ctx.data::<Presence>()
+            .map_or(PresenceStatus::Offline, |presence| {
+                presence.status(&self.id)
+            })
+    }
 }
 
 #[Object(name = "User", rename_fields = "camelCase", rename_args = "camelCase")]
@@ -76,27 +100,39 @@ impl Model {
         self.banned
     }
 
+    #[graphql(complexity = 0)]
+    async fn ban_reason(&self, ctx: &Context<'_>) -> Result<&Option<String>, Error> {
+        self.user_id_is_same(ctx, "banReason")?;
+
+        Ok(&self.ban_reason)
+    }
+
+    #[graphql(complexity = 0)]
+    async fn ban_expires(&self, ctx: &Context<'_>) -> Result<Option<NaiveDateTime>, Error> {
+        self.user_id_is_same(ctx, "banExpires")?;
+
+        Ok(self.ban_expires)
+    }
+
     #[graphql(complexity = 5)]
     async fn member_of(&self, ctx: &Context<'_>) -> Result<Vec<planet::Model>, Error> {
         self.user_id_is_same(ctx, "memberOf")?;
 
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
-        Ok(self
+        let planet_ids: Vec<String> = self
             .find_related(planet_member::Entity)
-            .find_with_related(planet::Entity)
             .all(db)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "FIND_PLANETS_ERROR"))?
-            .iter()
-            .filter_map(|value| {
-                if value.1.is_empty() {
-                    None
-                } else {
-                    Some(value.1[0].clone())
-                }
-            })
-            .collect())
+            .into_iter()
+            .map(|member| member.planet)
+            .collect();
+
+        load_ordered(&loaders.planets, &planet_ids)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_PLANETS_ERROR"))
     }
 
     #[graphql(complexity = 0)]
@@ -129,11 +165,9 @@ impl Model {
     async fn blocked_users(&self, ctx: &Context<'_>) -> Result<Vec<Model>, Error> {
         self.user_id_is_same(ctx, "blockedUsers")?;
 
-        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let loaders = ctx.data::<Loaders>().unwrap();
 
-        user::Entity::find()
-            .filter(user::Column::Id.is_in(self.blocked.clone()))
-            .all(db)
+        load_ordered(&loaders.users, &self.blocked)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "FIND_BLOCKED_ERROR"))
     }
@@ -150,8 +184,74 @@ impl Model {
     }
 
     #[graphql(complexity = 0)]
-    async fn online(&self) -> bool {
-        !self.sessions.is_empty()
+    async fn online(&self, ctx: &Context<'_>) -> bool {
+        self.visible_presence(ctx) != PresenceStatus::Offline
+    }
+
+    /// Whether the user is online, idle or offline.
+    #[graphql(complexity = 0)]
+    async fn presence(&self, ctx: &Context<'_>) -> PresenceStatus {
+        self.visible_presence(ctx)
+    }
+
+    /// The user's custom status message. Other users can only see it while the user appears
+    /// online or idle.
+    #[graphql(complexity = 0)]
+    async fn status_message(&self, ctx: &Context<'_>) -> Option<&String> {
+        if !self.is_current_user(ctx) && self.visible_presence(ctx) == PresenceStatus::Offline {
+            None
+        } else {
+            self.status_message.as_ref()
+        }
+    }
+
+    /// Whether the user appears offline to everyone else.
+    #[graphql(complexity = 0)]
+    async fn invisible(&self, ctx: &Context<'_>) -> Result<bool, Error> {
+        self.user_id_is_same(ctx, "invisible")?;
+
+        Ok(self.invisible)
+    }
+
+    /// The user's notifications, newest first.
+    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    async fn notifications(
+        &self,
+        ctx: &Context<'_>,
+        size: u64,
+        page: u64,
+        unread_only: Option<bool>,
+    ) -> Result<Vec<notification::Model>, Error> {
+        self.user_id_is_same(ctx, "notifications")?;
+
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let mut query = self.find_related(notification::Entity);
+
+        if unread_only.unwrap_or(false) {
+            query = query.filter(notification::Column::Read.eq(false));
+        }
+
+        query
+            .order_by_desc(notification::Column::Created)
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_NOTIFICATIONS_ERROR"))
+    }
+
+    /// The number of notifications the user hasn't read.
+    #[graphql(complexity = 5)]
+    async fn unread_notification_count(&self, ctx: &Context<'_>) -> Result<u64, Error> {
+        self.user_id_is_same(ctx, "unreadNotificationCount")?;
+
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(notification::Entity)
+            .filter(notification::Column::Read.eq(false))
+            .count(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "COUNT_NOTIFICATIONS_ERROR"))
     }
 
     #[graphql(complexity = 0)]
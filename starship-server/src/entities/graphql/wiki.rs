@@ -0,0 +1,56 @@
+use super::super::{wiki, wiki_article};
+use crate::components::wiki::slugify;
+use crate::errors;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+
+#[Object(name = "Wiki", rename_fields = "camelCase", rename_args = "camelCase")]
+impl wiki::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    /// Retrieves the wiki's articles in alphabetical order.
+    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    async fn articles(
+        &self,
+        ctx: &Context<'_>,
+        size: u64,
+        page: u64,
+    ) -> Result<Vec<wiki_article::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(wiki_article::Entity)
+            .order_by_asc(wiki_article::Column::Slug)
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_ARTICLES_ERROR"))
+    }
+
+    /// Finds an article in this wiki from its title or slug.
+    #[graphql(complexity = 5)]
+    async fn article(
+        &self,
+        ctx: &Context<'_>,
+        title: String,
+    ) -> Result<Option<wiki_article::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(wiki_article::Entity)
+            .filter(wiki_article::Column::Slug.eq(slugify(&title)))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_ARTICLE_ERROR"))
+    }
+}
@@ -0,0 +1,152 @@
+use super::super::{user, wiki_article, wiki_revision};
+use crate::components::wiki::{diff_lines, DiffLine};
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter,
+    QueryOrder,
+};
+
+/// Finds one of an article's revisions from its number.
+async fn find_revision(
+    article: &wiki_article::Model,
+    number: i32,
+    db: &DatabaseConnection,
+) -> Result<wiki_revision::Model, Error> {
+    article
+        .find_related(wiki_revision::Entity)
+        .filter(wiki_revision::Column::Number.eq(number))
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_REVISION_ERROR"))?
+        .ok_or(errors::create_not_found_error())
+}
+
+#[Object(
+    name = "WikiArticle",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl wiki_article::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    #[graphql(complexity = 0)]
+    async fn title(&self) -> &String {
+        &self.title
+    }
+
+    /// The name used to link to this article, made from its title.
+    #[graphql(complexity = 0)]
+    async fn slug(&self) -> &String {
+        &self.slug
+    }
+
+    /// The article's Markdown content.
+    #[graphql(complexity = 0)]
+    async fn content(&self) -> &String {
+        &self.content
+    }
+
+    /// The number of the article's current revision.
+    #[graphql(complexity = 0)]
+    async fn revision_number(&self) -> i32 {
+        self.revision
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+
+    #[graphql(complexity = 0)]
+    async fn updated_at(&self) -> NaiveDateTime {
+        self.updated
+    }
+
+    #[graphql(complexity = 5)]
+    async fn updated_by(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        match &self.updated_by {
+            Some(id) => loaders
+                .users
+                .load_one(id.clone())
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR")),
+            None => Ok(None),
+        }
+    }
+
+    /// The slugs of the articles this article links to, including articles that don't exist yet.
+    #[graphql(complexity = 0)]
+    async fn links(&self) -> &Vec<String> {
+        &self.links
+    }
+
+    /// The other articles in the wiki that link to this article, in alphabetical order.
+    #[graphql(complexity = 20)]
+    async fn backlinks(&self, ctx: &Context<'_>) -> Result<Vec<wiki_article::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        wiki_article::Entity::find()
+            .filter(wiki_article::Column::Wiki.eq(self.wiki.clone()))
+            .filter(wiki_article::Column::Id.ne(self.id.clone()))
+            .filter(Expr::cust_with_values(
+                r#"$1 = ANY("wiki_article"."links")"#,
+                [self.slug.clone()],
+            ))
+            .order_by_asc(wiki_article::Column::Slug)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_BACKLINKS_ERROR"))
+    }
+
+    /// Retrieves the article's revisions, newest first.
+    #[graphql(complexity = "5 * size as usize + size as usize * child_complexity")]
+    async fn revisions(
+        &self,
+        ctx: &Context<'_>,
+        size: u64,
+        page: u64,
+    ) -> Result<Vec<wiki_revision::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        self.find_related(wiki_revision::Entity)
+            .order_by_desc(wiki_revision::Column::Number)
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_REVISIONS_ERROR"))
+    }
+
+    /// Finds one of the article's revisions from its number.
+    #[graphql(complexity = 5)]
+    async fn revision(
+        &self,
+        ctx: &Context<'_>,
+        number: i32,
+    ) -> Result<wiki_revision::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        find_revision(self, number, db).await
+    }
+
+    /// Compares the content of two of the article's revisions line by line. Lines only in `from`
+    /// are removed, and lines only in `to` are added.
+    #[graphql(complexity = 50)]
+    async fn diff(&self, ctx: &Context<'_>, from: i32, to: i32) -> Result<Vec<DiffLine>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let from = find_revision(self, from, db).await?;
+        let to = find_revision(self, to, db).await?;
+
+        Ok(diff_lines(&from.content, &to.content))
+    }
+}
@@ -0,0 +1,59 @@
+use super::super::{user, wiki_revision};
+use crate::errors;
+use crate::loaders::Loaders;
+use async_graphql::types::ID;
+use async_graphql::{Context, Error, Object};
+use chrono::NaiveDateTime;
+
+#[Object(
+    name = "WikiRevision",
+    rename_fields = "camelCase",
+    rename_args = "camelCase"
+)]
+impl wiki_revision::Model {
+    #[graphql(complexity = 0)]
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
+
+    /// The revision's position in the article's history, starting from 1.
+    #[graphql(complexity = 0)]
+    async fn number(&self) -> i32 {
+        self.number
+    }
+
+    #[graphql(complexity = 0)]
+    async fn title(&self) -> &String {
+        &self.title
+    }
+
+    #[graphql(complexity = 0)]
+    async fn content(&self) -> &String {
+        &self.content
+    }
+
+    /// The description of the change given by its author.
+    #[graphql(complexity = 0)]
+    async fn summary(&self) -> &Option<String> {
+        &self.summary
+    }
+
+    #[graphql(complexity = 5)]
+    async fn author(&self, ctx: &Context<'_>) -> Result<Option<user::Model>, Error> {
+        let loaders = ctx.data::<Loaders>().unwrap();
+
+        match &self.author {
+            Some(id) => loaders
+                .users
+                .load_one(id.clone())
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "FIND_USER_ERROR")),
+            None => Ok(None),
+        }
+    }
+
+    #[graphql(complexity = 0)]
+    async fn created_at(&self) -> NaiveDateTime {
+        self.created
+    }
+}
@@ -5,10 +5,37 @@
 pub mod graphql;
 pub mod prelude;
 
+pub mod audit_log;
+pub mod auto_role;
+pub mod auto_role_assignment;
+pub mod chat;
+pub mod chat_message;
+pub mod chat_reaction;
+pub mod component_permission;
+pub mod conversation;
+pub mod conversation_member;
 pub mod custom_emoji;
+pub mod direct_message;
+pub mod event;
+pub mod event_calendar;
+pub mod event_rsvp;
+pub mod file;
+pub mod file_folder;
+pub mod file_tree;
+pub mod forum;
+pub mod forum_post;
+pub mod forum_reaction;
+pub mod forum_reply;
+pub mod forum_tag;
+pub mod join_request;
+pub mod notification;
+pub mod page;
 pub mod planet;
 pub mod planet_component;
 pub mod planet_member;
 pub mod planet_role;
 pub mod token;
 pub mod user;
+pub mod wiki;
+pub mod wiki_article;
+pub mod wiki_revision;
@@ -19,12 +19,32 @@ pub struct Model {
     pub css: String,
     pub description: Option<String>,
     pub home: Option<String>,
+    pub join_requests_enabled: bool,
+    pub join_question: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::audit_log::Entity")]
+    AuditLog,
+    #[sea_orm(has_many = "super::auto_role::Entity")]
+    AutoRole,
+    #[sea_orm(has_many = "super::chat::Entity")]
+    Chat,
     #[sea_orm(has_many = "super::custom_emoji::Entity")]
     CustomEmoji,
+    #[sea_orm(has_many = "super::event_calendar::Entity")]
+    EventCalendar,
+    #[sea_orm(has_many = "super::file_tree::Entity")]
+    FileTree,
+    #[sea_orm(has_many = "super::forum::Entity")]
+    Forum,
+    #[sea_orm(has_many = "super::join_request::Entity")]
+    JoinRequest,
+    #[sea_orm(has_many = "super::notification::Entity")]
+    Notification,
+    #[sea_orm(has_many = "super::page::Entity")]
+    Page,
     #[sea_orm(
         belongs_to = "super::planet_component::Entity",
         from = "Column::Home",
@@ -45,6 +65,26 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     User,
+    #[sea_orm(has_many = "super::wiki::Entity")]
+    Wiki,
+}
+
+impl Related<super::audit_log::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AuditLog.def()
+    }
+}
+
+impl Related<super::auto_role::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AutoRole.def()
+    }
+}
+
+impl Related<super::chat::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Chat.def()
+    }
 }
 
 impl Related<super::custom_emoji::Entity> for Entity {
@@ -53,6 +93,42 @@ impl Related<super::custom_emoji::Entity> for Entity {
     }
 }
 
+impl Related<super::event_calendar::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EventCalendar.def()
+    }
+}
+
+impl Related<super::file_tree::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FileTree.def()
+    }
+}
+
+impl Related<super::forum::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Forum.def()
+    }
+}
+
+impl Related<super::join_request::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::JoinRequest.def()
+    }
+}
+
+impl Related<super::notification::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notification.def()
+    }
+}
+
+impl Related<super::page::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Page.def()
+    }
+}
+
 impl Related<super::planet_component::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PlanetComponent.def()
@@ -77,4 +153,10 @@ impl Related<super::user::Entity> for Entity {
     }
 }
 
+impl Related<super::wiki::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Wiki.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
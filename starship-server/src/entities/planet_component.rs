@@ -34,6 +34,8 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     SelfRef,
+    #[sea_orm(has_many = "super::component_permission::Entity")]
+    ComponentPermission,
 }
 
 impl Related<super::planet::Entity> for Entity {
@@ -42,4 +44,10 @@ impl Related<super::planet::Entity> for Entity {
     }
 }
 
+impl Related<super::component_permission::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ComponentPermission.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
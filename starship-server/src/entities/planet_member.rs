@@ -13,6 +13,12 @@ pub struct Model {
     pub permissions: Vec<String>,
     pub created: DateTime,
     pub banned: bool,
+    pub ban_reason: Option<String>,
+    pub ban_expires: Option<DateTime>,
+    pub banned_by: Option<String>,
+    pub banned_at: Option<DateTime>,
+    pub nickname: Option<String>,
+    pub last_active: DateTime,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -1,9 +1,38 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.11.1
 
+#![allow(unused_imports)]
+
+pub use super::audit_log::Entity as AuditLog;
+pub use super::auto_role::Entity as AutoRole;
+pub use super::auto_role_assignment::Entity as AutoRoleAssignment;
+pub use super::chat::Entity as Chat;
+pub use super::chat_message::Entity as ChatMessage;
+pub use super::chat_reaction::Entity as ChatReaction;
+pub use super::component_permission::Entity as ComponentPermission;
+pub use super::conversation::Entity as Conversation;
+pub use super::conversation_member::Entity as ConversationMember;
 pub use super::custom_emoji::Entity as CustomEmoji;
+pub use super::direct_message::Entity as DirectMessage;
+pub use super::event::Entity as Event;
+pub use super::event_calendar::Entity as EventCalendar;
+pub use super::event_rsvp::Entity as EventRsvp;
+pub use super::file::Entity as File;
+pub use super::file_folder::Entity as FileFolder;
+pub use super::file_tree::Entity as FileTree;
+pub use super::forum::Entity as Forum;
+pub use super::forum_post::Entity as ForumPost;
+pub use super::forum_reaction::Entity as ForumReaction;
+pub use super::forum_reply::Entity as ForumReply;
+pub use super::forum_tag::Entity as ForumTag;
+pub use super::join_request::Entity as JoinRequest;
+pub use super::notification::Entity as Notification;
+pub use super::page::Entity as Page;
 pub use super::planet::Entity as Planet;
 pub use super::planet_component::Entity as PlanetComponent;
 pub use super::planet_member::Entity as PlanetMember;
 pub use super::planet_role::Entity as PlanetRole;
 pub use super::token::Entity as Token;
 pub use super::user::Entity as User;
+pub use super::wiki::Entity as Wiki;
+pub use super::wiki_article::Entity as WikiArticle;
+pub use super::wiki_revision::Entity as WikiRevision;
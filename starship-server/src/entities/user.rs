@@ -19,6 +19,9 @@ pub struct Model {
     pub blocked: Vec<String>,
     pub sessions: Vec<Uuid>,
     pub banned: bool,
+    pub ban_reason: Option<String>,
+    pub ban_expires: Option<DateTime>,
+    pub banned_by: Option<String>,
     pub admin: bool,
     pub notification_setting: i16,
     pub cap_waived: bool,
@@ -32,12 +35,19 @@ pub struct Model {
     pub token_geofenced: bool,
     pub token_expires: bool,
     pub token_ip_locked: bool,
+    pub status_message: Option<String>,
+    pub invisible: bool,
+    pub calendar_token: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::audit_log::Entity")]
+    AuditLog,
     #[sea_orm(has_many = "super::custom_emoji::Entity")]
     CustomEmoji,
+    #[sea_orm(has_many = "super::notification::Entity")]
+    Notification,
     #[sea_orm(has_many = "super::planet::Entity")]
     Planet,
     #[sea_orm(has_many = "super::planet_member::Entity")]
@@ -46,12 +56,24 @@ pub enum Relation {
     Token,
 }
 
+impl Related<super::audit_log::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AuditLog.def()
+    }
+}
+
 impl Related<super::custom_emoji::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::CustomEmoji.def()
     }
 }
 
+impl Related<super::notification::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notification.def()
+    }
+}
+
 impl Related<super::planet::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Planet.def()
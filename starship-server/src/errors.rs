@@ -1,4 +1,5 @@
 use async_graphql::{Error, ErrorExtensionValues};
+use chrono::NaiveDateTime;
 
 pub fn create_user_input_error(message: &str, code: &str) -> Error {
     let mut extensions = ErrorExtensionValues::default();
@@ -53,3 +54,38 @@ pub fn create_forbidden_error(message: Option<&str>, code: &str) -> Error {
         extensions: Some(extensions),
     }
 }
+
+pub fn create_banned_error(
+    subject: &str,
+    reason: Option<&str>,
+    expires: Option<NaiveDateTime>,
+    code: &str,
+) -> Error {
+    let mut extensions = ErrorExtensionValues::default();
+
+    extensions.set("type", "FORBIDDEN");
+    extensions.set("code", code);
+
+    let mut message = format!("You are banned from {subject}.");
+
+    if let Some(reason) = reason {
+        extensions.set("reason", reason);
+        message.push_str(&format!(" Reason: {}.", reason.trim_end_matches('.')));
+    }
+
+    if let Some(expires) = expires {
+        extensions.set("expires", expires.format("%Y-%m-%dT%H:%M:%S").to_string());
+        message.push_str(&format!(
+            " This ban expires on {} UTC.",
+            expires.format("%Y-%m-%d at %H:%M")
+        ));
+    } else {
+        message.push_str(" This ban is permanent.");
+    }
+
+    Error {
+        message,
+        source: None,
+        extensions: Some(extensions),
+    }
+}
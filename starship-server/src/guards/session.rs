@@ -35,7 +35,12 @@ impl Guard for SessionGuard {
         if self.session_type == SessionType::Admin && !user.admin {
             Err(errors::create_forbidden_error(None, "NOT_GLOBAL_ADMIN"))
         } else if self.session_type == SessionType::NotBanned && user.banned {
-            Err(errors::create_forbidden_error(None, "USER_BANNED"))
+            Err(errors::create_banned_error(
+                "Starship",
+                user.ban_reason.as_deref(),
+                user.ban_expires,
+                "USER_BANNED",
+            ))
         } else if self.session_type != SessionType::Token && !session.verified {
             Err(errors::create_forbidden_error(None, "UNVERIFIED_TOKEN"))
         } else {
@@ -0,0 +1,187 @@
+use crate::audit;
+use crate::entities::audit_log::AuditAction;
+use crate::entities::auto_role::{self, AutoRoleTrigger};
+use crate::entities::{auto_role_assignment, planet_member, planet_role};
+use crate::permissions::checks;
+use async_graphql::Error;
+use chrono::NaiveDateTime;
+use log::error;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, Select,
+    TransactionTrait, UpdateMany,
+};
+use std::time::Duration;
+
+/// How often the automatic role rules are evaluated.
+const INTERVAL: Duration = Duration::from_secs(60);
+
+/// The largest number of members a single rule assigns its role to each time it is evaluated.
+/// Any remaining members are picked up the next time the rules are evaluated.
+pub const BATCH_SIZE: u64 = 500;
+
+/// Evaluates every automatic role rule on an interval, forever.
+pub async fn run(db: DatabaseConnection) {
+    let mut interval = actix_web::rt::time::interval(INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = evaluate_rules(&db).await {
+            error!("failed to evaluate automatic role rules: {}", err.message);
+        }
+    }
+}
+
+/// Evaluates every automatic role rule once. Rules that fail to be applied are logged and skipped,
+/// so that one broken rule doesn't stop the others.
+async fn evaluate_rules(db: &DatabaseConnection) -> Result<(), Error> {
+    let rules = auto_role::Entity::find().all(db).await?;
+
+    for rule in rules {
+        if let Err(err) = apply_rule(db, &rule).await {
+            error!(
+                "failed to apply automatic role rule {}: {}",
+                rule.id, err.message
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks whether or not the member that created a rule can still assign its role. Rules created by
+/// members that have since left, been banned, or lost their rank stop assigning their role.
+async fn creator_high_enough(
+    db: &DatabaseConnection,
+    rule: &auto_role::Model,
+    role: &planet_role::Model,
+) -> Result<bool, Error> {
+    let creator = planet_member::Entity::find()
+        .filter(
+            planet_member::Column::User
+                .eq(rule.created_by.clone())
+                .and(planet_member::Column::Planet.eq(rule.planet.clone())),
+        )
+        .one(db)
+        .await?;
+
+    let Some(creator) = creator.filter(|creator| !creator.banned) else {
+        return Ok(false);
+    };
+
+    let creator_roles = planet_role::Entity::find()
+        .filter(planet_role::Column::Id.is_in(creator.roles.clone()))
+        .all(db)
+        .await?;
+
+    Ok(checks::high_enough(
+        Some(creator_roles),
+        vec![role.clone()],
+        Some(creator),
+    ))
+}
+
+/// Builds the query for the members of a planet that a rule applies to, that don't have its role
+/// yet and that the rule hasn't already assigned its role to, at most [`BATCH_SIZE`] at a time.
+/// Members are only given the role once by each rule, so a role a moderator removes stays removed.
+pub fn eligible_members(
+    rule: &auto_role::Model,
+    role_id: &str,
+    now: NaiveDateTime,
+) -> Select<planet_member::Entity> {
+    let query = planet_member::Entity::find()
+        .filter(planet_member::Column::Planet.eq(rule.planet.clone()))
+        .filter(planet_member::Column::Banned.eq(false))
+        .filter(Expr::cust_with_values(
+            r#"NOT ("planet_member"."roles" @> $1)"#,
+            [vec![role_id.to_string()]],
+        ))
+        .filter(Expr::cust_with_values(
+            r#"NOT EXISTS (SELECT 1 FROM "auto_role_assignment"
+            WHERE "auto_role_assignment"."rule" = $1
+            AND "auto_role_assignment"."member" = "planet_member"."id")"#,
+            [rule.id.clone()],
+        ));
+
+    let query = match rule.trigger {
+        AutoRoleTrigger::Join => query.filter(planet_member::Column::Created.gte(rule.created)),
+        AutoRoleTrigger::MembershipAge => {
+            let days = chrono::Duration::days(i64::from(rule.days.unwrap_or_default()));
+
+            query.filter(planet_member::Column::Created.lte(now - days))
+        }
+    };
+
+    query.limit(BATCH_SIZE)
+}
+
+/// Builds the update that adds a role to a member. The update only applies while the member is
+/// unbanned and doesn't have the role, so that it can't race with other changes to their roles.
+pub fn assign_role(member_id: &str, role_id: &str) -> UpdateMany<planet_member::Entity> {
+    planet_member::Entity::update_many()
+        .col_expr(
+            planet_member::Column::Roles,
+            Expr::cust_with_values(r#"array_append("roles", $1)"#, [role_id]),
+        )
+        .filter(planet_member::Column::Id.eq(member_id))
+        .filter(planet_member::Column::Banned.eq(false))
+        .filter(Expr::cust_with_values(
+            r#"NOT ("planet_member"."roles" @> $1)"#,
+            [vec![role_id.to_string()]],
+        ))
+}
+
+/// Assigns a rule's role to the members it applies to that don't have it yet, recording each member
+/// it is assigned to.
+async fn apply_rule(db: &DatabaseConnection, rule: &auto_role::Model) -> Result<(), Error> {
+    let Some(role) = planet_role::Entity::find_by_id(rule.role.clone())
+        .one(db)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    if !creator_high_enough(db, rule, &role).await? {
+        return Ok(());
+    }
+
+    let members = eligible_members(rule, &role.id, chrono::offset::Utc::now().naive_utc())
+        .all(db)
+        .await?;
+
+    for member in members {
+        let txn = db.begin().await?;
+
+        let result = assign_role(&member.id, &role.id).exec(&txn).await?;
+
+        // the member changed since they were found, so they are left for the next evaluation
+        if result.rows_affected == 0 {
+            txn.rollback().await?;
+            continue;
+        }
+
+        auto_role_assignment::Entity::insert(auto_role_assignment::ActiveModel {
+            rule: ActiveValue::Set(rule.id.clone()),
+            member: ActiveValue::Set(member.id.clone()),
+            planet: ActiveValue::Set(member.planet.clone()),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        })
+        .exec(&txn)
+        .await?;
+
+        audit::log_action(
+            &txn,
+            member.planet.clone(),
+            None,
+            AuditAction::MemberAddRole,
+            Some(member.id),
+            Some(role.id.clone()),
+        )
+        .await?;
+
+        txn.commit().await?;
+    }
+
+    Ok(())
+}
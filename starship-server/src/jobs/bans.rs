@@ -0,0 +1,70 @@
+use crate::audit;
+use crate::entities::audit_log::AuditAction;
+use async_graphql::Error;
+use log::error;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement, TransactionTrait};
+use std::time::Duration;
+
+/// How often expired bans are lifted.
+const INTERVAL: Duration = Duration::from_secs(60);
+
+/// Lifts expired planet and user bans on an interval, forever. Members whose ban has expired stay
+/// banned until this next runs. Sessions already treat users whose ban has expired as unbanned.
+pub async fn run(db: DatabaseConnection) {
+    let mut interval = actix_web::rt::time::interval(INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = lift_expired_bans(&db).await {
+            error!("failed to lift expired bans: {}", err.message);
+        }
+    }
+}
+
+/// Lifts every planet and user ban that has expired, recording each planet unban in its planet's
+/// audit log.
+async fn lift_expired_bans(db: &DatabaseConnection) -> Result<(), Error> {
+    let now = chrono::offset::Utc::now().naive_utc();
+    let txn = db.begin().await?;
+
+    let lifted = txn
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"UPDATE "planet_member"
+            SET "banned" = FALSE, "ban_reason" = NULL, "ban_expires" = NULL, "banned_by" = NULL,
+                "banned_at" = NULL
+            WHERE "banned" AND "ban_expires" <= $1
+            RETURNING "id", "planet";"#,
+            [now.into()],
+        ))
+        .await?;
+
+    for row in lifted {
+        let id: String = row.try_get("", "id")?;
+        let planet: String = row.try_get("", "planet")?;
+
+        audit::log_action(
+            &txn,
+            planet,
+            None,
+            AuditAction::MemberUnban,
+            Some(id),
+            Some("Ban expired.".to_string()),
+        )
+        .await?;
+    }
+
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"UPDATE "user"
+        SET "banned" = FALSE, "ban_reason" = NULL, "ban_expires" = NULL, "banned_by" = NULL
+        WHERE "banned" AND "ban_expires" <= $1;"#,
+        [now.into()],
+    ))
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
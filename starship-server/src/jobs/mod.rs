@@ -0,0 +1,15 @@
+//! Background jobs that run for as long as the server is running.
+
+pub mod auto_roles;
+mod bans;
+mod presence;
+
+use crate::presence::Presence;
+use sea_orm::DatabaseConnection;
+
+/// Starts every background job on the current runtime.
+pub fn spawn(db: &DatabaseConnection, presence: &Presence) {
+    actix_web::rt::spawn(auto_roles::run(db.clone()));
+    actix_web::rt::spawn(bans::run(db.clone()));
+    actix_web::rt::spawn(presence::run(presence.clone()));
+}
@@ -0,0 +1,15 @@
+use crate::presence::Presence;
+use std::time::Duration;
+
+/// How often users that have gone offline are forgotten.
+const INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Prunes offline users from the presence tracker on an interval, forever.
+pub async fn run(presence: Presence) {
+    let mut interval = actix_web::rt::time::interval(INTERVAL);
+
+    loop {
+        interval.tick().await;
+        presence.prune();
+    }
+}
@@ -0,0 +1,275 @@
+//! Batches the lookups GraphQL resolvers make by ID, so that a list of objects doesn't need a
+//! query for each object's relations.
+//!
+//! Resolvers that run alongside each other, such as the same field on every item in a list, ask
+//! a [`DataLoader`] for the IDs they need. The loader waits for every resolver to ask before
+//! loading all of the IDs with a single query.
+
+use crate::entities::{planet, planet_component, planet_member, planet_role, user};
+use crate::errors;
+use crate::permissions::util;
+use async_graphql::dataloader::{DataLoader, HashMapCache, Loader};
+use async_graphql::Error;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use std::collections::HashMap;
+
+/// A loader that keeps the values it has loaded, unless caching is turned off.
+pub type CachedLoader<L> = DataLoader<L, HashMapCache>;
+
+/// Creates a loader that batches on the current runtime. If `cache` is set, values are kept once
+/// they have been loaded, so the loader should only live as long as a single request.
+pub fn create_loader<L: Loader<String>>(loader: L, cache: bool) -> CachedLoader<L> {
+    let data_loader = DataLoader::with_cache(loader, actix_web::rt::spawn, HashMapCache::default());
+    data_loader.enable_all_cache(cache);
+    data_loader
+}
+
+/// Loads the values with some IDs, in the same order as the IDs. IDs without a value are left
+/// out.
+pub async fn load_ordered<L: Loader<String>>(
+    data_loader: &CachedLoader<L>,
+    ids: &[String],
+) -> Result<Vec<L::Value>, L::Error> {
+    let values = data_loader.load_many(ids.iter().cloned()).await?;
+
+    Ok(ids
+        .iter()
+        .filter_map(|id| values.get(id).cloned())
+        .collect())
+}
+
+pub struct UserLoader(DatabaseConnection);
+
+#[async_trait::async_trait]
+impl Loader<String> for UserLoader {
+    type Value = user::Model;
+    type Error = Error;
+
+    async fn load(&self, ids: &[String]) -> Result<HashMap<String, user::Model>, Error> {
+        Ok(user::Entity::find()
+            .filter(user::Column::Id.is_in(ids.to_vec()))
+            .all(&self.0)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "LOAD_USERS_ERROR"))?
+            .into_iter()
+            .map(|user| (user.id.clone(), user))
+            .collect())
+    }
+}
+
+pub struct PlanetLoader(DatabaseConnection);
+
+#[async_trait::async_trait]
+impl Loader<String> for PlanetLoader {
+    type Value = planet::Model;
+    type Error = Error;
+
+    async fn load(&self, ids: &[String]) -> Result<HashMap<String, planet::Model>, Error> {
+        Ok(planet::Entity::find()
+            .filter(planet::Column::Id.is_in(ids.to_vec()))
+            .all(&self.0)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "LOAD_PLANETS_ERROR"))?
+            .into_iter()
+            .map(|planet| (planet.id.clone(), planet))
+            .collect())
+    }
+}
+
+pub struct RoleLoader(DatabaseConnection);
+
+#[async_trait::async_trait]
+impl Loader<String> for RoleLoader {
+    type Value = planet_role::Model;
+    type Error = Error;
+
+    async fn load(&self, ids: &[String]) -> Result<HashMap<String, planet_role::Model>, Error> {
+        Ok(planet_role::Entity::find()
+            .filter(planet_role::Column::Id.is_in(ids.to_vec()))
+            .all(&self.0)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "LOAD_ROLES_ERROR"))?
+            .into_iter()
+            .map(|role| (role.id.clone(), role))
+            .collect())
+    }
+}
+
+pub struct ComponentLoader(DatabaseConnection);
+
+#[async_trait::async_trait]
+impl Loader<String> for ComponentLoader {
+    type Value = planet_component::Model;
+    type Error = Error;
+
+    async fn load(
+        &self,
+        ids: &[String],
+    ) -> Result<HashMap<String, planet_component::Model>, Error> {
+        Ok(planet_component::Entity::find()
+            .filter(planet_component::Column::Id.is_in(ids.to_vec()))
+            .all(&self.0)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "LOAD_COMPONENTS_ERROR"))?
+            .into_iter()
+            .map(|component| (component.id.clone(), component))
+            .collect())
+    }
+}
+
+/// Loads the components nested inside of components by the parents' IDs, in order. Components
+/// without any children have no value.
+pub struct ChildrenLoader(DatabaseConnection);
+
+#[async_trait::async_trait]
+impl Loader<String> for ChildrenLoader {
+    type Value = Vec<planet_component::Model>;
+    type Error = Error;
+
+    async fn load(
+        &self,
+        ids: &[String],
+    ) -> Result<HashMap<String, Vec<planet_component::Model>>, Error> {
+        let components = planet_component::Entity::find()
+            .filter(planet_component::Column::ParentId.is_in(ids.to_vec()))
+            .order_by_asc(planet_component::Column::Position)
+            .all(&self.0)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "LOAD_CHILDREN_ERROR"))?;
+
+        let mut children: HashMap<String, Vec<planet_component::Model>> = HashMap::new();
+
+        for component in components {
+            if let Some(parent_id) = component.parent_id.clone() {
+                children.entry(parent_id).or_default().push(component);
+            }
+        }
+
+        Ok(children)
+    }
+}
+
+/// The current user's membership of a planet.
+#[derive(Clone)]
+pub struct Membership {
+    pub planet: planet::Model,
+    pub member: Option<planet_member::Model>,
+    pub roles: Option<Vec<planet_role::Model>>,
+}
+
+impl Membership {
+    /// Checks whether or not the current user has a permission in the planet. If they don't, an
+    /// error ready for presentation to the client is returned.
+    pub fn check_permission(&self, permission: &str) -> Result<(), Error> {
+        util::check_permission(
+            permission,
+            &self.planet,
+            self.member.clone(),
+            self.roles.clone(),
+        )
+    }
+}
+
+/// Loads the current user's membership of planets by the planets' IDs, so that permission checks
+/// repeated for every item in a list only need to look the membership up once.
+pub struct MembershipLoader {
+    db: DatabaseConnection,
+    user_id: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl Loader<String> for MembershipLoader {
+    type Value = Membership;
+    type Error = Error;
+
+    async fn load(&self, ids: &[String]) -> Result<HashMap<String, Membership>, Error> {
+        let planets = planet::Entity::find()
+            .filter(planet::Column::Id.is_in(ids.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "LOAD_PLANETS_ERROR"))?;
+
+        let members = match &self.user_id {
+            Some(user_id) => planet_member::Entity::find()
+                .filter(planet_member::Column::User.eq(user_id.clone()))
+                .filter(planet_member::Column::Planet.is_in(ids.to_vec()))
+                .all(&self.db)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "LOAD_MEMBERS_ERROR"))?,
+            None => vec![],
+        };
+
+        let role_ids: Vec<String> = members
+            .iter()
+            .flat_map(|member| member.roles.iter().cloned())
+            .collect();
+
+        let roles = if role_ids.is_empty() {
+            vec![]
+        } else {
+            planet_role::Entity::find()
+                .filter(planet_role::Column::Id.is_in(role_ids))
+                .all(&self.db)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "LOAD_ROLES_ERROR"))?
+        };
+
+        Ok(planets
+            .into_iter()
+            .map(|planet| {
+                let member = members
+                    .iter()
+                    .find(|member| member.planet == planet.id)
+                    .cloned();
+                let member_roles = member.as_ref().map(|member| {
+                    roles
+                        .iter()
+                        .filter(|role| member.roles.contains(&role.id))
+                        .cloned()
+                        .collect()
+                });
+
+                (
+                    planet.id.clone(),
+                    Membership {
+                        planet,
+                        member,
+                        roles: member_roles,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+/// The loaders available to resolvers through the context.
+pub struct Loaders {
+    pub users: CachedLoader<UserLoader>,
+    pub planets: CachedLoader<PlanetLoader>,
+    pub roles: CachedLoader<RoleLoader>,
+    pub components: CachedLoader<ComponentLoader>,
+    pub children: CachedLoader<ChildrenLoader>,
+    pub memberships: CachedLoader<MembershipLoader>,
+}
+
+impl Loaders {
+    /// Creates a set of loaders. Caching should only be turned on for loaders that live as long
+    /// as a single request, so that long-lived connections don't see stale values. Memberships are
+    /// loaded for the user with `user_id`, who should be the user the request is made by.
+    pub fn new(db: &DatabaseConnection, user_id: Option<String>, cache: bool) -> Loaders {
+        Loaders {
+            users: create_loader(UserLoader(db.clone()), cache),
+            planets: create_loader(PlanetLoader(db.clone()), cache),
+            roles: create_loader(RoleLoader(db.clone()), cache),
+            components: create_loader(ComponentLoader(db.clone()), cache),
+            children: create_loader(ChildrenLoader(db.clone()), cache),
+            memberships: create_loader(
+                MembershipLoader {
+                    db: db.clone(),
+                    user_id,
+                },
+                cache,
+            ),
+        }
+    }
+}
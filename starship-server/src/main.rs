@@ -1,41 +1,123 @@
 #![allow(clippy::unused_async)]
 
+mod archive;
+mod audit;
 mod components;
+mod config;
 mod db;
+mod direct_messages;
 mod entities;
 mod errors;
 mod guards;
+mod jobs;
+mod loaders;
+mod metrics;
 mod mutations;
+mod notifications;
 mod permissions;
+mod presence;
 mod queries;
+mod routes;
 mod sessions;
+mod storage;
+mod subscriptions;
 mod tests;
 
 use actix_cors::Cors;
+use actix_web::http::header;
 use actix_web::{guard, web, App, HttpRequest, HttpResponse, HttpServer};
-use async_graphql::{http::GraphiQLSource, EmptySubscription, Schema};
-use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
+use async_graphql::{http::GraphiQLSource, Data, Schema};
+use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse, GraphQLSubscription};
+use components::registry::ComponentRegistry;
+use config::Config;
 use db::set_up;
+use futures_util::StreamExt;
 use log::info;
 use sea_orm::DatabaseConnection;
-use std::env;
 use std::io::Result;
+use std::sync::{Arc, OnceLock};
+
+type StarshipSchema = Schema<queries::Query, mutations::Mutation, subscriptions::Subscription>;
 
 async fn index(
-    schema: web::Data<Schema<queries::Query, mutations::Mutation, EmptySubscription>>,
+    schema: web::Data<StarshipSchema>,
     db: web::Data<DatabaseConnection>,
+    config: web::Data<Config>,
     req: HttpRequest,
     gql_req: GraphQLRequest,
 ) -> GraphQLResponse {
+    let db = (*db.into_inner()).clone();
+    let session = sessions::Session::make_session_from_request(&req, &config, db.clone()).await;
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
     let mut request = gql_req.into_inner();
     request = request
-        .data(sessions::Session::make_session_from_request(&req, (*db.into_inner()).clone()).await);
+        .data(loaders::Loaders::new(&db, user_id, true))
+        .data(session);
     schema.execute(request).await.into()
 }
 
-async fn gql_schema(
-    schema: web::Data<Schema<queries::Query, mutations::Mutation, EmptySubscription>>,
-) -> HttpResponse {
+/// Serves GraphQL subscriptions over a WebSocket. Clients authenticate by sending an
+/// `Authorization` value in their `connection_init` payload. Signed in users are shown as online
+/// for as long as the connection stays open, and idle once they haven't sent anything over it for a
+/// while.
+async fn index_ws(
+    schema: web::Data<StarshipSchema>,
+    db: web::Data<DatabaseConnection>,
+    presence: web::Data<presence::Presence>,
+    config: web::Data<Config>,
+    req: HttpRequest,
+    payload: web::Payload,
+) -> actix_web::Result<HttpResponse> {
+    let db = (*db.into_inner()).clone();
+    let config = (*config.into_inner()).clone();
+    let user_agent = req
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().map(std::string::ToString::to_string).ok());
+    let ip_address = req.peer_addr();
+    let presence = (*presence.into_inner()).clone();
+    let guard = Arc::new(OnceLock::<presence::PresenceGuard>::new());
+    let connection_guard = guard.clone();
+
+    // every message from the client counts as activity, so connected users only go idle when
+    // their client stops sending anything
+    let payload = payload.inspect(move |_| {
+        if let Some(guard) = guard.get() {
+            guard.activity();
+        }
+    });
+
+    GraphQLSubscription::new(StarshipSchema::clone(&schema))
+        .on_connection_init(move |value| async move {
+            let session = sessions::Session::make_session_from_payload(
+                &value,
+                user_agent,
+                ip_address,
+                &config,
+                db.clone(),
+            )
+            .await;
+
+            // values aren't cached, since the connection can stay open for a long time
+            let user_id = session.user.as_ref().map(|user| user.id.clone());
+            let loaders = loaders::Loaders::new(&db, user_id, false);
+
+            let mut data = Data::default();
+            data.insert(loaders);
+
+            // the guard lives as long as the connection's payload stream, so the user goes offline
+            // when the connection closes
+            if let Some(user) = session.user.as_ref() {
+                let _ = connection_guard.set(presence.connect(user.id.clone()));
+            }
+
+            data.insert(session);
+            Ok(data)
+        })
+        .start(&req, payload)
+}
+
+async fn gql_schema(schema: web::Data<StarshipSchema>) -> HttpResponse {
     HttpResponse::Ok()
         .content_type("text/plain; charset=utf-8")
         .body(schema.sdl())
@@ -58,46 +140,99 @@ async fn main() -> Result<()> {
     info!("Leading environment variables");
     dotenv::dotenv().ok();
 
+    info!("Loading configuration");
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => panic!("fatal: {err}"),
+    };
+
     info!("Connecting to database");
-    let db = match set_up().await {
-        Ok(db) => db,
+    let (db, pool) = match set_up(&config.database).await {
+        Ok(connection) => connection,
         Err(err) => panic!("fatal: {err} "),
     };
 
+    let presence = presence::Presence::default();
+
+    info!("Starting background jobs");
+    jobs::spawn(&db, &presence);
+
+    info!("Setting up file storage");
+    let storage = storage::from_config(&config.storage);
+
+    info!("Registering component types");
+    let registry = ComponentRegistry::with_builtin_components(storage.clone());
+
+    let metrics = metrics::Metrics::default();
+
     info!("Creating schema");
     let schema = Schema::build(
         queries::Query::default(),
         mutations::Mutation::default(),
-        EmptySubscription,
+        subscriptions::Subscription::default(),
     )
-    .limit_complexity(1000) // just prevent the queries from being absurd for now
-    .limit_depth(10)
+    .limit_complexity(config.limits.query_complexity)
+    .limit_depth(config.limits.query_depth)
+    .extension(metrics::MetricsExtension(metrics.clone()))
     .data(db.clone())
+    .data(config.clone())
+    .data(registry)
+    .data(storage.clone())
+    .data(subscriptions::Broker::default())
+    .data(presence.clone())
     .finish();
 
     info!("Creating HttpServer");
+    let address = (config.server.ip_addr.clone(), config.server.port);
+
     HttpServer::new(move || {
-        let cors = Cors::permissive();
+        let cors = if config.cors.origins.is_empty() {
+            Cors::permissive()
+        } else {
+            config
+                .cors
+                .origins
+                .iter()
+                .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin))
+                .allow_any_method()
+                .allow_any_header()
+        };
+
+        let features = &config.features;
 
         App::new()
             .wrap(cors)
             .app_data(web::Data::new(schema.clone()))
             .app_data(web::Data::new(db.clone()))
+            .app_data(web::Data::new(storage.clone()))
+            .app_data(web::Data::new(presence.clone()))
+            .app_data(web::Data::new(config.clone()))
+            .app_data(web::Data::new(metrics.clone()))
+            .app_data(web::Data::new(pool.clone()))
             .service(web::resource("/graphql").guard(guard::Post()).to(index))
+            .configure(|cfg| {
+                if features.subscriptions {
+                    cfg.service(
+                        web::resource("/graphql")
+                            .guard(guard::Get())
+                            .guard(guard::Header("upgrade", "websocket"))
+                            .to(index_ws),
+                    );
+                }
+            })
             .service(web::resource("/schema").guard(guard::Get()).to(gql_schema))
-            .service(
-                web::resource("/graphql")
-                    .guard(guard::Get())
-                    .to(gql_playgound),
-            )
+            .configure(|cfg| {
+                if features.playground {
+                    cfg.service(
+                        web::resource("/graphql")
+                            .guard(guard::Get())
+                            .to(gql_playgound),
+                    );
+                }
+            })
+            .configure(routes::configure)
     })
-    .bind((
-        env::var("IP_ADDR").expect("fatal: address unspecified"),
-        env::var("PORT")
-            .expect("fatal: port unspecified")
-            .parse()
-            .expect("fatal: port is not a number"),
-    ))?
+    .bind(address)?
     .run()
     .await
 }
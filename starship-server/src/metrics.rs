@@ -0,0 +1,293 @@
+//! Metrics about the server, served in the Prometheus text format at `/metrics`.
+//!
+//! GraphQL requests are measured by [`MetricsExtension`], which is added to the schema. Everything
+//! else, such as database pool usage, is read when the metrics are rendered.
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextParseQuery, NextPrepareRequest, NextRequest,
+};
+use async_graphql::parser::types::{DocumentOperations, ExecutableDocument};
+use async_graphql::{Request, Response, ServerResult, Value, Variables};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// The upper bounds, in seconds, of the buckets GraphQL latencies are counted in.
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// The most operation names that are tracked separately. Operation names come from clients, so
+/// anything past this is counted under `other` to stop the metrics from growing without limit.
+const MAX_OPERATIONS: usize = 200;
+
+/// The name requests without an operation name are counted under.
+const ANONYMOUS_OPERATION: &str = "anonymous";
+
+#[derive(Clone, Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if value <= bound {
+                *bucket += 1;
+            }
+        }
+
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct Recorded {
+    operations: BTreeMap<String, Histogram>,
+    /// Error counts, keyed by operation, then the error's type and code.
+    errors: BTreeMap<(String, String, String), u64>,
+}
+
+/// Values that are read when the metrics are rendered, rather than recorded as they happen.
+pub struct Gauges {
+    /// The number of connections open to the database, in use or not.
+    pub pool_connections: u32,
+    /// The number of database connections that aren't in use.
+    pub pool_idle: usize,
+    /// The most connections the pool will open.
+    pub pool_max: u32,
+    /// The number of open WebSocket connections from signed in users.
+    pub active_sessions: usize,
+    /// The number of users that are online or idle.
+    pub online_users: usize,
+}
+
+/// The metrics recorded since the server started.
+#[derive(Clone, Default)]
+pub struct Metrics {
+    recorded: Arc<Mutex<Recorded>>,
+}
+
+/// Escapes a label value for the Prometheus text format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Gets an error extension as a string, for use as a label.
+fn extension(response_error: &async_graphql::ServerError, name: &str) -> String {
+    match response_error
+        .extensions
+        .as_ref()
+        .and_then(|extensions| extensions.get(name))
+    {
+        Some(Value::String(value)) => value.clone(),
+        _ => "UNKNOWN".to_string(),
+    }
+}
+
+impl Metrics {
+    /// Records a finished GraphQL request, along with any errors in its response.
+    pub fn record_request(&self, operation: Option<&str>, seconds: f64, response: &Response) {
+        let mut recorded = self.recorded.lock().unwrap();
+
+        let operation = operation.unwrap_or(ANONYMOUS_OPERATION);
+        let operation = if recorded.operations.contains_key(operation)
+            || recorded.operations.len() < MAX_OPERATIONS
+        {
+            operation
+        } else {
+            "other"
+        };
+
+        recorded
+            .operations
+            .entry(operation.to_string())
+            .or_default()
+            .observe(seconds);
+
+        for error in &response.errors {
+            *recorded
+                .errors
+                .entry((
+                    operation.to_string(),
+                    extension(error, "type"),
+                    extension(error, "code"),
+                ))
+                .or_default() += 1;
+        }
+    }
+
+    /// Renders every metric in the Prometheus text format.
+    pub fn render(&self, gauges: &Gauges) -> String {
+        let recorded = self.recorded.lock().unwrap();
+        let mut out = String::new();
+
+        // writing to a string can't fail, so the results are ignored
+        let _ = writeln!(
+            out,
+            "# HELP starship_graphql_request_duration_seconds How long GraphQL requests take, by operation."
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE starship_graphql_request_duration_seconds histogram"
+        );
+
+        for (operation, histogram) in &recorded.operations {
+            let operation = escape(operation);
+
+            for (count, bound) in histogram.buckets.iter().zip(LATENCY_BUCKETS) {
+                let _ = writeln!(
+                    out,
+                    "starship_graphql_request_duration_seconds_bucket{{operation=\"{operation}\",le=\"{bound}\"}} {count}"
+                );
+            }
+
+            let _ = writeln!(
+                out,
+                "starship_graphql_request_duration_seconds_bucket{{operation=\"{operation}\",le=\"+Inf\"}} {}",
+                histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "starship_graphql_request_duration_seconds_sum{{operation=\"{operation}\"}} {}",
+                histogram.sum
+            );
+            let _ = writeln!(
+                out,
+                "starship_graphql_request_duration_seconds_count{{operation=\"{operation}\"}} {}",
+                histogram.count
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP starship_graphql_errors_total GraphQL errors returned, by operation and the error's type and code."
+        );
+        let _ = writeln!(out, "# TYPE starship_graphql_errors_total counter");
+
+        for ((operation, error_type, code), count) in &recorded.errors {
+            let _ = writeln!(
+                out,
+                "starship_graphql_errors_total{{operation=\"{}\",type=\"{}\",code=\"{}\"}} {count}",
+                escape(operation),
+                escape(error_type),
+                escape(code)
+            );
+        }
+
+        let gauges = [
+            (
+                "starship_db_pool_connections",
+                "Connections open to the database.",
+                gauges.pool_connections as usize,
+            ),
+            (
+                "starship_db_pool_idle_connections",
+                "Database connections that aren't in use.",
+                gauges.pool_idle,
+            ),
+            (
+                "starship_db_pool_max_connections",
+                "The most connections the database pool will open.",
+                gauges.pool_max as usize,
+            ),
+            (
+                "starship_active_sessions",
+                "Open WebSocket connections from signed in users.",
+                gauges.active_sessions,
+            ),
+            (
+                "starship_online_users",
+                "Users that are online or idle.",
+                gauges.online_users,
+            ),
+        ];
+
+        for (name, help, value) in gauges {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} gauge");
+            let _ = writeln!(out, "{name} {value}");
+        }
+
+        out
+    }
+}
+
+/// Measures every GraphQL request made to the schema it is added to.
+pub struct MetricsExtension(pub Metrics);
+
+impl ExtensionFactory for MetricsExtension {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RequestMetrics {
+            metrics: self.0.clone(),
+            operation: Mutex::new(None),
+        })
+    }
+}
+
+/// Measures a single request. A new one is created for every request.
+struct RequestMetrics {
+    metrics: Metrics,
+    operation: Mutex<Option<String>>,
+}
+
+#[async_trait::async_trait]
+impl Extension for RequestMetrics {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let start = Instant::now();
+        let response = next.run(ctx).await;
+
+        let operation = self.operation.lock().unwrap().clone();
+        self.metrics.record_request(
+            operation.as_deref(),
+            start.elapsed().as_secs_f64(),
+            &response,
+        );
+
+        response
+    }
+
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        self.operation
+            .lock()
+            .unwrap()
+            .clone_from(&request.operation_name);
+
+        next.run(ctx, request).await
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+
+        // clients often leave out the operation name when the query only has one operation
+        let mut operation = self.operation.lock().unwrap();
+
+        if operation.is_none() {
+            if let DocumentOperations::Multiple(operations) = &document.operations {
+                if operations.len() == 1 {
+                    *operation = operations.keys().next().map(ToString::to_string);
+                }
+            }
+        }
+
+        Ok(document)
+    }
+}
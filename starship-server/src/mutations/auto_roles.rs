@@ -0,0 +1,187 @@
+use crate::audit;
+use crate::db;
+use crate::entities::audit_log::AuditAction;
+use crate::entities::auto_role::{self, AutoRoleTrigger};
+use crate::entities::{auto_role_assignment, planet_role};
+use crate::errors;
+use crate::permissions::util;
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use nanoid::nanoid;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+
+/// The longest a member can be required to be on a planet before a rule gives them a role.
+const MAX_DAYS: i32 = 3650;
+
+#[derive(Default, Description)]
+pub struct AutoRoleMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl AutoRoleMutation {
+    /// Creates a rule that gives a role to members automatically, either when they join the planet
+    /// or once they have been on the planet for a number of days. Rules are evaluated in the
+    /// background, so members may not receive the role immediately.
+    ///
+    /// A rule only assigns its role while the member that created it is still allowed to assign
+    /// that role themselves.
+    #[graphql(complexity = 50)]
+    async fn create_auto_role(
+        &self,
+        ctx: &Context<'_>,
+        role_id: ID,
+        trigger: AutoRoleTrigger,
+        days: Option<i32>,
+    ) -> Result<auto_role::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let role = planet_role::Entity::find_by_id(role_id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "ROLE_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_not_found_error())?;
+
+        let planet = util::get_planet(role.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), role.planet.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission(
+            "planet.roles.auto_assign",
+            &planet,
+            member.clone(),
+            roles.clone(),
+        )?;
+        util::high_enough(roles, vec![role.clone()], member)?;
+
+        if role.default {
+            return Err(errors::create_user_input_error(
+                "Every member already has the default role.",
+                "DEFAULT_ROLE",
+            ));
+        }
+
+        match (trigger, days) {
+            (AutoRoleTrigger::Join, Some(_)) => {
+                return Err(errors::create_user_input_error(
+                    "Rules that apply when members join cannot have a number of days.",
+                    "UNEXPECTED_DAYS",
+                ));
+            }
+            (AutoRoleTrigger::MembershipAge, None) => {
+                return Err(errors::create_user_input_error(
+                    "Rules based on how long members have been on the planet need a number of days.",
+                    "MISSING_DAYS",
+                ));
+            }
+            (AutoRoleTrigger::MembershipAge, Some(days)) if !(1..=MAX_DAYS).contains(&days) => {
+                return Err(errors::create_user_input_error(
+                    &format!("The number of days must be between 1 and {MAX_DAYS}."),
+                    "INVALID_DAYS",
+                ));
+            }
+            _ => {}
+        }
+
+        let rule = auto_role::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            planet: ActiveValue::Set(planet.id.clone()),
+            role: ActiveValue::Set(role.id.clone()),
+            trigger: ActiveValue::Set(trigger),
+            days: ActiveValue::Set(days),
+            created_by: ActiveValue::Set(user_id.clone().unwrap()),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        let txn = db::begin(db).await?;
+
+        let insertion = auto_role::Entity::insert(rule)
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+        let rule = auto_role::Entity::find_by_id(insertion.last_insert_id)
+            .one(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "RULE_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(None, "FIND_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            planet.id,
+            user_id,
+            AuditAction::AutoRoleCreate,
+            Some(rule.id.clone()),
+            Some(role.id),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(rule)
+    }
+
+    /// Deletes an automatic role rule. Members that already received the role keep it.
+    #[graphql(complexity = 50)]
+    async fn delete_auto_role(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let rule = auto_role::Entity::find_by_id(id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "RULE_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_not_found_error())?;
+
+        let role = planet_role::Entity::find_by_id(rule.role.clone())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "ROLE_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "ROLE_MISSING_ERROR",
+            ))?;
+
+        let planet = util::get_planet(rule.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), rule.planet.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission(
+            "planet.roles.auto_assign",
+            &planet,
+            member.clone(),
+            roles.clone(),
+        )?;
+        util::high_enough(roles, vec![role], member)?;
+
+        let active_rule: auto_role::ActiveModel = rule.clone().into();
+
+        let txn = db::begin(db).await?;
+
+        auto_role_assignment::Entity::delete_many()
+            .filter(auto_role_assignment::Column::Rule.eq(rule.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_ASSIGNMENTS_ERROR"))?;
+
+        active_rule
+            .delete(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_RULE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            rule.planet,
+            user_id,
+            AuditAction::AutoRoleDelete,
+            Some(rule.id),
+            Some(rule.role),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(true)
+    }
+}
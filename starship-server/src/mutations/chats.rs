@@ -0,0 +1,335 @@
+use crate::audit;
+use crate::components::access::ComponentAccess;
+use crate::components::chat::{check_can_send, reaction_counts, validate_content, validate_topic};
+use crate::components::forum::{validate_emoji, ReactionCount};
+use crate::db;
+use crate::entities::audit_log::AuditAction;
+use crate::entities::{chat, chat_message, chat_reaction};
+use crate::errors;
+use crate::guards::session::{SessionGuard, SessionType};
+use crate::notifications;
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use nanoid::nanoid;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+
+/// Gets a chat message along with the chat component it belongs to.
+async fn get_message(
+    ctx: &Context<'_>,
+    id: &ID,
+) -> Result<(ComponentAccess, chat_message::Model), Error> {
+    let db = ctx.data::<DatabaseConnection>().unwrap();
+    let session = ctx.data::<Session>().unwrap();
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+    let message = chat_message::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "MESSAGE_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let access = ComponentAccess::load_by_data(message.chat.clone(), "chat", user_id, db).await?;
+    access.check_permission("chat.view")?;
+
+    Ok((access, message))
+}
+
+#[derive(Default, Description)]
+pub struct ChatMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl ChatMutation {
+    /// Sends a message to a chat. Attachments require the `chat.messages.attach` permission.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn send_chat_message(
+        &self,
+        ctx: &Context<'_>,
+        component_id: ID,
+        content: String,
+        attachments: Option<Vec<String>>,
+    ) -> Result<chat_message::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let access =
+            ComponentAccess::load(component_id.to_string(), "chat", Some(user_id.clone()), db)
+                .await?;
+
+        let attachments = attachments.unwrap_or_default();
+        check_can_send(&access, &attachments)?;
+
+        validate_content(&content, !attachments.is_empty())?;
+
+        let message = chat_message::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            chat: ActiveValue::Set(access.component.component_id.clone()),
+            author: ActiveValue::Set(user_id),
+            content: ActiveValue::Set(content),
+            attachments: ActiveValue::Set(attachments),
+            pinned: ActiveValue::Set(false),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+            edited: ActiveValue::Set(None),
+        };
+
+        let txn = db::begin(db).await?;
+
+        let message = message
+            .insert(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+        notifications::notify_mentions(
+            &txn,
+            &access,
+            "chat.view",
+            message.id.clone(),
+            &message.content,
+        )
+        .await?;
+
+        access.record_activity(&txn).await?;
+
+        db::commit(txn).await?;
+
+        Ok(message)
+    }
+
+    /// Edits the content of a message, recording when it was edited.
+    #[graphql(complexity = 20)]
+    async fn edit_chat_message(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        content: String,
+    ) -> Result<chat_message::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, message) = get_message(ctx, &id).await?;
+        access.check_ownership_permission("chat.messages.edit", &message.author)?;
+
+        validate_content(&content, !message.attachments.is_empty())?;
+
+        let mut active_message: chat_message::ActiveModel = message.into();
+        active_message.content = ActiveValue::Set(content);
+        active_message.edited = ActiveValue::Set(Some(chrono::offset::Utc::now().naive_utc()));
+
+        let txn = db::begin(db).await?;
+
+        let message = active_message
+            .update(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        access.record_activity(&txn).await?;
+
+        db::commit(txn).await?;
+
+        Ok(message)
+    }
+
+    /// Deletes a message, along with its reactions.
+    #[graphql(complexity = 20)]
+    async fn delete_chat_message(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, message) = get_message(ctx, &id).await?;
+        access.check_ownership_permission("chat.messages.delete", &message.author)?;
+
+        let txn = db::begin(db).await?;
+
+        chat_reaction::Entity::delete_many()
+            .filter(chat_reaction::Column::Message.eq(message.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REACTIONS_ERROR"))?;
+
+        chat_message::Entity::delete_by_id(message.id.clone())
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_MESSAGE_ERROR"))?;
+
+        if access.user_id.as_ref() != Some(&message.author) {
+            audit::log_action(
+                &txn,
+                access.planet.id,
+                access.user_id,
+                AuditAction::ChatMessageDelete,
+                Some(message.id),
+                Some(message.author),
+            )
+            .await?;
+        }
+
+        db::commit(txn).await?;
+
+        Ok(true)
+    }
+
+    /// Sets whether or not a message is pinned to its chat.
+    #[graphql(complexity = 10)]
+    async fn set_chat_message_pinned(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        pinned: bool,
+    ) -> Result<chat_message::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, message) = get_message(ctx, &id).await?;
+        access.check_ownership_permission("chat.messages.pin", &message.author)?;
+
+        let mut active_message: chat_message::ActiveModel = message.into();
+        active_message.pinned = ActiveValue::Set(pinned);
+
+        let txn = db::begin(db).await?;
+
+        let message = active_message
+            .update(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            access.planet.id,
+            access.user_id,
+            AuditAction::ChatMessagePin,
+            Some(message.id.clone()),
+            Some(pinned.to_string()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(message)
+    }
+
+    /// Sets the topic of a chat. An empty topic removes it.
+    #[graphql(complexity = 10)]
+    async fn set_chat_topic(
+        &self,
+        ctx: &Context<'_>,
+        component_id: ID,
+        topic: Option<String>,
+    ) -> Result<chat::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let access = ComponentAccess::load(component_id.to_string(), "chat", user_id, db).await?;
+        access.check_permission("chat.set_topic")?;
+
+        let topic = validate_topic(topic)?;
+
+        let chat = chat::Entity::find_by_id(access.component.component_id.clone())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "CHAT_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "CHAT_MISSING_ERROR",
+            ))?;
+
+        let mut active_chat: chat::ActiveModel = chat.into();
+        active_chat.topic = ActiveValue::Set(topic.clone());
+
+        let txn = db::begin(db).await?;
+
+        let chat = active_chat
+            .update(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            access.planet.id,
+            access.user_id,
+            AuditAction::ChatSetTopic,
+            Some(access.component.id),
+            topic,
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(chat)
+    }
+
+    /// Reacts to a message. Returns the updated reactions.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn add_chat_reaction(
+        &self,
+        ctx: &Context<'_>,
+        message_id: ID,
+        emoji: String,
+    ) -> Result<Vec<ReactionCount>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let (access, message) = get_message(ctx, &message_id).await?;
+        access.check_ownership_permission("chat.messages.react", &message.author)?;
+
+        validate_emoji(&emoji)?;
+
+        let existing = chat_reaction::Entity::find()
+            .filter(chat_reaction::Column::Message.eq(message.id.clone()))
+            .filter(chat_reaction::Column::User.eq(user_id.clone()))
+            .filter(chat_reaction::Column::Emoji.eq(emoji.clone()))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "REACTION_RETRIEVAL_ERROR"))?;
+
+        if existing.is_some() {
+            return Err(errors::create_user_input_error(
+                "You have already reacted with that emoji.",
+                "ALREADY_REACTED",
+            ));
+        }
+
+        let reaction = chat_reaction::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            message: ActiveValue::Set(message.id.clone()),
+            user: ActiveValue::Set(user_id.clone()),
+            emoji: ActiveValue::Set(emoji),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        chat_reaction::Entity::insert(reaction)
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+        reaction_counts(&message.id, Some(&user_id), db).await
+    }
+
+    /// Removes the current user's reaction from a message. Returns the updated reactions.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn remove_chat_reaction(
+        &self,
+        ctx: &Context<'_>,
+        message_id: ID,
+        emoji: String,
+    ) -> Result<Vec<ReactionCount>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let (_, message) = get_message(ctx, &message_id).await?;
+
+        chat_reaction::Entity::delete_many()
+            .filter(chat_reaction::Column::Message.eq(message.id.clone()))
+            .filter(chat_reaction::Column::User.eq(user_id.clone()))
+            .filter(chat_reaction::Column::Emoji.eq(emoji))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REACTION_ERROR"))?;
+
+        reaction_counts(&message.id, Some(&user_id), db).await
+    }
+}
@@ -1,11 +1,20 @@
-use crate::components::index::{create_component, delete_component};
-use crate::entities::planet_component;
+use crate::audit;
+use crate::components::registry::ComponentRegistry;
+use crate::components::tree;
+use crate::db;
+use crate::entities::audit_log::AuditAction;
+use crate::entities::{component_permission, planet_component, planet_member, planet_role};
 use crate::errors;
 use crate::permissions::util;
 use crate::sessions::Session;
+use crate::subscriptions::{Broker, ChangeKind, PlanetEvent};
 use async_graphql::{Context, Description, Error, Object, ID};
 use nanoid::nanoid;
-use sea_orm::{ActiveModelTrait, ActiveValue, DatabaseConnection, EntityTrait, QueryOrder};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder,
+};
 
 #[derive(Default, Description)]
 pub struct ComponentMutation;
@@ -22,6 +31,8 @@ impl ComponentMutation {
         component: String,
     ) -> Result<planet_component::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
+        let registry = ctx.data::<ComponentRegistry>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -30,12 +41,19 @@ impl ComponentMutation {
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission("planet.component.create", &planet, member, roles)?;
 
-        let component_id =
-            create_component(component.as_str(), planet_id.to_string(), user_id.unwrap()).await?;
+        let component_type = registry.get(&component)?;
+
+        let txn = db::begin(db).await?;
+
+        let component_id = component_type
+            .create(&txn, &planet.id, user_id.as_deref().unwrap())
+            .await?;
 
         let position = planet_component::Entity::find()
+            .filter(planet_component::Column::Planet.eq(planet.id.clone()))
+            .filter(planet_component::Column::ParentId.is_null())
             .order_by_asc(planet_component::Column::Position)
-            .one(db)
+            .one(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "ORDER_RETRIEVAL_ERROR"))?
             .map_or(0, |component| component.position)
@@ -53,15 +71,35 @@ impl ComponentMutation {
         };
 
         let component_result = planet_component::Entity::insert(component)
-            .exec(db)
+            .exec(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
 
-        planet_component::Entity::find_by_id(component_result.last_insert_id)
-            .one(db)
+        let component = planet_component::Entity::find_by_id(component_result.last_insert_id)
+            .one(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "RETRIEVAL_ERROR"))?
-            .ok_or(errors::create_internal_server_error(None, "MISSING_ERROR"))
+            .ok_or(errors::create_internal_server_error(None, "MISSING_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            component.planet.clone(),
+            user_id,
+            AuditAction::ComponentCreate,
+            Some(component.id.clone()),
+            Some(component.name.clone()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::ComponentChanged {
+            planet: component.planet.clone(),
+            component: component.id.clone(),
+            change: ChangeKind::Created,
+        });
+
+        Ok(component)
     }
 
     /// Deletes a component.
@@ -73,6 +111,8 @@ impl ComponentMutation {
         token: Option<u32>,
     ) -> Result<bool, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
+        let registry = ctx.data::<ComponentRegistry>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -83,7 +123,7 @@ impl ComponentMutation {
             .ok_or(errors::create_not_found_error())?;
 
         let planet = util::get_planet(component.clone().planet, db).await?;
-        let member = util::get_planet_member(user_id, component.clone().planet, db).await?;
+        let member = util::get_planet_member(user_id.clone(), component.clone().planet, db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission("planet.component.delete", &planet, member, roles)?;
 
@@ -95,15 +135,60 @@ impl ComponentMutation {
             ));
         }
 
-        delete_component(&component.r#type, component.id.clone()).await?;
+        let txn = db::begin(db).await?;
+
+        let cleanup = registry
+            .get(&component.r#type)?
+            .delete(&txn, &component.component_id)
+            .await?;
 
-        let active_component: planet_component::ActiveModel = component.into();
+        // the component's children take its place in the tree
+        planet_component::Entity::update_many()
+            .col_expr(
+                planet_component::Column::ParentId,
+                Expr::value(component.parent_id.clone()),
+            )
+            .filter(planet_component::Column::ParentId.eq(component.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_CHILDREN_ERROR"))?;
+
+        component_permission::Entity::delete_many()
+            .filter(component_permission::Column::Component.eq(component.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_OVERRIDES_ERROR"))?;
+
+        let active_component: planet_component::ActiveModel = component.clone().into();
 
         active_component
-            .delete(db)
+            .delete(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "DELETE_PLANET_ERROR"))
-            .map(|_| true)
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_PLANET_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            component.planet.clone(),
+            user_id,
+            AuditAction::ComponentDelete,
+            Some(component.id.clone()),
+            Some(component.name),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        if let Some(cleanup) = cleanup {
+            cleanup.await;
+        }
+
+        broker.publish(PlanetEvent::ComponentChanged {
+            planet: component.planet.clone(),
+            component: component.id.clone(),
+            change: ChangeKind::Deleted,
+        });
+
+        Ok(true)
     }
 
     /// Renames a component.
@@ -115,6 +200,7 @@ impl ComponentMutation {
         name: String,
     ) -> Result<planet_component::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -125,16 +211,327 @@ impl ComponentMutation {
             .ok_or(errors::create_not_found_error())?;
 
         let planet = util::get_planet(component.clone().planet, db).await?;
-        let member = util::get_planet_member(user_id, component.clone().planet, db).await?;
+        let member = util::get_planet_member(user_id.clone(), component.clone().planet, db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission("planet.component.rename", &planet, member, roles)?;
 
         let mut active_component: planet_component::ActiveModel = component.into();
         active_component.name = ActiveValue::Set(name);
 
-        active_component
-            .update(db)
+        let txn = db::begin(db).await?;
+
+        let component = active_component
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            component.planet.clone(),
+            user_id,
+            AuditAction::ComponentRename,
+            Some(component.id.clone()),
+            Some(component.name.clone()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::ComponentChanged {
+            planet: component.planet.clone(),
+            component: component.id.clone(),
+            change: ChangeKind::Updated,
+        });
+
+        Ok(component)
+    }
+
+    /// Moves a component inside of another component, or to the top level of its planet if
+    /// `parentId` isn't provided. `position` is the index the component is placed at among its new
+    /// siblings.
+    #[graphql(complexity = 50)]
+    async fn move_component(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        parent_id: Option<ID>,
+        position: u32,
+    ) -> Result<planet_component::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let component = planet_component::Entity::find_by_id(id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_not_found_error())?;
+
+        let planet = util::get_planet(component.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), component.planet.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission("planet.component.move", &planet, member, roles)?;
+
+        let components = planet_component::Entity::find()
+            .filter(planet_component::Column::Planet.eq(planet.id.clone()))
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_COMPONENTS_ERROR"))?;
+
+        let parent_id = parent_id.map(|id| id.to_string());
+
+        if let Some(parent_id) = &parent_id {
+            if !components.iter().any(|other| &other.id == parent_id) {
+                return Err(errors::create_not_found_error());
+            }
+
+            if planet.home == Some(component.id.clone()) {
+                return Err(errors::create_user_input_error(
+                    "The home component cannot have a parent.",
+                    "HOME",
+                ));
+            }
+
+            if tree::would_create_cycle(&component.id, parent_id, &components) {
+                return Err(errors::create_user_input_error(
+                    "A component cannot be moved inside of itself.",
+                    "CYCLE",
+                ));
+            }
+        }
+
+        // the component is placed last among its old siblings, so that the rest close the gap it
+        // leaves behind and it can be given its new position below
+        let old_order = if component.parent_id != parent_id {
+            let old_siblings = components
+                .iter()
+                .filter(|other| other.parent_id == component.parent_id)
+                .cloned()
+                .collect();
+
+            tree::reorder(&component.id, usize::MAX, old_siblings)
+        } else {
+            vec![]
+        };
+
+        let siblings = components
+            .into_iter()
+            .filter(|other| other.parent_id == parent_id)
+            .collect();
+        let order = tree::reorder(&component.id, position as usize, siblings);
+
+        let txn = db::begin(db).await?;
+
+        for (index, sibling) in old_order.iter().enumerate() {
+            if sibling == &component.id {
+                continue;
+            }
+
+            planet_component::Entity::update_many()
+                .col_expr(
+                    planet_component::Column::Position,
+                    Expr::value(index as i32),
+                )
+                .filter(planet_component::Column::Id.eq(sibling.clone()))
+                .exec(&txn)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+        }
+
+        for (index, sibling) in order.iter().enumerate() {
+            let mut update = planet_component::Entity::update_many().col_expr(
+                planet_component::Column::Position,
+                Expr::value(index as i32),
+            );
+
+            if sibling == &component.id {
+                update = update.col_expr(
+                    planet_component::Column::ParentId,
+                    Expr::value(parent_id.clone()),
+                );
+            }
+
+            update
+                .filter(planet_component::Column::Id.eq(sibling.clone()))
+                .exec(&txn)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+        }
+
+        let component = planet_component::Entity::find_by_id(component.id)
+            .one(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(None, "MISSING_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            component.planet.clone(),
+            user_id,
+            AuditAction::ComponentMove,
+            Some(component.id.clone()),
+            parent_id,
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::ComponentChanged {
+            planet: component.planet.clone(),
+            component: component.id.clone(),
+            change: ChangeKind::Updated,
+        });
+
+        Ok(component)
+    }
+
+    /// Updates a component's permission overrides for a role, a member, or everyone on the planet
+    /// if neither is provided. Permissions use the same prefixes as role permissions, and the
+    /// override is removed once it no longer changes any permissions.
+    #[graphql(complexity = 50)]
+    async fn update_component_permissions(
+        &self,
+        ctx: &Context<'_>,
+        component_id: ID,
+        role_id: Option<ID>,
+        member_id: Option<ID>,
+        permissions: Vec<String>,
+    ) -> Result<Vec<component_permission::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let component = planet_component::Entity::find_by_id(component_id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_not_found_error())?;
+
+        let planet = util::get_planet(component.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), component.planet.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission(
+            "planet.component.edit_permissions",
+            &planet,
+            member.clone(),
+            roles.clone(),
+        )?;
+
+        if role_id.is_some() && member_id.is_some() {
+            return Err(errors::create_user_input_error(
+                "An override can only apply to a role or a member, not both.",
+                "INVALID_TARGET",
+            ));
+        }
+
+        if let Some(role_id) = &role_id {
+            let role = planet_role::Entity::find_by_id(role_id.to_string())
+                .one(db)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "ROLE_RETRIEVAL_ERROR"))?
+                .filter(|role| role.planet == planet.id)
+                .ok_or(errors::create_not_found_error())?;
+
+            util::high_enough(roles, vec![role], member)?;
+        } else if let Some(member_id) = &member_id {
+            let target = planet_member::Entity::find_by_id(member_id.to_string())
+                .one(db)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "TARGET_RETRIEVAL_ERROR"))?
+                .filter(|target| target.planet == planet.id)
+                .ok_or(errors::create_not_found_error())?;
+            let target_roles = util::get_member_roles(Some(target), db)
+                .await?
+                .unwrap_or_default();
+
+            util::high_enough(roles, target_roles, member)?;
+        }
+
+        let role_condition = match &role_id {
+            Some(role_id) => component_permission::Column::Role.eq(role_id.to_string()),
+            None => component_permission::Column::Role.is_null(),
+        };
+        let member_condition = match &member_id {
+            Some(member_id) => component_permission::Column::Member.eq(member_id.to_string()),
+            None => component_permission::Column::Member.is_null(),
+        };
+
+        let existing = component_permission::Entity::find()
+            .filter(
+                Condition::all()
+                    .add(component_permission::Column::Component.eq(component.id.clone()))
+                    .add(role_condition)
+                    .add(member_condition),
+            )
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "OVERRIDE_RETRIEVAL_ERROR"))?;
+
+        let updated_permissions = util::update_permissions(
+            existing
+                .as_ref()
+                .map(|o| o.permissions.clone())
+                .unwrap_or_default(),
+            permissions.clone(),
+        )?;
+
+        let txn = db::begin(db).await?;
+
+        match (existing, updated_permissions.is_empty()) {
+            (Some(existing), true) => {
+                let active_override: component_permission::ActiveModel = existing.into();
+
+                active_override.delete(&txn).await.map_err(|_| {
+                    errors::create_internal_server_error(None, "DELETE_OVERRIDE_ERROR")
+                })?;
+            }
+            (Some(existing), false) => {
+                let mut active_override: component_permission::ActiveModel = existing.into();
+                active_override.permissions = ActiveValue::Set(updated_permissions);
+
+                active_override
+                    .update(&txn)
+                    .await
+                    .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+            }
+            (None, false) => {
+                let active_override = component_permission::ActiveModel {
+                    id: ActiveValue::Set(nanoid!(16)),
+                    planet: ActiveValue::Set(planet.id.clone()),
+                    component: ActiveValue::Set(component.id.clone()),
+                    role: ActiveValue::Set(role_id.map(|id| id.to_string())),
+                    member: ActiveValue::Set(member_id.map(|id| id.to_string())),
+                    permissions: ActiveValue::Set(updated_permissions),
+                };
+
+                component_permission::Entity::insert(active_override)
+                    .exec(&txn)
+                    .await
+                    .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+            }
+            (None, true) => {}
+        }
+
+        audit::log_action(
+            &txn,
+            planet.id,
+            user_id,
+            AuditAction::ComponentUpdatePermissions,
+            Some(component.id.clone()),
+            Some(permissions.join(" ")),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::ComponentChanged {
+            planet: component.planet.clone(),
+            component: component.id.clone(),
+            change: ChangeKind::Updated,
+        });
+
+        util::get_component_overrides(component.id, db).await
     }
 }
@@ -0,0 +1,346 @@
+use crate::components::chat::validate_content;
+use crate::db::{begin, commit};
+use crate::direct_messages::{
+    blocked_by_any, check_enabled, get_conversation, other_members, validate_name,
+    MAX_CONVERSATION_MEMBERS,
+};
+use crate::entities::notification::NotificationKind;
+use crate::entities::{conversation, conversation_member, direct_message, user};
+use crate::errors;
+use crate::guards::session::{SessionGuard, SessionType};
+use crate::notifications::{self, NewNotification};
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use nanoid::nanoid;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+
+/// Gets a direct message written by the current user, checking that they are still in its
+/// conversation.
+async fn get_own_message(ctx: &Context<'_>, id: &ID) -> Result<direct_message::Model, Error> {
+    let db = ctx.data::<DatabaseConnection>().unwrap();
+    let session = ctx.data::<Session>().unwrap();
+    // unwrap is safe because guard guarantees we have a user
+    let user_id = session.user.as_ref().unwrap().id.clone();
+
+    let message = direct_message::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "MESSAGE_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    get_conversation(message.conversation.clone(), &user_id, db).await?;
+
+    if message.author != user_id {
+        return Err(errors::create_forbidden_error(
+            Some("You can only change your own messages."),
+            "FORBIDDEN",
+        ));
+    }
+
+    Ok(message)
+}
+
+fn blocked_error() -> Error {
+    errors::create_forbidden_error(
+        Some("You can't send direct messages to this user."),
+        "BLOCKED",
+    )
+}
+
+#[derive(Default, Description)]
+pub struct ConversationMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl ConversationMutation {
+    /// Starts a conversation with other users. A conversation with a single other user is a
+    /// one-to-one conversation, and starting one with someone you already have one with returns
+    /// the existing conversation. Conversations with more users are groups, and can be named.
+    /// Conversations can't be started with anyone that has blocked you.
+    #[graphql(guard = "SessionGuard::new(SessionType::NotBanned)", complexity = 50)]
+    async fn start_conversation(
+        &self,
+        ctx: &Context<'_>,
+        user_ids: Vec<ID>,
+        name: Option<String>,
+    ) -> Result<conversation::Model, Error> {
+        check_enabled(ctx)?;
+
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let mut ids: Vec<String> = vec![];
+
+        for id in user_ids {
+            let id = id.to_string();
+
+            if id != user_id && !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        if ids.is_empty() {
+            return Err(errors::create_user_input_error(
+                "Conversations need at least one other user.",
+                "NO_USERS",
+            ));
+        }
+
+        if ids.len() >= MAX_CONVERSATION_MEMBERS {
+            return Err(errors::create_user_input_error(
+                "Conversations cannot have more than 10 users.",
+                "TOO_MANY_USERS",
+            ));
+        }
+
+        let users = user::Entity::find()
+            .filter(user::Column::Id.is_in(ids.clone()))
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_USERS_ERROR"))?;
+
+        if users.len() != ids.len() {
+            return Err(errors::create_not_found_error());
+        }
+
+        if blocked_by_any(&user_id, &users) {
+            return Err(blocked_error());
+        }
+
+        let group = ids.len() > 1;
+        let name = if group { validate_name(name)? } else { None };
+
+        if !group {
+            let own_conversations: Vec<String> = conversation_member::Entity::find()
+                .filter(conversation_member::Column::User.eq(user_id.clone()))
+                .find_also_related(conversation::Entity)
+                .all(db)
+                .await
+                .map_err(|_| {
+                    errors::create_internal_server_error(None, "FIND_CONVERSATIONS_ERROR")
+                })?
+                .into_iter()
+                .filter_map(|(_, conversation)| conversation)
+                .filter(|conversation| !conversation.group)
+                .map(|conversation| conversation.id)
+                .collect();
+
+            let existing = conversation_member::Entity::find()
+                .filter(conversation_member::Column::User.eq(ids[0].clone()))
+                .filter(conversation_member::Column::Conversation.is_in(own_conversations))
+                .find_also_related(conversation::Entity)
+                .one(db)
+                .await
+                .map_err(|_| {
+                    errors::create_internal_server_error(None, "FIND_CONVERSATIONS_ERROR")
+                })?;
+
+            if let Some((_, Some(conversation))) = existing {
+                return Ok(conversation);
+            }
+        }
+
+        let now = chrono::offset::Utc::now().naive_utc();
+
+        let conversation = conversation::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            name: ActiveValue::Set(name),
+            group: ActiveValue::Set(group),
+            creator: ActiveValue::Set(user_id.clone()),
+            created: ActiveValue::Set(now),
+            updated: ActiveValue::Set(now),
+        };
+
+        let txn = begin(db).await?;
+
+        let conversation = conversation::Entity::insert(conversation)
+            .exec_with_returning(&txn)
+            .await
+            .map_err(|_| {
+                errors::create_internal_server_error(None, "CONVERSATION_INSERTION_ERROR")
+            })?;
+
+        let members =
+            std::iter::once(user_id)
+                .chain(ids)
+                .map(|member| conversation_member::ActiveModel {
+                    id: ActiveValue::Set(nanoid!(16)),
+                    conversation: ActiveValue::Set(conversation.id.clone()),
+                    user: ActiveValue::Set(member),
+                    joined: ActiveValue::Set(now),
+                });
+
+        conversation_member::Entity::insert_many(members)
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "MEMBER_INSERTION_ERROR"))?;
+
+        commit(txn).await?;
+
+        Ok(conversation)
+    }
+
+    /// Sends a message to a conversation. Messages can't be sent to a one-to-one conversation if
+    /// the other user has blocked you. In groups, members that have blocked you don't see your
+    /// messages.
+    #[graphql(guard = "SessionGuard::new(SessionType::NotBanned)", complexity = 20)]
+    async fn send_direct_message(
+        &self,
+        ctx: &Context<'_>,
+        conversation_id: ID,
+        content: String,
+    ) -> Result<direct_message::Model, Error> {
+        check_enabled(ctx)?;
+
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let (conversation, members) =
+            get_conversation(conversation_id.to_string(), &user_id, db).await?;
+        let recipients = other_members(&members, &user_id, db).await?;
+
+        if !conversation.group && blocked_by_any(&user_id, &recipients) {
+            return Err(blocked_error());
+        }
+
+        validate_content(&content, false)?;
+
+        let now = chrono::offset::Utc::now().naive_utc();
+
+        let message = direct_message::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            conversation: ActiveValue::Set(conversation.id.clone()),
+            author: ActiveValue::Set(user_id.clone()),
+            content: ActiveValue::Set(content),
+            created: ActiveValue::Set(now),
+            edited: ActiveValue::Set(None),
+        };
+
+        let txn = begin(db).await?;
+
+        let message = message
+            .insert(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+        conversation::Entity::update_many()
+            .col_expr(conversation::Column::Updated, Expr::value(now))
+            .filter(conversation::Column::Id.eq(conversation.id))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_CONVERSATION_ERROR"))?;
+
+        // members that have blocked the sender are skipped by `notify`
+        for recipient in recipients {
+            notifications::notify(
+                &txn,
+                NewNotification {
+                    user: recipient.id,
+                    kind: NotificationKind::DirectMessage,
+                    planet: None,
+                    component: None,
+                    target: Some(message.id.clone()),
+                    actor: Some(user_id.clone()),
+                    content: message.content.clone(),
+                },
+            )
+            .await?;
+        }
+
+        commit(txn).await?;
+
+        Ok(message)
+    }
+
+    /// Edits the content of one of your messages, recording when it was edited.
+    #[graphql(guard = "SessionGuard::new(SessionType::NotBanned)", complexity = 20)]
+    async fn edit_direct_message(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        content: String,
+    ) -> Result<direct_message::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let message = get_own_message(ctx, &id).await?;
+
+        validate_content(&content, false)?;
+
+        let mut active_message: direct_message::ActiveModel = message.into();
+        active_message.content = ActiveValue::Set(content);
+        active_message.edited = ActiveValue::Set(Some(chrono::offset::Utc::now().naive_utc()));
+
+        active_message
+            .update(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+    }
+
+    /// Deletes one of your messages.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn delete_direct_message(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let message = get_own_message(ctx, &id).await?;
+
+        direct_message::Entity::delete_by_id(message.id)
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_MESSAGE_ERROR"))?;
+
+        Ok(true)
+    }
+
+    /// Leaves a group conversation. The conversation is deleted, along with its messages, once
+    /// everyone has left.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 50)]
+    async fn leave_conversation(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let (conversation, members) = get_conversation(id.to_string(), &user_id, db).await?;
+
+        if !conversation.group {
+            return Err(errors::create_user_input_error(
+                "You can't leave a one-to-one conversation.",
+                "NOT_GROUP",
+            ));
+        }
+
+        let txn = begin(db).await?;
+
+        conversation_member::Entity::delete_many()
+            .filter(conversation_member::Column::Conversation.eq(conversation.id.clone()))
+            .filter(conversation_member::Column::User.eq(user_id))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_MEMBER_ERROR"))?;
+
+        if members.len() == 1 {
+            direct_message::Entity::delete_many()
+                .filter(direct_message::Column::Conversation.eq(conversation.id.clone()))
+                .exec(&txn)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "DELETE_MESSAGES_ERROR"))?;
+
+            conversation::Entity::delete_by_id(conversation.id)
+                .exec(&txn)
+                .await
+                .map_err(|_| {
+                    errors::create_internal_server_error(None, "DELETE_CONVERSATION_ERROR")
+                })?;
+        }
+
+        commit(txn).await?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,227 @@
+use crate::audit;
+use crate::components::access::ComponentAccess;
+use crate::components::events::{validate_event, EventInput};
+use crate::db;
+use crate::entities::audit_log::AuditAction;
+use crate::entities::event;
+use crate::entities::event_rsvp::{self, RsvpStatus};
+use crate::errors;
+use crate::guards::session::{SessionGuard, SessionType};
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use nanoid::nanoid;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+
+/// Gets an event along with the events component it belongs to.
+async fn get_event(ctx: &Context<'_>, id: &ID) -> Result<(ComponentAccess, event::Model), Error> {
+    let db = ctx.data::<DatabaseConnection>().unwrap();
+    let session = ctx.data::<Session>().unwrap();
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+    let event = event::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "EVENT_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let access =
+        ComponentAccess::load_by_data(event.calendar.clone(), "events", user_id, db).await?;
+    access.check_permission("events.view")?;
+
+    Ok((access, event))
+}
+
+/// Sets the details of an event from validated input.
+fn apply_input(event: &mut event::ActiveModel, input: EventInput) {
+    event.title = ActiveValue::Set(input.title);
+    event.description = ActiveValue::Set(input.description);
+    event.location = ActiveValue::Set(input.location);
+    event.start = ActiveValue::Set(input.start);
+    event.end = ActiveValue::Set(input.end);
+    event.timezone = ActiveValue::Set(input.timezone);
+    event.recurrence = ActiveValue::Set(input.recurrence.as_ref().map(|r| r.frequency));
+    event.recurrence_interval =
+        ActiveValue::Set(input.recurrence.as_ref().map_or(1, |r| r.interval));
+    event.recurrence_count = ActiveValue::Set(input.recurrence.as_ref().and_then(|r| r.count));
+    event.recurrence_until = ActiveValue::Set(input.recurrence.as_ref().and_then(|r| r.until));
+}
+
+#[derive(Default, Description)]
+pub struct EventMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl EventMutation {
+    /// Creates an event in an events component.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn create_event(
+        &self,
+        ctx: &Context<'_>,
+        component_id: ID,
+        event: EventInput,
+    ) -> Result<event::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let access = ComponentAccess::load(
+            component_id.to_string(),
+            "events",
+            Some(user_id.clone()),
+            db,
+        )
+        .await?;
+        access.check_permission("events.view")?;
+        access.check_permission("events.create")?;
+
+        let input = validate_event(event)?;
+
+        let mut event = event::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            calendar: ActiveValue::Set(access.component.component_id.clone()),
+            creator: ActiveValue::Set(user_id),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+            updated: ActiveValue::Set(None),
+            ..Default::default()
+        };
+        apply_input(&mut event, input);
+
+        let event = event
+            .insert(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+        access.record_activity(db).await?;
+
+        Ok(event)
+    }
+
+    /// Replaces the details of an event.
+    #[graphql(complexity = 20)]
+    async fn update_event(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        event: EventInput,
+    ) -> Result<event::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, existing) = get_event(ctx, &id).await?;
+        access.check_ownership_permission("events.edit", &existing.creator)?;
+
+        let input = validate_event(event)?;
+
+        let mut active_event: event::ActiveModel = existing.into();
+        apply_input(&mut active_event, input);
+        active_event.updated = ActiveValue::Set(Some(chrono::offset::Utc::now().naive_utc()));
+
+        active_event
+            .update(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+    }
+
+    /// Deletes an event, along with its RSVPs.
+    #[graphql(complexity = 20)]
+    async fn delete_event(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, event) = get_event(ctx, &id).await?;
+        access.check_ownership_permission("events.delete", &event.creator)?;
+
+        let txn = db::begin(db).await?;
+
+        event_rsvp::Entity::delete_many()
+            .filter(event_rsvp::Column::Event.eq(event.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_RSVPS_ERROR"))?;
+
+        event::Entity::delete_by_id(event.id.clone())
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_EVENT_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            access.planet.id,
+            access.user_id,
+            AuditAction::EventDelete,
+            Some(event.id),
+            Some(event.title),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(true)
+    }
+
+    /// Responds to an event, replacing any previous response. Recurring events have one response
+    /// for every occurrence.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 10)]
+    async fn set_event_rsvp(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        status: RsvpStatus,
+    ) -> Result<event_rsvp::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, event) = get_event(ctx, &id).await?;
+        access.check_permission("events.rsvp")?;
+
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = access.user_id.unwrap();
+
+        let existing = event_rsvp::Entity::find()
+            .filter(event_rsvp::Column::Event.eq(event.id.clone()))
+            .filter(event_rsvp::Column::User.eq(user_id.clone()))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_RSVP_ERROR"))?;
+
+        let rsvp = match existing {
+            Some(existing) => {
+                let mut active_rsvp: event_rsvp::ActiveModel = existing.into();
+                active_rsvp.status = ActiveValue::Set(status);
+                active_rsvp.update(db).await
+            }
+            None => {
+                event_rsvp::ActiveModel {
+                    id: ActiveValue::Set(nanoid!(16)),
+                    event: ActiveValue::Set(event.id),
+                    user: ActiveValue::Set(user_id),
+                    status: ActiveValue::Set(status),
+                    created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+                }
+                .insert(db)
+                .await
+            }
+        };
+
+        rsvp.map_err(|_| errors::create_internal_server_error(None, "RSVP_ERROR"))
+    }
+
+    /// Removes the current user's response to an event.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 10)]
+    async fn remove_event_rsvp(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, event) = get_event(ctx, &id).await?;
+
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = access.user_id.unwrap();
+
+        event_rsvp::Entity::delete_many()
+            .filter(event_rsvp::Column::Event.eq(event.id))
+            .filter(event_rsvp::Column::User.eq(user_id))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_RSVP_ERROR"))?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,231 @@
+use crate::audit;
+use crate::components::access::ComponentAccess;
+use crate::components::files::{
+    delete_files, folder_with_descendants, get_folder, remove_contents, validate_name,
+};
+use crate::db;
+use crate::entities::audit_log::AuditAction;
+use crate::entities::{file, file_folder};
+use crate::errors;
+use crate::guards::session::{SessionGuard, SessionType};
+use crate::sessions::Session;
+use crate::storage::Storage;
+use async_graphql::{Context, Description, Error, Object, ID};
+use nanoid::nanoid;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+use std::sync::Arc;
+
+/// Gets a folder along with the files component it belongs to.
+async fn get_folder_access(
+    ctx: &Context<'_>,
+    id: &ID,
+) -> Result<(ComponentAccess, file_folder::Model), Error> {
+    let db = ctx.data::<DatabaseConnection>().unwrap();
+    let session = ctx.data::<Session>().unwrap();
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+    let folder = file_folder::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FOLDER_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let access = ComponentAccess::load_by_data(folder.tree.clone(), "files", user_id, db).await?;
+    access.check_permission("files.view")?;
+
+    Ok((access, folder))
+}
+
+/// Gets a file along with the files component it belongs to.
+async fn get_file_access(
+    ctx: &Context<'_>,
+    id: &ID,
+) -> Result<(ComponentAccess, file::Model), Error> {
+    let db = ctx.data::<DatabaseConnection>().unwrap();
+    let session = ctx.data::<Session>().unwrap();
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+    let file = file::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FILE_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let access = ComponentAccess::load_by_data(file.tree.clone(), "files", user_id, db).await?;
+    access.check_permission("files.view")?;
+
+    Ok((access, file))
+}
+
+#[derive(Default, Description)]
+pub struct FileMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl FileMutation {
+    /// Creates a folder inside of another folder, or at the top of the tree if `parentId` isn't
+    /// provided.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn create_file_folder(
+        &self,
+        ctx: &Context<'_>,
+        component_id: ID,
+        parent_id: Option<ID>,
+        name: String,
+    ) -> Result<file_folder::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let access =
+            ComponentAccess::load(component_id.to_string(), "files", Some(user_id.clone()), db)
+                .await?;
+        access.check_permission("files.view")?;
+        access.check_permission("files.folders.create")?;
+
+        let name = validate_name(&name)?;
+        let tree = access.component.component_id;
+
+        if let Some(parent_id) = &parent_id {
+            get_folder(&tree, parent_id, db).await?;
+        }
+
+        let folder = file_folder::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            tree: ActiveValue::Set(tree),
+            parent: ActiveValue::Set(parent_id.map(|id| id.to_string())),
+            owner: ActiveValue::Set(user_id),
+            name: ActiveValue::Set(name),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        folder
+            .insert(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))
+    }
+
+    #[graphql(complexity = 10)]
+    async fn rename_file_folder(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        name: String,
+    ) -> Result<file_folder::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, folder) = get_folder_access(ctx, &id).await?;
+        access.check_ownership_permission("files.folders.rename", &folder.owner)?;
+
+        let mut active_folder: file_folder::ActiveModel = folder.into();
+        active_folder.name = ActiveValue::Set(validate_name(&name)?);
+
+        active_folder
+            .update(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+    }
+
+    /// Deletes a folder, along with every folder and file inside of it.
+    #[graphql(complexity = 100)]
+    async fn delete_file_folder(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let storage = ctx.data::<Arc<dyn Storage>>().unwrap();
+
+        let (access, folder) = get_folder_access(ctx, &id).await?;
+        access.check_ownership_permission("files.folders.delete", &folder.owner)?;
+
+        let folders = folder_with_descendants(&folder, db).await?;
+
+        let files = file::Entity::find()
+            .filter(file::Column::Folder.is_in(folders.clone()))
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_FILES_ERROR"))?;
+
+        let txn = db::begin(db).await?;
+
+        delete_files(&files, &txn).await?;
+
+        file_folder::Entity::delete_many()
+            .filter(file_folder::Column::Id.is_in(folders))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_FOLDERS_ERROR"))?;
+
+        if access.user_id.as_ref() != Some(&folder.owner) {
+            audit::log_action(
+                &txn,
+                access.planet.id,
+                access.user_id,
+                AuditAction::FileFolderDelete,
+                Some(folder.id),
+                Some(folder.name),
+            )
+            .await?;
+        }
+
+        db::commit(txn).await?;
+
+        remove_contents(&files, storage.as_ref()).await;
+
+        Ok(true)
+    }
+
+    #[graphql(complexity = 10)]
+    async fn rename_file(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        name: String,
+    ) -> Result<file::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, file) = get_file_access(ctx, &id).await?;
+        access.check_ownership_permission("files.files.rename", &file.owner)?;
+
+        let mut active_file: file::ActiveModel = file.into();
+        active_file.name = ActiveValue::Set(validate_name(&name)?);
+
+        active_file
+            .update(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+    }
+
+    /// Deletes a file, returning the space it used to its owner.
+    #[graphql(complexity = 20)]
+    async fn delete_file(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let storage = ctx.data::<Arc<dyn Storage>>().unwrap();
+
+        let (access, file) = get_file_access(ctx, &id).await?;
+        access.check_ownership_permission("files.files.delete", &file.owner)?;
+
+        let files = vec![file];
+
+        let txn = db::begin(db).await?;
+
+        delete_files(&files, &txn).await?;
+
+        if access.user_id.as_ref() != Some(&files[0].owner) {
+            audit::log_action(
+                &txn,
+                access.planet.id,
+                access.user_id,
+                AuditAction::FileDelete,
+                Some(files[0].id.clone()),
+                Some(files[0].name.clone()),
+            )
+            .await?;
+        }
+
+        db::commit(txn).await?;
+
+        remove_contents(&files, storage.as_ref()).await;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,685 @@
+use crate::audit;
+use crate::components::access::ComponentAccess;
+use crate::components::forum::{
+    check_can_reply, participants_query, reaction_counts, validate_attachments, validate_content,
+    validate_emoji, validate_tags, validate_title, ReactionCount,
+};
+use crate::db;
+use crate::entities::audit_log::AuditAction;
+use crate::entities::notification::NotificationKind;
+use crate::entities::{forum_post, forum_reaction, forum_reply, forum_tag};
+use crate::errors;
+use crate::guards::session::{SessionGuard, SessionType};
+use crate::notifications::{self, NewNotification};
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use nanoid::nanoid;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+
+/// Gets a forum post along with the forum component it belongs to.
+async fn get_post(
+    ctx: &Context<'_>,
+    id: &ID,
+) -> Result<(ComponentAccess, forum_post::Model), Error> {
+    let db = ctx.data::<DatabaseConnection>().unwrap();
+    let session = ctx.data::<Session>().unwrap();
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+    let post = forum_post::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "POST_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let access = ComponentAccess::load_by_data(post.forum.clone(), "forum", user_id, db).await?;
+    access.check_permission("forum.view")?;
+
+    Ok((access, post))
+}
+
+/// Gets a forum reply along with the post it replies to and the forum component they belong to.
+async fn get_reply(
+    ctx: &Context<'_>,
+    id: &ID,
+) -> Result<(ComponentAccess, forum_post::Model, forum_reply::Model), Error> {
+    let db = ctx.data::<DatabaseConnection>().unwrap();
+
+    let reply = forum_reply::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "REPLY_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let (access, post) = get_post(ctx, &ID(reply.post.clone())).await?;
+
+    Ok((access, post, reply))
+}
+
+/// Gets the author of the post or reply being reacted to, checking that the reply belongs to the
+/// post.
+async fn reaction_target_author(
+    post: &forum_post::Model,
+    reply_id: Option<&ID>,
+    db: &DatabaseConnection,
+) -> Result<String, Error> {
+    match reply_id {
+        Some(reply_id) => forum_reply::Entity::find_by_id(reply_id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "REPLY_RETRIEVAL_ERROR"))?
+            .filter(|reply| reply.post == post.id)
+            .map(|reply| reply.author)
+            .ok_or(errors::create_not_found_error()),
+        None => Ok(post.author.clone()),
+    }
+}
+
+#[derive(Default, Description)]
+pub struct ForumMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl ForumMutation {
+    /// Starts a new thread in a forum. Tags require the `forum.tags.use` permission, and
+    /// attachments require the `forum.posts.attach` permission.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 50)]
+    async fn create_forum_post(
+        &self,
+        ctx: &Context<'_>,
+        component_id: ID,
+        title: String,
+        content: String,
+        tags: Option<Vec<ID>>,
+        attachments: Option<Vec<String>>,
+    ) -> Result<forum_post::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let access =
+            ComponentAccess::load(component_id.to_string(), "forum", Some(user_id.clone()), db)
+                .await?;
+        access.check_permission("forum.posts.create")?;
+
+        let title = validate_title(&title)?;
+        validate_content(&content)?;
+
+        let tags: Vec<String> = tags
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tag| tag.to_string())
+            .collect();
+        let attachments = attachments.unwrap_or_default();
+
+        if !tags.is_empty() {
+            access.check_permission("forum.tags.use")?;
+        }
+
+        if !attachments.is_empty() {
+            access.check_permission("forum.posts.attach")?;
+            validate_attachments(&attachments)?;
+        }
+
+        let forum_id = access.component.component_id.clone();
+        let tags = validate_tags(&forum_id, tags, db).await?;
+        let now = chrono::offset::Utc::now().naive_utc();
+
+        let post = forum_post::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            forum: ActiveValue::Set(forum_id),
+            author: ActiveValue::Set(user_id),
+            title: ActiveValue::Set(title),
+            content: ActiveValue::Set(content),
+            tags: ActiveValue::Set(tags),
+            attachments: ActiveValue::Set(attachments),
+            sticky: ActiveValue::Set(false),
+            locked: ActiveValue::Set(false),
+            reply_count: ActiveValue::Set(0),
+            created: ActiveValue::Set(now),
+            edited: ActiveValue::Set(None),
+            last_activity: ActiveValue::Set(now),
+        };
+
+        let post = post
+            .insert(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+        notifications::notify_mentions(db, &access, "forum.view", post.id.clone(), &post.content)
+            .await?;
+
+        access.record_activity(db).await?;
+
+        Ok(post)
+    }
+
+    /// Edits a thread. Only the provided fields are changed.
+    #[graphql(complexity = 50)]
+    async fn edit_forum_post(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        title: Option<String>,
+        content: Option<String>,
+        tags: Option<Vec<ID>>,
+    ) -> Result<forum_post::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, post) = get_post(ctx, &id).await?;
+        access.check_ownership_permission("forum.posts.edit", &post.author)?;
+
+        let forum_id = post.forum.clone();
+        let mut active_post: forum_post::ActiveModel = post.into();
+
+        if let Some(title) = title {
+            active_post.title = ActiveValue::Set(validate_title(&title)?);
+        }
+
+        if let Some(content) = content {
+            validate_content(&content)?;
+            active_post.content = ActiveValue::Set(content);
+        }
+
+        if let Some(tags) = tags {
+            let tags: Vec<String> = tags.into_iter().map(|tag| tag.to_string()).collect();
+
+            if !tags.is_empty() {
+                access.check_permission("forum.tags.use")?;
+            }
+
+            active_post.tags = ActiveValue::Set(validate_tags(&forum_id, tags, db).await?);
+        }
+
+        active_post.edited = ActiveValue::Set(Some(chrono::offset::Utc::now().naive_utc()));
+
+        active_post
+            .update(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+    }
+
+    /// Deletes a thread, along with all of its replies.
+    #[graphql(complexity = 100)]
+    async fn delete_forum_post(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, post) = get_post(ctx, &id).await?;
+        access.check_ownership_permission("forum.posts.delete", &post.author)?;
+
+        let txn = db::begin(db).await?;
+
+        forum_reaction::Entity::delete_many()
+            .filter(forum_reaction::Column::Post.eq(post.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REACTIONS_ERROR"))?;
+
+        forum_reply::Entity::delete_many()
+            .filter(forum_reply::Column::Post.eq(post.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REPLIES_ERROR"))?;
+
+        forum_post::Entity::delete_by_id(post.id.clone())
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_POST_ERROR"))?;
+
+        if access.user_id.as_ref() != Some(&post.author) {
+            audit::log_action(
+                &txn,
+                access.planet.id,
+                access.user_id,
+                AuditAction::ForumPostDelete,
+                Some(post.id),
+                Some(post.title),
+            )
+            .await?;
+        }
+
+        db::commit(txn).await?;
+
+        Ok(true)
+    }
+
+    /// Replies to a thread. Locked threads can only be replied to with the
+    /// `forum.posts.lock.ignore` permission.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 50)]
+    async fn create_forum_reply(
+        &self,
+        ctx: &Context<'_>,
+        post_id: ID,
+        content: String,
+    ) -> Result<forum_reply::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let (access, post) = get_post(ctx, &post_id).await?;
+        check_can_reply(&access, &post)?;
+
+        validate_content(&content)?;
+
+        let now = chrono::offset::Utc::now().naive_utc();
+
+        let reply = forum_reply::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            post: ActiveValue::Set(post.id.clone()),
+            author: ActiveValue::Set(user_id),
+            content: ActiveValue::Set(content),
+            created: ActiveValue::Set(now),
+            edited: ActiveValue::Set(None),
+        };
+
+        let reply = reply
+            .insert(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+        forum_post::Entity::update_many()
+            .col_expr(
+                forum_post::Column::ReplyCount,
+                Expr::col(forum_post::Column::ReplyCount).add(1),
+            )
+            .col_expr(forum_post::Column::LastActivity, Expr::value(now))
+            .filter(forum_post::Column::Id.eq(post.id.clone()))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_POST_ERROR"))?;
+
+        let participants = participants_query(&post)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_PARTICIPANTS_ERROR"))?;
+
+        notifications::notify(
+            db,
+            NewNotification {
+                user: post.author,
+                kind: NotificationKind::Reply,
+                planet: Some(access.planet.id.clone()),
+                component: Some(access.component.id.clone()),
+                target: Some(reply.id.clone()),
+                actor: Some(reply.author.clone()),
+                content: reply.content.clone(),
+            },
+        )
+        .await?;
+
+        notifications::notify_activity(
+            db,
+            &access,
+            "forum.view",
+            participants.into_iter().map(|reply| reply.author).collect(),
+            reply.id.clone(),
+            &reply.content,
+        )
+        .await?;
+
+        notifications::notify_mentions(db, &access, "forum.view", reply.id.clone(), &reply.content)
+            .await?;
+
+        access.record_activity(db).await?;
+
+        Ok(reply)
+    }
+
+    /// Edits a reply. Replies are edited under the same permissions as threads.
+    #[graphql(complexity = 50)]
+    async fn edit_forum_reply(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        content: String,
+    ) -> Result<forum_reply::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, _, reply) = get_reply(ctx, &id).await?;
+        access.check_ownership_permission("forum.posts.edit", &reply.author)?;
+
+        validate_content(&content)?;
+
+        let mut active_reply: forum_reply::ActiveModel = reply.into();
+        active_reply.content = ActiveValue::Set(content);
+        active_reply.edited = ActiveValue::Set(Some(chrono::offset::Utc::now().naive_utc()));
+
+        active_reply
+            .update(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+    }
+
+    /// Deletes a reply. Replies are deleted under the same permissions as threads.
+    #[graphql(complexity = 50)]
+    async fn delete_forum_reply(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, post, reply) = get_reply(ctx, &id).await?;
+        access.check_ownership_permission("forum.posts.delete", &reply.author)?;
+
+        let txn = db::begin(db).await?;
+
+        forum_reaction::Entity::delete_many()
+            .filter(forum_reaction::Column::Reply.eq(reply.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REACTIONS_ERROR"))?;
+
+        forum_reply::Entity::delete_by_id(reply.id.clone())
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REPLY_ERROR"))?;
+
+        forum_post::Entity::update_many()
+            .col_expr(
+                forum_post::Column::ReplyCount,
+                Expr::col(forum_post::Column::ReplyCount).sub(1),
+            )
+            .filter(forum_post::Column::Id.eq(post.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_POST_ERROR"))?;
+
+        if access.user_id.as_ref() != Some(&reply.author) {
+            audit::log_action(
+                &txn,
+                access.planet.id,
+                access.user_id,
+                AuditAction::ForumReplyDelete,
+                Some(reply.id),
+                Some(post.title),
+            )
+            .await?;
+        }
+
+        db::commit(txn).await?;
+
+        Ok(true)
+    }
+
+    /// Sets whether or not a thread is shown before the other threads in its forum.
+    #[graphql(complexity = 10)]
+    async fn set_forum_post_sticky(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        sticky: bool,
+    ) -> Result<forum_post::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, post) = get_post(ctx, &id).await?;
+        access.check_permission("forum.posts.sticky")?;
+
+        let mut active_post: forum_post::ActiveModel = post.into();
+        active_post.sticky = ActiveValue::Set(sticky);
+
+        let txn = db::begin(db).await?;
+
+        let post = active_post
+            .update(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            access.planet.id,
+            access.user_id,
+            AuditAction::ForumPostSticky,
+            Some(post.id.clone()),
+            Some(sticky.to_string()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(post)
+    }
+
+    /// Sets whether or not a thread is locked. Locked threads can only be replied to with the
+    /// `forum.posts.lock.ignore` permission.
+    #[graphql(complexity = 10)]
+    async fn set_forum_post_locked(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        locked: bool,
+    ) -> Result<forum_post::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, post) = get_post(ctx, &id).await?;
+        access.check_permission("forum.posts.lock.set")?;
+
+        let mut active_post: forum_post::ActiveModel = post.into();
+        active_post.locked = ActiveValue::Set(locked);
+
+        let txn = db::begin(db).await?;
+
+        let post = active_post
+            .update(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            access.planet.id,
+            access.user_id,
+            AuditAction::ForumPostLock,
+            Some(post.id.clone()),
+            Some(locked.to_string()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(post)
+    }
+
+    /// Creates a tag that can be added to threads in a forum.
+    #[graphql(complexity = 10)]
+    async fn create_forum_tag(
+        &self,
+        ctx: &Context<'_>,
+        component_id: ID,
+        name: String,
+        color: String,
+    ) -> Result<forum_tag::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let access = ComponentAccess::load(component_id.to_string(), "forum", user_id, db).await?;
+        access.check_permission("forum.tags.create")?;
+
+        let name = name.trim().to_string();
+
+        if name.is_empty() || name.len() > 32 {
+            return Err(errors::create_user_input_error(
+                "Tag names must be between 1 and 32 characters long.",
+                "INVALID_NAME",
+            ));
+        }
+
+        if color.len() != 7 && color.len() != 9 {
+            return Err(errors::create_user_input_error(
+                "Invalid color code.",
+                "INVALID_COLOR",
+            ));
+        }
+
+        let tag = forum_tag::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            forum: ActiveValue::Set(access.component.component_id),
+            name: ActiveValue::Set(name),
+            color: ActiveValue::Set(color),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        let txn = db::begin(db).await?;
+
+        let tag = tag
+            .insert(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            access.planet.id,
+            access.user_id,
+            AuditAction::ForumTagCreate,
+            Some(tag.id.clone()),
+            Some(tag.name.clone()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(tag)
+    }
+
+    /// Deletes a tag, removing it from every thread it was added to.
+    #[graphql(complexity = 50)]
+    async fn delete_forum_tag(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let tag = forum_tag::Entity::find_by_id(id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "TAG_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_not_found_error())?;
+
+        let access = ComponentAccess::load_by_data(tag.forum.clone(), "forum", user_id, db).await?;
+        access.check_permission("forum.tags.delete")?;
+
+        let txn = db::begin(db).await?;
+
+        forum_post::Entity::update_many()
+            .col_expr(
+                forum_post::Column::Tags,
+                Expr::cust_with_values(r#"array_remove("tags", $1)"#, [tag.id.clone()]),
+            )
+            .filter(forum_post::Column::Forum.eq(tag.forum.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_POSTS_ERROR"))?;
+
+        forum_tag::Entity::delete_by_id(tag.id.clone())
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_TAG_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            access.planet.id,
+            access.user_id,
+            AuditAction::ForumTagDelete,
+            Some(tag.id),
+            Some(tag.name),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(true)
+    }
+
+    /// Reacts to a thread, or to one of its replies if `replyId` is provided. Returns the updated
+    /// reactions.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn add_forum_reaction(
+        &self,
+        ctx: &Context<'_>,
+        post_id: ID,
+        reply_id: Option<ID>,
+        emoji: String,
+    ) -> Result<Vec<ReactionCount>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let (access, post) = get_post(ctx, &post_id).await?;
+        let author = reaction_target_author(&post, reply_id.as_ref(), db).await?;
+        access.check_ownership_permission("forum.posts.react", &author)?;
+
+        validate_emoji(&emoji)?;
+
+        let reply_id = reply_id.map(|id| id.to_string());
+        let reply_condition = match &reply_id {
+            Some(reply_id) => forum_reaction::Column::Reply.eq(reply_id.clone()),
+            None => forum_reaction::Column::Reply.is_null(),
+        };
+
+        let existing = forum_reaction::Entity::find()
+            .filter(forum_reaction::Column::Post.eq(post.id.clone()))
+            .filter(reply_condition)
+            .filter(forum_reaction::Column::User.eq(user_id.clone()))
+            .filter(forum_reaction::Column::Emoji.eq(emoji.clone()))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "REACTION_RETRIEVAL_ERROR"))?;
+
+        if existing.is_some() {
+            return Err(errors::create_user_input_error(
+                "You have already reacted with that emoji.",
+                "ALREADY_REACTED",
+            ));
+        }
+
+        let reaction = forum_reaction::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            post: ActiveValue::Set(post.id.clone()),
+            reply: ActiveValue::Set(reply_id.clone()),
+            user: ActiveValue::Set(user_id.clone()),
+            emoji: ActiveValue::Set(emoji),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        forum_reaction::Entity::insert(reaction)
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+        reaction_counts(&post.id, reply_id.as_deref(), Some(&user_id), db).await
+    }
+
+    /// Removes the current user's reaction from a thread, or from one of its replies if `replyId`
+    /// is provided. Returns the updated reactions.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn remove_forum_reaction(
+        &self,
+        ctx: &Context<'_>,
+        post_id: ID,
+        reply_id: Option<ID>,
+        emoji: String,
+    ) -> Result<Vec<ReactionCount>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let (_, post) = get_post(ctx, &post_id).await?;
+
+        let reply_id = reply_id.map(|id| id.to_string());
+        let reply_condition = match &reply_id {
+            Some(reply_id) => forum_reaction::Column::Reply.eq(reply_id.clone()),
+            None => forum_reaction::Column::Reply.is_null(),
+        };
+
+        forum_reaction::Entity::delete_many()
+            .filter(forum_reaction::Column::Post.eq(post.id.clone()))
+            .filter(reply_condition)
+            .filter(forum_reaction::Column::User.eq(user_id.clone()))
+            .filter(forum_reaction::Column::Emoji.eq(emoji))
+            .exec(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REACTION_ERROR"))?;
+
+        reaction_counts(&post.id, reply_id.as_deref(), Some(&user_id), db).await
+    }
+}
@@ -0,0 +1,399 @@
+use super::members::insert_member;
+use crate::audit;
+use crate::db;
+use crate::entities::audit_log::AuditAction;
+use crate::entities::join_request::{self, JoinRequestStatus};
+use crate::entities::{planet, planet_member};
+use crate::errors;
+use crate::guards::session::{SessionGuard, SessionType};
+use crate::permissions::util;
+use crate::sessions::Session;
+use crate::subscriptions::{Broker, PlanetEvent};
+use async_graphql::{Context, Description, Error, Object, ID};
+use chrono::NaiveDateTime;
+use nanoid::nanoid;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, UpdateMany,
+};
+
+/// Gets a join request and the planet it was made for, checking that the current user can review
+/// it. Only pending requests can be reviewed.
+async fn get_reviewable_request(
+    ctx: &Context<'_>,
+    id: ID,
+) -> Result<(join_request::Model, planet::Model), Error> {
+    let db = ctx.data::<DatabaseConnection>().unwrap();
+    let session = ctx.data::<Session>().unwrap();
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+    let request = join_request::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "REQUEST_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let planet = util::get_planet(request.planet.clone(), db).await?;
+    let member = util::get_planet_member(user_id, request.planet.clone(), db).await?;
+    let roles = util::get_member_roles(member.clone(), db).await?;
+    util::check_permission("planet.join_requests.manage", &planet, member, roles)?;
+
+    if request.status != JoinRequestStatus::Pending {
+        return Err(errors::create_user_input_error(
+            "This request has already been reviewed.",
+            "ALREADY_REVIEWED",
+        ));
+    }
+
+    Ok((request, planet))
+}
+
+/// Builds the update that marks a join request as reviewed by a user. The update only applies to
+/// requests that are still pending, so a request can't be reviewed twice.
+pub fn resolve_query(
+    request_id: &str,
+    status: JoinRequestStatus,
+    user_id: Option<String>,
+    reviewed: NaiveDateTime,
+) -> UpdateMany<join_request::Entity> {
+    join_request::Entity::update_many()
+        .col_expr(join_request::Column::Status, Expr::value(status))
+        .col_expr(join_request::Column::ReviewedBy, Expr::value(user_id))
+        .col_expr(join_request::Column::Reviewed, Expr::value(reviewed))
+        .filter(
+            join_request::Column::Id
+                .eq(request_id)
+                .and(join_request::Column::Status.eq(JoinRequestStatus::Pending)),
+        )
+}
+
+/// Checks that the update from [`resolve_query`] marked the request as reviewed. If it didn't,
+/// the request was reviewed by someone else in the meantime and an error ready for presentation
+/// to the client is returned.
+pub fn check_resolved(rows_affected: u64) -> Result<(), Error> {
+    if rows_affected == 0 {
+        return Err(errors::create_user_input_error(
+            "This request has already been reviewed.",
+            "ALREADY_REVIEWED",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Marks a pending join request as reviewed by a user.
+async fn resolve_request<C: ConnectionTrait>(
+    db: &C,
+    request: join_request::Model,
+    status: JoinRequestStatus,
+    user_id: Option<String>,
+) -> Result<join_request::Model, Error> {
+    let reviewed = chrono::offset::Utc::now().naive_utc();
+
+    let result = resolve_query(&request.id, status, user_id.clone(), reviewed)
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+    check_resolved(result.rows_affected)?;
+
+    Ok(join_request::Model {
+        status,
+        reviewed_by: user_id,
+        reviewed: Some(reviewed),
+        ..request
+    })
+}
+
+#[derive(Default, Description)]
+pub struct JoinRequestMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl JoinRequestMutation {
+    /// Asks to join a private planet that accepts join requests. If the planet asks a question,
+    /// an answer can be attached to the request.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 50)]
+    async fn request_to_join(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+        answer: Option<String>,
+    ) -> Result<join_request::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let planet = util::get_planet(planet_id.to_string(), db).await?;
+
+        if !planet.private {
+            return Err(errors::create_user_input_error(
+                "This planet is public, so you can join it directly.",
+                "PUBLIC_PLANET",
+            ));
+        }
+
+        if !planet.join_requests_enabled {
+            return Err(errors::create_not_found_error());
+        }
+
+        let answer = answer
+            .map(|answer| answer.trim().to_string())
+            .filter(|answer| !answer.is_empty());
+
+        if answer.as_ref().is_some_and(|answer| answer.len() > 2000) {
+            return Err(errors::create_user_input_error(
+                "Answers cannot be longer than 2000 characters.",
+                "ANSWER_TOO_LONG",
+            ));
+        }
+
+        if let Some(member) =
+            util::get_planet_member(Some(user_id.clone()), planet.id.clone(), db).await?
+        {
+            return Err(if member.banned {
+                errors::create_banned_error(
+                    "this planet",
+                    member.ban_reason.as_deref(),
+                    member.ban_expires,
+                    "MEMBER_BANNED",
+                )
+            } else {
+                errors::create_user_input_error(
+                    "You are already a member of this planet.",
+                    "ALREADY_MEMBER",
+                )
+            });
+        }
+
+        let existing = join_request::Entity::find()
+            .filter(
+                join_request::Column::Planet
+                    .eq(planet.id.clone())
+                    .and(join_request::Column::User.eq(user_id.clone())),
+            )
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "REQUEST_RETRIEVAL_ERROR"))?;
+
+        let now = chrono::offset::Utc::now().naive_utc();
+
+        // users keep a single request per planet, which is reopened if they ask again after it
+        // was reviewed
+        match existing {
+            Some(existing) if existing.status == JoinRequestStatus::Pending => {
+                Err(errors::create_user_input_error(
+                    "You have already asked to join this planet.",
+                    "ALREADY_REQUESTED",
+                ))
+            }
+            Some(existing) => {
+                let mut active_request: join_request::ActiveModel = existing.into();
+                active_request.answer = ActiveValue::Set(answer);
+                active_request.status = ActiveValue::Set(JoinRequestStatus::Pending);
+                active_request.created = ActiveValue::Set(now);
+                active_request.reviewed_by = ActiveValue::Set(None);
+                active_request.reviewed = ActiveValue::Set(None);
+
+                active_request
+                    .update(db)
+                    .await
+                    .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            }
+            None => {
+                let request = join_request::ActiveModel {
+                    id: ActiveValue::Set(nanoid!(16)),
+                    planet: ActiveValue::Set(planet.id),
+                    user: ActiveValue::Set(user_id),
+                    answer: ActiveValue::Set(answer),
+                    status: ActiveValue::Set(JoinRequestStatus::Pending),
+                    created: ActiveValue::Set(now),
+                    reviewed_by: ActiveValue::Set(None),
+                    reviewed: ActiveValue::Set(None),
+                };
+
+                let insertion = join_request::Entity::insert(request)
+                    .exec(db)
+                    .await
+                    .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+                join_request::Entity::find_by_id(insertion.last_insert_id)
+                    .one(db)
+                    .await
+                    .map_err(|_| {
+                        errors::create_internal_server_error(None, "REQUEST_RETRIEVAL_ERROR")
+                    })?
+                    .ok_or(errors::create_internal_server_error(None, "FIND_ERROR"))
+            }
+        }
+    }
+
+    /// Approves a request to join a planet, adding the user to the planet.
+    #[graphql(complexity = 200)]
+    async fn approve_join_request(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<planet_member::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let (request, planet) = get_reviewable_request(ctx, id).await?;
+
+        if planet_member::Entity::find()
+            .filter(
+                planet_member::Column::User
+                    .eq(request.user.clone())
+                    .and(planet_member::Column::Planet.eq(planet.id.clone())),
+            )
+            .one(db)
+            .await
+            .map_err(|_| {
+                errors::create_internal_server_error(None, "MEMBER_CHECK_RETRIEVAL_ERROR")
+            })?
+            .is_some()
+        {
+            return Err(errors::create_user_input_error(
+                "This user is already a member of the planet.",
+                "ALREADY_MEMBER",
+            ));
+        }
+
+        let txn = db::begin(db).await?;
+
+        // the request is resolved first, so that approving it twice at once can't add the member
+        // twice
+        let request =
+            resolve_request(&txn, request, JoinRequestStatus::Approved, user_id.clone()).await?;
+        let member = insert_member(&planet, request.user.clone(), &txn).await?;
+
+        audit::log_action(
+            &txn,
+            planet.id.clone(),
+            Some(request.user.clone()),
+            AuditAction::MemberJoin,
+            Some(member.id.clone()),
+            None,
+        )
+        .await?;
+
+        audit::log_action(
+            &txn,
+            planet.id,
+            user_id,
+            AuditAction::JoinRequestApprove,
+            Some(request.id),
+            Some(request.user),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::MemberJoined {
+            planet: member.planet.clone(),
+            member: member.id.clone(),
+        });
+
+        Ok(member)
+    }
+
+    /// Denies a request to join a planet. The user can ask to join again later.
+    #[graphql(complexity = 50)]
+    async fn deny_join_request(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<join_request::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let (request, planet) = get_reviewable_request(ctx, id).await?;
+
+        let txn = db::begin(db).await?;
+
+        let request =
+            resolve_request(&txn, request, JoinRequestStatus::Denied, user_id.clone()).await?;
+
+        audit::log_action(
+            &txn,
+            planet.id,
+            user_id,
+            AuditAction::JoinRequestDeny,
+            Some(request.id.clone()),
+            Some(request.user.clone()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(request)
+    }
+
+    /// Sets whether or not users can ask to join a private planet, and the question they are
+    /// asked when they do. Providing no question removes it.
+    #[graphql(complexity = 10)]
+    async fn update_join_request_settings(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+        enabled: bool,
+        question: Option<String>,
+    ) -> Result<planet::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let planet = util::get_planet(planet_id.to_string(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), planet.id.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission("planet.join_requests.manage", &planet, member, roles)?;
+
+        let question = question
+            .map(|question| question.trim().to_string())
+            .filter(|question| !question.is_empty());
+
+        if question
+            .as_ref()
+            .is_some_and(|question| question.len() > 512)
+        {
+            return Err(errors::create_user_input_error(
+                "Questions cannot be longer than 512 characters.",
+                "QUESTION_TOO_LONG",
+            ));
+        }
+
+        let mut active_planet: planet::ActiveModel = planet.into();
+        active_planet.join_requests_enabled = ActiveValue::Set(enabled);
+        active_planet.join_question = ActiveValue::Set(question);
+
+        let txn = db::begin(db).await?;
+
+        let planet = active_planet
+            .update(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            planet.id.clone(),
+            user_id,
+            AuditAction::PlanetUpdateJoinSettings,
+            Some(planet.id.clone()),
+            planet.join_question.clone(),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::PlanetUpdated {
+            planet: planet.id.clone(),
+        });
+
+        Ok(planet)
+    }
+}
@@ -1,15 +1,91 @@
-use crate::entities::{planet, planet_member, planet_role};
+use crate::audit;
+use crate::db;
+use crate::entities::audit_log::AuditAction;
+use crate::entities::{
+    auto_role_assignment, component_permission, planet, planet_member, planet_role,
+};
 use crate::errors;
 use crate::guards::session::{SessionGuard, SessionType};
-use crate::permissions::util;
+use crate::permissions::{checks, util};
 use crate::sessions::Session;
+use crate::subscriptions::{Broker, PlanetEvent};
 use async_graphql::{Context, Description, Error, Object, ID};
+use chrono::NaiveDateTime;
 use nanoid::nanoid;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait,
-    QueryFilter,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    ModelTrait, QueryFilter,
 };
 
+/// Normalizes a nickname, returning `None` if the nickname is empty. If the nickname is invalid, an
+/// error ready for presentation to the client is returned.
+fn normalize_nickname(nickname: Option<String>) -> Result<Option<String>, Error> {
+    let nickname = nickname
+        .map(|nickname| nickname.trim().to_string())
+        .filter(|nickname| !nickname.is_empty());
+
+    if nickname
+        .as_ref()
+        .is_some_and(|nickname| nickname.chars().count() > 64)
+    {
+        return Err(errors::create_user_input_error(
+            "Nicknames cannot be longer than 64 characters.",
+            "NICKNAME_TOO_LONG",
+        ));
+    }
+
+    Ok(nickname)
+}
+
+/// Adds a user to a planet with the planet's default role, without checking whether or not they
+/// are allowed to join it.
+pub(super) async fn insert_member<C: ConnectionTrait>(
+    planet: &planet::Model,
+    user_id: String,
+    db: &C,
+) -> Result<planet_member::Model, Error> {
+    let role = planet_role::Entity::find()
+        .filter(
+            planet_role::Column::Planet
+                .eq(planet.id.clone())
+                .and(planet_role::Column::Default.eq(true)),
+        )
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "ROLE_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_internal_server_error(
+            None,
+            "MISSING_DEFAULT_ROLE_ERROR",
+        ))?;
+
+    let member = planet_member::ActiveModel {
+        id: ActiveValue::Set(nanoid!(16)),
+        planet: ActiveValue::Set(planet.id.clone()),
+        user: ActiveValue::Set(user_id),
+        roles: ActiveValue::Set(vec![role.id]),
+        permissions: ActiveValue::Set(vec![]),
+        created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        banned: ActiveValue::Set(false),
+        ban_reason: ActiveValue::Set(None),
+        ban_expires: ActiveValue::Set(None),
+        banned_by: ActiveValue::Set(None),
+        banned_at: ActiveValue::Set(None),
+        nickname: ActiveValue::Set(None),
+        last_active: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+    };
+
+    let insertion = planet_member::Entity::insert(member)
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+
+    planet_member::Entity::find_by_id(insertion.last_insert_id)
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "MEMBER_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_internal_server_error(None, "FIND_ERROR"))
+}
+
 #[derive(Default, Description)]
 pub struct MemberMutation;
 
@@ -19,6 +95,7 @@ impl MemberMutation {
     #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 200)]
     async fn join_planet(&self, ctx: &Context<'_>, id: ID) -> Result<planet_member::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -28,20 +105,6 @@ impl MemberMutation {
             .map_err(|_| errors::create_internal_server_error(None, "PLANET_RETRIEVAL_ERROR"))?
             .ok_or(errors::create_not_found_error())?;
 
-        let role = planet_role::Entity::find()
-            .filter(
-                planet_role::Column::Planet
-                    .eq(planet.id.clone())
-                    .and(planet_role::Column::Default.eq(true)),
-            )
-            .one(db)
-            .await
-            .map_err(|_| errors::create_internal_server_error(None, "ROLE_RETRIEVAL_ERROR"))?
-            .ok_or(errors::create_internal_server_error(
-                None,
-                "MISSING_DEFAULT_ROLE_ERROR",
-            ))?;
-
         if planet.private {
             Err(errors::create_not_found_error())
         } else {
@@ -64,26 +127,28 @@ impl MemberMutation {
                 ));
             }
 
-            let member = planet_member::ActiveModel {
-                id: ActiveValue::Set(nanoid!(16)),
-                planet: ActiveValue::Set(planet.id),
-                user: ActiveValue::Set(user_id.unwrap()),
-                roles: ActiveValue::Set(vec![role.id]),
-                permissions: ActiveValue::Set(vec![]),
-                created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
-                banned: ActiveValue::Set(false),
-            };
-
-            let insertion = planet_member::Entity::insert(member)
-                .exec(db)
-                .await
-                .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
+            let txn = db::begin(db).await?;
 
-            Ok(planet_member::Entity::find_by_id(insertion.last_insert_id)
-                .one(db)
-                .await
-                .map_err(|_| errors::create_internal_server_error(None, "MEMBER_RETRIEVAL_ERROR"))?
-                .ok_or(errors::create_internal_server_error(None, "FIND_ERROR"))?)
+            let member = insert_member(&planet, user_id.clone().unwrap(), &txn).await?;
+
+            audit::log_action(
+                &txn,
+                member.planet.clone(),
+                user_id,
+                AuditAction::MemberJoin,
+                Some(member.id.clone()),
+                None,
+            )
+            .await?;
+
+            db::commit(txn).await?;
+
+            broker.publish(PlanetEvent::MemberJoined {
+                planet: member.planet.clone(),
+                member: member.id.clone(),
+            });
+
+            Ok(member)
         }
     }
 
@@ -91,6 +156,7 @@ impl MemberMutation {
     #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 200)]
     async fn leave_planet(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -133,11 +199,44 @@ impl MemberMutation {
             ));
         }
 
+        let txn = db::begin(db).await?;
+
+        component_permission::Entity::delete_many()
+            .filter(component_permission::Column::Member.eq(member.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_OVERRIDES_ERROR"))?;
+
+        auto_role_assignment::Entity::delete_many()
+            .filter(auto_role_assignment::Column::Member.eq(member.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_ASSIGNMENTS_ERROR"))?;
+
         member
-            .delete(db)
+            .clone()
+            .delete(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "MEMBER_DELETION_ERROR"))?;
 
+        audit::log_action(
+            &txn,
+            member.planet.clone(),
+            Some(member.user.clone()),
+            AuditAction::MemberLeave,
+            Some(member.id.clone()),
+            None,
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::MemberLeft {
+            planet: member.planet.clone(),
+            member: member.id.clone(),
+            user: member.user.clone(),
+        });
+
         Ok(true)
     }
 
@@ -165,7 +264,7 @@ impl MemberMutation {
 
         let planet = util::get_planet(member.planet.clone().to_string(), db).await?;
         let requesting_member =
-            util::get_planet_member(user_id, member.planet.clone().to_string(), db).await?;
+            util::get_planet_member(user_id.clone(), member.planet.clone().to_string(), db).await?;
 
         let roles = util::get_member_roles(requesting_member.clone(), db).await?;
         util::check_permission(
@@ -176,19 +275,38 @@ impl MemberMutation {
         )?;
 
         let mut active_member: planet_member::ActiveModel = member.clone().into();
-        active_member.permissions =
-            ActiveValue::Set(util::update_permissions(member.permissions, permissions)?);
+        active_member.permissions = ActiveValue::Set(util::update_permissions(
+            member.permissions,
+            permissions.clone(),
+        )?);
+
+        let txn = db::begin(db).await?;
 
-        active_member
-            .update(db)
+        let member = active_member
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            member.planet.clone(),
+            user_id,
+            AuditAction::MemberUpdatePermissions,
+            Some(member.id.clone()),
+            Some(permissions.join(" ")),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(member)
     }
 
     /// Kicks a member from a planet.
     #[graphql(complexity = 50)]
     async fn kick_member(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -205,9 +323,9 @@ impl MemberMutation {
             util::get_planet_member(user_id.clone(), kick_member.planet.clone(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
 
-        util::check_permission("planet.member.kick", &planet, member, roles)?;
+        util::check_permission("planet.member.kick", &planet, member.clone(), roles.clone())?;
 
-        if kick_member.user == user_id.unwrap() {
+        if Some(kick_member.user.clone()) == user_id {
             return Err(errors::create_user_input_error(
                 "You cannot kick yourself.",
                 "SELF",
@@ -221,6 +339,17 @@ impl MemberMutation {
             ));
         }
 
+        let target_roles = util::get_member_roles(Some(kick_member.clone()), db)
+            .await?
+            .unwrap_or_default();
+
+        if !checks::high_enough(roles, target_roles, member) {
+            return Err(errors::create_user_input_error(
+                "This member's highest role is not below yours.",
+                "ROLE_TOO_LOW",
+            ));
+        }
+
         if kick_member.banned {
             return Err(errors::create_user_input_error(
                 "You can not kick a banned member.",
@@ -228,17 +357,57 @@ impl MemberMutation {
             ));
         }
 
+        let txn = db::begin(db).await?;
+
+        component_permission::Entity::delete_many()
+            .filter(component_permission::Column::Member.eq(kick_member.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_OVERRIDES_ERROR"))?;
+
+        auto_role_assignment::Entity::delete_many()
+            .filter(auto_role_assignment::Column::Member.eq(kick_member.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_ASSIGNMENTS_ERROR"))?;
+
         kick_member
-            .delete(db)
+            .clone()
+            .delete(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "MEMBER_DELETION_ERROR"))?;
 
+        audit::log_action(
+            &txn,
+            kick_member.planet.clone(),
+            user_id,
+            AuditAction::MemberKick,
+            Some(kick_member.id.clone()),
+            Some(kick_member.user.clone()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::MemberLeft {
+            planet: kick_member.planet.clone(),
+            member: kick_member.id.clone(),
+            user: kick_member.user.clone(),
+        });
+
         Ok(true)
     }
 
-    /// Toggles whether or not a member is banned.
+    /// Toggles whether or not a member is banned. When banning a member, a reason and an expiry
+    /// date can optionally be provided. Bans without an expiry date are permanent.
     #[graphql(complexity = 50)]
-    async fn ban_member(&self, ctx: &Context<'_>, id: ID) -> Result<planet_member::Model, Error> {
+    async fn ban_member(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        reason: Option<String>,
+        expires: Option<NaiveDateTime>,
+    ) -> Result<planet_member::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
@@ -256,9 +425,9 @@ impl MemberMutation {
             util::get_planet_member(user_id.clone(), ban_member.planet.clone(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
 
-        util::check_permission("planet.member.kick", &planet, member, roles)?;
+        util::check_permission("planet.member.ban", &planet, member.clone(), roles.clone())?;
 
-        if ban_member.user == user_id.unwrap() {
+        if Some(ban_member.user.clone()) == user_id {
             return Err(errors::create_user_input_error(
                 "You cannot ban yourself.",
                 "SELF",
@@ -272,15 +441,61 @@ impl MemberMutation {
             ));
         }
 
+        let target_roles = util::get_member_roles(Some(ban_member.clone()), db)
+            .await?
+            .unwrap_or_default();
+
+        if !checks::high_enough(roles, target_roles, member) {
+            return Err(errors::create_user_input_error(
+                "This member's highest role is not below yours.",
+                "ROLE_TOO_LOW",
+            ));
+        }
+
         let banned = !ban_member.banned;
 
         let mut active_member: planet_member::ActiveModel = ban_member.clone().into();
         active_member.banned = ActiveValue::Set(banned);
 
-        active_member
-            .update(db)
+        if banned {
+            util::validate_ban(reason.as_ref(), expires)?;
+
+            active_member.ban_reason = ActiveValue::Set(reason);
+            active_member.ban_expires = ActiveValue::Set(expires);
+            active_member.banned_at =
+                ActiveValue::Set(Some(chrono::offset::Utc::now().naive_utc()));
+            active_member.banned_by = ActiveValue::Set(user_id.clone());
+        } else {
+            active_member.ban_reason = ActiveValue::Set(None);
+            active_member.ban_expires = ActiveValue::Set(None);
+            active_member.banned_by = ActiveValue::Set(None);
+            active_member.banned_at = ActiveValue::Set(None);
+        }
+
+        let txn = db::begin(db).await?;
+
+        let member = active_member
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            member.planet.clone(),
+            user_id,
+            if banned {
+                AuditAction::MemberBan
+            } else {
+                AuditAction::MemberUnban
+            },
+            Some(member.id.clone()),
+            member.ban_reason.clone(),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(member)
     }
 
     /// Adds a role to the planet member.
@@ -333,15 +548,31 @@ impl MemberMutation {
         }
 
         let mut new_roles = target_member.roles.clone();
-        new_roles.push(target_role.id);
+        new_roles.push(target_role.id.clone());
 
         let mut active_member: planet_member::ActiveModel = target_member.clone().into();
         active_member.roles = ActiveValue::Set(new_roles);
 
-        active_member
-            .update(db)
+        let txn = db::begin(db).await?;
+
+        let member = active_member
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            member.planet.clone(),
+            user_id,
+            AuditAction::MemberAddRole,
+            Some(member.id.clone()),
+            Some(target_role.id),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(member)
     }
 
     /// Removes a role from a planet member.
@@ -399,9 +630,145 @@ impl MemberMutation {
         let mut active_member: planet_member::ActiveModel = target_member.clone().into();
         active_member.roles = ActiveValue::Set(new_roles);
 
-        active_member
-            .update(db)
+        let txn = db::begin(db).await?;
+
+        let member = active_member
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            member.planet.clone(),
+            user_id,
+            AuditAction::MemberRemoveRole,
+            Some(member.id.clone()),
+            Some(target_role.id),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(member)
+    }
+
+    /// Sets the current user's nickname on a planet. Providing no nickname removes it.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 10)]
+    async fn set_nickname(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+        nickname: Option<String>,
+    ) -> Result<planet_member::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let member = util::get_planet_member(user_id.clone(), planet_id.to_string(), db)
+            .await?
+            .ok_or(errors::create_user_input_error(
+                "You aren't a member of that planet.",
+                "NOT_MEMBER",
+            ))?;
+
+        if member.banned {
+            return Err(errors::create_user_input_error(
+                "You cannot change your nickname on a planet you are banned from.",
+                "BANNED",
+            ));
+        }
+
+        let mut active_member: planet_member::ActiveModel = member.into();
+        active_member.nickname = ActiveValue::Set(normalize_nickname(nickname)?);
+
+        let txn = db::begin(db).await?;
+
+        let member = active_member
+            .update(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            member.planet.clone(),
+            user_id,
+            AuditAction::MemberSetNickname,
+            Some(member.id.clone()),
+            member.nickname.clone(),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(member)
+    }
+
+    /// Sets a member's nickname. Providing no nickname removes it.
+    #[graphql(complexity = 50)]
+    async fn set_member_nickname(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        nickname: Option<String>,
+    ) -> Result<planet_member::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let target_member = planet_member::Entity::find_by_id(id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "TARGET_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_not_found_error())?;
+
+        let planet = util::get_planet(target_member.planet.clone(), db).await?;
+        let member =
+            util::get_planet_member(user_id.clone(), target_member.planet.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission(
+            "planet.member.manage_nicknames",
+            &planet,
+            member.clone(),
+            roles.clone(),
+        )?;
+
+        if target_member.user == planet.owner && user_id.as_ref() != Some(&planet.owner) {
+            return Err(errors::create_user_input_error(
+                "You cannot change the nickname of the owner of the planet.",
+                "PLANET_OWNER",
+            ));
+        }
+
+        // members can always change their own nickname, whatever their rank
+        if user_id.as_ref() != Some(&target_member.user) {
+            let target_roles = util::get_member_roles(Some(target_member.clone()), db)
+                .await?
+                .unwrap_or_default();
+            util::high_enough(roles, target_roles, member)?;
+        }
+
+        let mut active_member: planet_member::ActiveModel = target_member.into();
+        active_member.nickname = ActiveValue::Set(normalize_nickname(nickname)?);
+
+        let txn = db::begin(db).await?;
+
+        let member = active_member
+            .update(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            member.planet.clone(),
+            user_id,
+            AuditAction::MemberSetNickname,
+            Some(member.id.clone()),
+            member.nickname.clone(),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(member)
     }
 }
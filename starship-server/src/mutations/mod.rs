@@ -1,16 +1,38 @@
+mod auto_roles;
+mod chats;
 mod components;
+mod conversations;
+mod events;
+mod files;
+mod forums;
+pub mod join_requests;
 mod members;
+pub mod moderation;
+mod notifications;
+mod pages;
 mod planets;
 mod roles;
 mod users;
+mod wikis;
 
 use async_graphql::MergedObject;
 
 #[derive(MergedObject, Default)]
 pub struct Mutation(
     users::UserMutation,
+    notifications::NotificationMutation,
     planets::PlanetMutation,
     components::ComponentMutation,
+    pages::PageMutation,
+    forums::ForumMutation,
+    chats::ChatMutation,
+    files::FileMutation,
+    wikis::WikiMutation,
+    events::EventMutation,
+    conversations::ConversationMutation,
     members::MemberMutation,
+    join_requests::JoinRequestMutation,
+    moderation::ModerationMutation,
     roles::RoleMutation,
+    auto_roles::AutoRoleMutation,
 );
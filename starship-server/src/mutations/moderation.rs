@@ -0,0 +1,543 @@
+use crate::audit;
+use crate::db::{begin, commit};
+use crate::entities::audit_log::AuditAction;
+use crate::entities::{
+    auto_role_assignment, component_permission, planet, planet_member, planet_role,
+};
+use crate::errors;
+use crate::permissions::{checks, util};
+use crate::sessions::Session;
+use crate::subscriptions::{Broker, PlanetEvent};
+use async_graphql::{Context, Description, Error, Object, SimpleObject, ID};
+use chrono::NaiveDateTime;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    ModelTrait, QueryFilter,
+};
+use std::collections::HashMap;
+
+/// The largest number of members that can be moderated in a single bulk operation.
+pub const MAX_BULK_MEMBERS: usize = 100;
+
+/// The result of a bulk moderation operation for a single member.
+#[derive(SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct BulkMemberResult {
+    pub member_id: ID,
+    pub success: bool,
+    /// Why the operation failed for this member, if it did.
+    pub error: Option<String>,
+    /// The error code explaining why the operation failed for this member, if it did.
+    pub code: Option<String>,
+}
+
+impl BulkMemberResult {
+    fn succeeded(member_id: &str) -> Self {
+        BulkMemberResult {
+            member_id: ID(member_id.to_string()),
+            success: true,
+            error: None,
+            code: None,
+        }
+    }
+
+    fn failed(member_id: &str, error: &str, code: &str) -> Self {
+        BulkMemberResult {
+            member_id: ID(member_id.to_string()),
+            success: false,
+            error: Some(error.to_string()),
+            code: Some(code.to_string()),
+        }
+    }
+}
+
+/// A planet member targeted by a bulk operation, along with their roles.
+pub struct Target {
+    pub member: planet_member::Model,
+    pub roles: Vec<planet_role::Model>,
+}
+
+/// Removes duplicate IDs while keeping their order, and rejects lists that are too long.
+pub fn dedupe_ids(member_ids: Vec<ID>) -> Result<Vec<String>, Error> {
+    let mut ids: Vec<String> = vec![];
+
+    for id in member_ids {
+        if !ids.contains(&id.0) {
+            ids.push(id.0);
+        }
+    }
+
+    if ids.len() > MAX_BULK_MEMBERS {
+        return Err(errors::create_user_input_error(
+            &format!("You can only moderate up to {MAX_BULK_MEMBERS} members at once."),
+            "TOO_MANY_MEMBERS",
+        ));
+    }
+
+    Ok(ids)
+}
+
+/// Retrieves the targeted members of a planet and their roles, keyed by member ID. Members that
+/// don't exist or belong to other planets are left out.
+async fn get_targets(
+    planet: &planet::Model,
+    ids: &[String],
+    db: &DatabaseConnection,
+) -> Result<HashMap<String, Target>, Error> {
+    let members = planet_member::Entity::find()
+        .filter(
+            planet_member::Column::Planet
+                .eq(planet.id.clone())
+                .and(planet_member::Column::Id.is_in(ids.to_vec())),
+        )
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "TARGET_RETRIEVAL_ERROR"))?;
+
+    let planet_roles = planet
+        .find_related(planet_role::Entity)
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "ROLES_RETRIEVAL_ERROR"))?;
+
+    Ok(members
+        .into_iter()
+        .map(|member| {
+            let roles = planet_roles
+                .iter()
+                .filter(|role| member.roles.contains(&role.id))
+                .cloned()
+                .collect();
+
+            (member.id.clone(), Target { member, roles })
+        })
+        .collect())
+}
+
+/// Checks whether or not a member can be kicked or banned by the current user. Members can't
+/// moderate themselves, the planet's owner, or anyone whose highest role isn't below their own.
+pub fn check_target<'a>(
+    id: &str,
+    target: Option<&'a Target>,
+    planet: &planet::Model,
+    user_id: Option<&String>,
+    member: &Option<planet_member::Model>,
+    roles: &Option<Vec<planet_role::Model>>,
+) -> Result<&'a Target, BulkMemberResult> {
+    let target = target.ok_or(BulkMemberResult::failed(
+        id,
+        "This member could not be found.",
+        "NOT_FOUND",
+    ))?;
+
+    if Some(&target.member.user) == user_id {
+        return Err(BulkMemberResult::failed(
+            id,
+            "You cannot moderate yourself.",
+            "SELF",
+        ));
+    }
+
+    if target.member.user == planet.owner {
+        return Err(BulkMemberResult::failed(
+            id,
+            "You cannot moderate the owner of the planet.",
+            "PLANET_OWNER",
+        ));
+    }
+
+    if !checks::high_enough(roles.clone(), target.roles.clone(), member.clone()) {
+        return Err(BulkMemberResult::failed(
+            id,
+            "This member's highest role is not below yours.",
+            "ROLE_TOO_LOW",
+        ));
+    }
+
+    Ok(target)
+}
+
+/// Removes members from a planet, along with their component permission overrides.
+async fn remove_members<C: ConnectionTrait>(ids: Vec<String>, db: &C) -> Result<(), Error> {
+    component_permission::Entity::delete_many()
+        .filter(component_permission::Column::Member.is_in(ids.clone()))
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "DELETE_OVERRIDES_ERROR"))?;
+
+    auto_role_assignment::Entity::delete_many()
+        .filter(auto_role_assignment::Column::Member.is_in(ids.clone()))
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "DELETE_ASSIGNMENTS_ERROR"))?;
+
+    planet_member::Entity::delete_many()
+        .filter(planet_member::Column::Id.is_in(ids))
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "MEMBER_DELETION_ERROR"))?;
+
+    Ok(())
+}
+
+#[derive(Default, Description)]
+pub struct ModerationMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl ModerationMutation {
+    /// Kicks several members from a planet at once. Either every kick succeeds or none of them are
+    /// applied, apart from members that fail their individual checks, which are reported in the
+    /// results instead.
+    #[graphql(complexity = "10 * member_ids.len()")]
+    async fn bulk_kick_members(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+        member_ids: Vec<ID>,
+    ) -> Result<Vec<BulkMemberResult>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let planet = util::get_planet(planet_id.to_string(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), planet.id.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission("planet.member.kick", &planet, member.clone(), roles.clone())?;
+
+        let ids = dedupe_ids(member_ids)?;
+        let targets = get_targets(&planet, &ids, db).await?;
+
+        let mut results = vec![];
+        let mut kicked = vec![];
+
+        for id in &ids {
+            let target = match check_target(
+                id,
+                targets.get(id),
+                &planet,
+                user_id.as_ref(),
+                &member,
+                &roles,
+            ) {
+                Ok(target) => target,
+                Err(result) => {
+                    results.push(result);
+                    continue;
+                }
+            };
+
+            if target.member.banned {
+                results.push(BulkMemberResult::failed(
+                    id,
+                    "You can not kick a banned member.",
+                    "BANNED",
+                ));
+                continue;
+            }
+
+            kicked.push(&target.member);
+            results.push(BulkMemberResult::succeeded(id));
+        }
+
+        let txn = begin(db).await?;
+
+        remove_members(kicked.iter().map(|m| m.id.clone()).collect(), &txn).await?;
+
+        for kicked_member in &kicked {
+            audit::log_action(
+                &txn,
+                planet.id.clone(),
+                user_id.clone(),
+                AuditAction::MemberKick,
+                Some(kicked_member.id.clone()),
+                Some(kicked_member.user.clone()),
+            )
+            .await?;
+        }
+
+        commit(txn).await?;
+
+        for kicked_member in kicked {
+            broker.publish(PlanetEvent::MemberLeft {
+                planet: planet.id.clone(),
+                member: kicked_member.id.clone(),
+                user: kicked_member.user.clone(),
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Bans several members from a planet at once. Members that are already banned are reported
+    /// as failures rather than being unbanned. Either every ban succeeds or none of them are
+    /// applied, apart from members that fail their individual checks.
+    #[graphql(complexity = "10 * member_ids.len()")]
+    async fn bulk_ban_members(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+        member_ids: Vec<ID>,
+        reason: Option<String>,
+        expires: Option<NaiveDateTime>,
+    ) -> Result<Vec<BulkMemberResult>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let planet = util::get_planet(planet_id.to_string(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), planet.id.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission("planet.member.ban", &planet, member.clone(), roles.clone())?;
+        util::validate_ban(reason.as_ref(), expires)?;
+
+        let ids = dedupe_ids(member_ids)?;
+        let targets = get_targets(&planet, &ids, db).await?;
+
+        let mut results = vec![];
+        let txn = begin(db).await?;
+
+        for id in &ids {
+            let target = match check_target(
+                id,
+                targets.get(id),
+                &planet,
+                user_id.as_ref(),
+                &member,
+                &roles,
+            ) {
+                Ok(target) => target,
+                Err(result) => {
+                    results.push(result);
+                    continue;
+                }
+            };
+
+            if target.member.banned {
+                results.push(BulkMemberResult::failed(
+                    id,
+                    "This member is already banned.",
+                    "ALREADY_BANNED",
+                ));
+                continue;
+            }
+
+            let mut active_member: planet_member::ActiveModel = target.member.clone().into();
+            active_member.banned = ActiveValue::Set(true);
+            active_member.ban_reason = ActiveValue::Set(reason.clone());
+            active_member.ban_expires = ActiveValue::Set(expires);
+            active_member.banned_at =
+                ActiveValue::Set(Some(chrono::offset::Utc::now().naive_utc()));
+            active_member.banned_by = ActiveValue::Set(user_id.clone());
+
+            active_member
+                .update(&txn)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+            audit::log_action(
+                &txn,
+                planet.id.clone(),
+                user_id.clone(),
+                AuditAction::MemberBan,
+                Some(id.clone()),
+                reason.clone(),
+            )
+            .await?;
+
+            results.push(BulkMemberResult::succeeded(id));
+        }
+
+        commit(txn).await?;
+
+        Ok(results)
+    }
+
+    /// Adds a role to several members at once. Either every member receives the role or none of
+    /// them do, apart from members that fail their individual checks.
+    #[graphql(complexity = "10 * member_ids.len()")]
+    async fn bulk_add_role_members(
+        &self,
+        ctx: &Context<'_>,
+        role_id: ID,
+        member_ids: Vec<ID>,
+    ) -> Result<Vec<BulkMemberResult>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let target_role = planet_role::Entity::find_by_id(role_id.to_string())
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "TARGET_ROLE_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_not_found_error())?;
+
+        let planet = util::get_planet(target_role.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), planet.id.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission(
+            "planet.roles.add_member",
+            &planet,
+            member.clone(),
+            roles.clone(),
+        )?;
+        util::high_enough(roles, vec![target_role.clone()], member)?;
+
+        let ids = dedupe_ids(member_ids)?;
+        let targets = get_targets(&planet, &ids, db).await?;
+
+        let mut results = vec![];
+        let txn = begin(db).await?;
+
+        for id in &ids {
+            let Some(target) = targets.get(id) else {
+                results.push(BulkMemberResult::failed(
+                    id,
+                    "This member could not be found.",
+                    "NOT_FOUND",
+                ));
+                continue;
+            };
+
+            if target.member.roles.contains(&target_role.id) {
+                results.push(BulkMemberResult::failed(
+                    id,
+                    "This user already has that role.",
+                    "ALREADY_HAS_ROLE",
+                ));
+                continue;
+            }
+
+            let mut new_roles = target.member.roles.clone();
+            new_roles.push(target_role.id.clone());
+
+            let mut active_member: planet_member::ActiveModel = target.member.clone().into();
+            active_member.roles = ActiveValue::Set(new_roles);
+
+            active_member
+                .update(&txn)
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+            audit::log_action(
+                &txn,
+                planet.id.clone(),
+                user_id.clone(),
+                AuditAction::MemberAddRole,
+                Some(id.clone()),
+                Some(target_role.id.clone()),
+            )
+            .await?;
+
+            results.push(BulkMemberResult::succeeded(id));
+        }
+
+        commit(txn).await?;
+
+        Ok(results)
+    }
+
+    /// Kicks every member that hasn't been active on a planet for at least the given number of
+    /// days. Members are active when they open the planet or post on it, measured to the hour.
+    /// Banned members, the owner and members whose highest role isn't below your own are never
+    /// pruned. If `dryRun` is set, the members are counted but not removed.
+    ///
+    /// Returns the number of members that were (or would be) pruned.
+    #[graphql(complexity = 500)]
+    async fn prune_members(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+        days: u32,
+        #[graphql(default)] dry_run: bool,
+    ) -> Result<u64, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let planet = util::get_planet(planet_id.to_string(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), planet.id.clone(), db).await?;
+        let roles = util::get_member_roles(member.clone(), db).await?;
+        util::check_permission(
+            "planet.member.prune",
+            &planet,
+            member.clone(),
+            roles.clone(),
+        )?;
+
+        if days == 0 {
+            return Err(errors::create_user_input_error(
+                "Members must be inactive for at least one day to be pruned.",
+                "INVALID_DAYS",
+            ));
+        }
+
+        let cutoff =
+            chrono::offset::Utc::now().naive_utc() - chrono::Duration::days(i64::from(days));
+
+        let inactive = planet_member::Entity::find()
+            .filter(
+                planet_member::Column::Planet
+                    .eq(planet.id.clone())
+                    .and(planet_member::Column::Banned.eq(false))
+                    .and(planet_member::Column::User.ne(planet.owner.clone()))
+                    .and(planet_member::Column::LastActive.lt(cutoff)),
+            )
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "MEMBER_RETRIEVAL_ERROR"))?;
+
+        let planet_roles = planet
+            .find_related(planet_role::Entity)
+            .all(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "ROLES_RETRIEVAL_ERROR"))?;
+
+        let pruned: Vec<planet_member::Model> = inactive
+            .into_iter()
+            .filter(|target| {
+                let target_roles = planet_roles
+                    .iter()
+                    .filter(|role| target.roles.contains(&role.id))
+                    .cloned()
+                    .collect();
+
+                Some(&target.user) != user_id.as_ref()
+                    && checks::high_enough(roles.clone(), target_roles, member.clone())
+            })
+            .collect();
+
+        let count = pruned.len() as u64;
+
+        if dry_run || pruned.is_empty() {
+            return Ok(count);
+        }
+
+        let txn = begin(db).await?;
+
+        remove_members(pruned.iter().map(|m| m.id.clone()).collect(), &txn).await?;
+
+        audit::log_action(
+            &txn,
+            planet.id.clone(),
+            user_id,
+            AuditAction::MemberPrune,
+            None,
+            Some(format!("Pruned {count} members inactive for {days} days.")),
+        )
+        .await?;
+
+        commit(txn).await?;
+
+        for pruned_member in pruned {
+            broker.publish(PlanetEvent::MemberLeft {
+                planet: planet.id.clone(),
+                member: pruned_member.id,
+                user: pruned_member.user,
+            });
+        }
+
+        Ok(count)
+    }
+}
@@ -0,0 +1,49 @@
+use crate::entities::notification;
+use crate::errors;
+use crate::guards::session::{SessionGuard, SessionType};
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+
+#[derive(Default, Description)]
+pub struct NotificationMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl NotificationMutation {
+    /// Marks the current user's notifications as read. Only the notifications in `ids` are marked
+    /// if it is provided, and only those from a planet if `planetId` is provided. Returns the
+    /// number of notifications that were marked.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 10)]
+    async fn mark_notifications_read(
+        &self,
+        ctx: &Context<'_>,
+        ids: Option<Vec<ID>>,
+        planet_id: Option<ID>,
+    ) -> Result<u64, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let mut query = notification::Entity::update_many()
+            .col_expr(notification::Column::Read, Expr::value(true))
+            .filter(notification::Column::User.eq(user_id))
+            .filter(notification::Column::Read.eq(false));
+
+        if let Some(ids) = ids {
+            query = query
+                .filter(notification::Column::Id.is_in(ids.into_iter().map(|id| id.to_string())));
+        }
+
+        if let Some(planet_id) = planet_id {
+            query = query.filter(notification::Column::Planet.eq(planet_id.to_string()));
+        }
+
+        query
+            .exec(db)
+            .await
+            .map(|result| result.rows_affected)
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+    }
+}
@@ -0,0 +1,39 @@
+use crate::components::page::{get_page, validate_content};
+use crate::entities::page;
+use crate::errors;
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use sea_orm::{ActiveModelTrait, ActiveValue, DatabaseConnection};
+
+#[derive(Default, Description)]
+pub struct PageMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl PageMutation {
+    /// Saves the page of a page component. The content must be a Toolbox project serialized as
+    /// JSON, and replaces the page's previous content.
+    #[graphql(complexity = 50)]
+    async fn save_page(
+        &self,
+        ctx: &Context<'_>,
+        component_id: ID,
+        content: String,
+    ) -> Result<page::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let page = get_page(component_id.to_string(), user_id.clone(), "page.edit", db).await?;
+        let content = validate_content(&content)?;
+
+        let mut active_page: page::ActiveModel = page.into();
+        active_page.content = ActiveValue::Set(content);
+        active_page.updated = ActiveValue::Set(chrono::offset::Utc::now().naive_utc());
+        active_page.updated_by = ActiveValue::Set(user_id);
+
+        active_page
+            .update(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+    }
+}
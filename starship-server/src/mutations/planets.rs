@@ -1,8 +1,17 @@
-use crate::entities::{planet, planet_component, planet_member, planet_role};
+use crate::audit;
+use crate::components::registry::ComponentRegistry;
+use crate::db;
+use crate::entities::audit_log::{self, AuditAction};
+use crate::entities::{
+    auto_role, auto_role_assignment, component_permission, join_request, notification, planet,
+    planet_component, planet_member, planet_role,
+};
 use crate::errors;
 use crate::guards::session::{SessionGuard, SessionType};
-use crate::permissions::{constants, util};
+use crate::permissions::constants::{self, PermissionLevel};
+use crate::permissions::util;
 use crate::sessions::Session;
+use crate::subscriptions::{Broker, PlanetEvent};
 use async_graphql::{Context, Description, Error, Object, ID};
 use nanoid::nanoid;
 use sea_orm::{
@@ -23,6 +32,7 @@ impl PlanetMutation {
         private: bool,
     ) -> Result<planet::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let registry = ctx.data::<ComponentRegistry>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         // unwrap is safe because guard guarantees we have a user
         let user = session.user.as_ref().unwrap();
@@ -45,8 +55,10 @@ impl PlanetMutation {
             ..Default::default()
         };
 
+        let txn = db::begin(db).await?;
+
         let result = planet::Entity::insert(planet.clone())
-            .exec(db)
+            .exec(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "PLANET_INSERTION_ERROR"))?;
 
@@ -55,10 +67,9 @@ impl PlanetMutation {
             name: ActiveValue::Set("Default".to_string()),
             color: ActiveValue::Set("#FFFFFF".to_string()),
             permissions: ActiveValue::Set(
-                constants::VIEWER_PERMISSIONS
-                    .iter()
-                    .chain(constants::MEMBER_PERMISSIONS.iter())
-                    .map(std::string::ToString::to_string)
+                constants::with_level(PermissionLevel::Viewer)
+                    .chain(constants::with_level(PermissionLevel::Member))
+                    .map(|permission| format!("+{}", permission.name))
                     .collect(),
             ),
             planet: ActiveValue::Set(result.last_insert_id.clone()),
@@ -67,7 +78,7 @@ impl PlanetMutation {
         };
 
         let role_result = planet_role::Entity::insert(role)
-            .exec(db)
+            .exec(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "ROLE_INSERTION_ERROR"))?;
 
@@ -79,35 +90,59 @@ impl PlanetMutation {
             permissions: ActiveValue::Set(vec!["+owner".to_string()]),
             created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
             banned: ActiveValue::Set(false),
+            ban_reason: ActiveValue::Set(None),
+            ban_expires: ActiveValue::Set(None),
+            banned_by: ActiveValue::Set(None),
+            banned_at: ActiveValue::Set(None),
+            nickname: ActiveValue::Set(None),
+            last_active: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
         };
 
         planet_member::Entity::insert(member)
-            .exec(db)
+            .exec(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "MEMBER_INSERTION_ERROR"))?;
 
-        // TODO: make this create a page instead
+        let page_id = registry
+            .get("page")?
+            .create(&txn, &result.last_insert_id, &user.id)
+            .await?;
+
         let component = planet_component::ActiveModel {
             id: ActiveValue::Set(nanoid!(16)),
-            r#type: ActiveValue::Set("dummy".to_string()),
-            component_id: ActiveValue::Set("dummy".to_string()),
-            name: ActiveValue::Set("Dummy Component".to_string()),
+            r#type: ActiveValue::Set("page".to_string()),
+            component_id: ActiveValue::Set(page_id),
+            name: ActiveValue::Set("Home".to_string()),
             planet: ActiveValue::Set(result.last_insert_id),
             created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
             ..Default::default() // i don't know what's up with this but we have to have it
         };
 
         let component_result = planet_component::Entity::insert(component)
-            .exec(db)
+            .exec(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "COMPONENT_INSERTION_ERROR"))?;
 
         planet.home = ActiveValue::Set(Some(component_result.last_insert_id));
 
-        planet
-            .update(db)
+        let planet = planet
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            planet.id.clone(),
+            Some(user.id.clone()),
+            AuditAction::PlanetCreate,
+            Some(planet.id.clone()),
+            Some(planet.name.clone()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        Ok(planet)
     }
 
     /// Renames a planet.
@@ -119,21 +154,42 @@ impl PlanetMutation {
         name: String,
     ) -> Result<planet::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
         let planet = util::get_planet(id.to_string(), db).await?;
-        let member = util::get_planet_member(user_id, id.to_string(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), id.to_string(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission("planet.change_name", &planet, member, roles)?;
 
         let mut active_planet: planet::ActiveModel = planet.into();
         active_planet.name = ActiveValue::Set(name);
 
-        active_planet
-            .update(db)
+        let txn = db::begin(db).await?;
+
+        let planet = active_planet
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            planet.id.clone(),
+            user_id,
+            AuditAction::PlanetRename,
+            Some(planet.id.clone()),
+            Some(planet.name.clone()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::PlanetUpdated {
+            planet: planet.id.clone(),
+        });
+
+        Ok(planet)
     }
 
     /// Sets a planet's description.
@@ -145,21 +201,42 @@ impl PlanetMutation {
         description: String,
     ) -> Result<planet::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
         let planet = util::get_planet(id.to_string(), db).await?;
-        let member = util::get_planet_member(user_id, id.to_string(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), id.to_string(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission("planet.change_name", &planet, member, roles)?;
 
         let mut active_planet: planet::ActiveModel = planet.into();
         active_planet.description = ActiveValue::Set(Some(description));
 
-        active_planet
-            .update(db)
+        let txn = db::begin(db).await?;
+
+        let planet = active_planet
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            planet.id.clone(),
+            user_id,
+            AuditAction::PlanetChangeDescription,
+            Some(planet.id.clone()),
+            planet.description.clone(),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::PlanetUpdated {
+            planet: planet.id.clone(),
+        });
+
+        Ok(planet)
     }
 
     /// Toggles whether or not a planet is private.
@@ -171,11 +248,12 @@ impl PlanetMutation {
         token: Option<u32>,
     ) -> Result<planet::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
         let planet = util::get_planet(id.to_string(), db).await?;
-        let member = util::get_planet_member(user_id, id.to_string(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), id.to_string(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission("planet.change_publicity", &planet, member, roles)?;
 
@@ -184,10 +262,30 @@ impl PlanetMutation {
         let mut active_planet: planet::ActiveModel = planet.into();
         active_planet.private = ActiveValue::Set(!active_planet.private.unwrap());
 
-        active_planet
-            .update(db)
+        let txn = db::begin(db).await?;
+
+        let planet = active_planet
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            planet.id.clone(),
+            user_id,
+            AuditAction::PlanetChangePublicity,
+            Some(planet.id.clone()),
+            Some(if planet.private { "private" } else { "public" }.to_string()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::PlanetUpdated {
+            planet: planet.id.clone(),
+        });
+
+        Ok(planet)
     }
 
     /// Deletes a planet and all of it's associated data immediately. This action is unrecoverable.
@@ -199,6 +297,7 @@ impl PlanetMutation {
         token: Option<u32>,
     ) -> Result<bool, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let registry = ctx.data::<ComponentRegistry>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -209,6 +308,8 @@ impl PlanetMutation {
 
         util::verify_token(db, session.user.as_ref().unwrap(), token).await?;
 
+        let txn = db::begin(db).await?;
+
         // clear out circular references
         let mut active_planet: planet::ActiveModel = planet.clone().into();
 
@@ -216,41 +317,102 @@ impl PlanetMutation {
 
         active_planet
             .clone()
-            .update(db)
+            .update(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
 
-        // TODO: remove all component data
+        let components = planet_component::Entity::find()
+            .filter(planet_component::Column::Planet.eq(planet.id.clone()))
+            .all(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_COMPONENTS_ERROR"))?;
+
+        let mut cleanups = vec![];
+
+        // components of types that are no longer registered have no data that can be removed
+        for component in components {
+            if let Ok(component_type) = registry.get(&component.r#type) {
+                cleanups.extend(component_type.delete(&txn, &component.component_id).await?);
+            }
+        }
+
+        audit_log::Entity::delete_many()
+            .filter(audit_log::Column::Planet.eq(planet.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_AUDIT_LOG_ERROR"))?;
+
+        auto_role_assignment::Entity::delete_many()
+            .filter(auto_role_assignment::Column::Planet.eq(planet.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_ASSIGNMENTS_ERROR"))?;
+
+        auto_role::Entity::delete_many()
+            .filter(auto_role::Column::Planet.eq(planet.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_AUTO_ROLES_ERROR"))?;
+
+        join_request::Entity::delete_many()
+            .filter(join_request::Column::Planet.eq(planet.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| {
+                errors::create_internal_server_error(None, "DELETE_JOIN_REQUESTS_ERROR")
+            })?;
+
+        notification::Entity::delete_many()
+            .filter(notification::Column::Planet.eq(planet.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| {
+                errors::create_internal_server_error(None, "DELETE_NOTIFICATIONS_ERROR")
+            })?;
+
+        component_permission::Entity::delete_many()
+            .filter(component_permission::Column::Planet.eq(planet.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_OVERRIDES_ERROR"))?;
 
         planet_member::Entity::delete_many()
             .filter(planet_member::Column::Planet.eq(planet.id.clone()))
-            .exec(db)
+            .exec(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "DELETE_MEMBERS_ERROR"))?;
 
         planet_role::Entity::delete_many()
             .filter(planet_role::Column::Planet.eq(planet.id.clone()))
-            .exec(db)
+            .exec(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "DELETE_ROLES_ERROR"))?;
 
         planet_component::Entity::delete_many()
             .filter(planet_component::Column::Planet.eq(planet.id.clone()))
-            .exec(db)
+            .exec(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "DELETE_COMPONENTS_ERROR"))?;
 
         active_planet
-            .delete(db)
+            .delete(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "DELETE_PLANET_ERROR"))
-            .map(|_| true)
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_PLANET_ERROR"))?;
+
+        db::commit(txn).await?;
+
+        for cleanup in cleanups {
+            cleanup.await;
+        }
+
+        Ok(true)
     }
 
     /// Changes the home component of a planet.
     #[graphql(complexity = 50)]
     async fn set_home_component(&self, ctx: &Context<'_>, id: ID) -> Result<planet::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -273,11 +435,31 @@ impl PlanetMutation {
         }
 
         let mut active_planet: planet::ActiveModel = planet.into();
-        active_planet.home = ActiveValue::Set(Some(component.id));
+        active_planet.home = ActiveValue::Set(Some(component.id.clone()));
 
-        active_planet
-            .update(db)
+        let txn = db::begin(db).await?;
+
+        let planet = active_planet
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            planet.id.clone(),
+            user_id,
+            AuditAction::PlanetSetHome,
+            Some(component.id),
+            Some(component.name),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::PlanetUpdated {
+            planet: planet.id.clone(),
+        });
+
+        Ok(planet)
     }
 }
@@ -1,13 +1,17 @@
-use crate::entities::planet_role;
+use crate::audit;
+use crate::db;
+use crate::entities::audit_log::AuditAction;
+use crate::entities::{auto_role, auto_role_assignment, component_permission, planet_role};
 use crate::errors;
 use crate::permissions::util;
 use crate::sessions::Session;
+use crate::subscriptions::{Broker, ChangeKind, PlanetEvent};
 use async_graphql::{Context, Description, Error, InputObject, Object, ID};
 use nanoid::nanoid;
+use sea_orm::sea_query::Query;
 use sea_orm::{
     ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseBackend,
-    DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Statement, TransactionTrait,
-    TryIntoModel,
+    DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Statement, TryIntoModel,
 };
 
 #[derive(InputObject, Debug, Clone)]
@@ -30,11 +34,12 @@ impl RoleMutation {
         name: String,
     ) -> Result<planet_role::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
         let planet = util::get_planet(planet_id.to_string(), db).await?;
-        let member = util::get_planet_member(user_id, planet_id.to_string(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), planet_id.to_string(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission("planet.roles.create", &planet, member, roles)?;
 
@@ -56,14 +61,36 @@ impl RoleMutation {
             position: ActiveValue::Set(position),
         };
 
+        let txn = db::begin(db).await?;
+
         planet_role::Entity::insert(new_role.clone())
-            .exec(db)
+            .exec(&txn)
             .await
             .map_err(|_| errors::create_internal_server_error(None, "INSERTION_ERROR"))?;
 
-        new_role
+        let role = new_role
             .try_into_model()
-            .map_err(|_| errors::create_internal_server_error(None, "CONVERSION_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "CONVERSION_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            role.planet.clone(),
+            user_id,
+            AuditAction::RoleCreate,
+            Some(role.id.clone()),
+            Some(role.name.clone()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::RoleChanged {
+            planet: role.planet.clone(),
+            role: role.id.clone(),
+            change: ChangeKind::Created,
+        });
+
+        Ok(role)
     }
 
     /// Renames and changes the color of a role.
@@ -76,6 +103,7 @@ impl RoleMutation {
         color: String,
     ) -> Result<planet_role::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -86,7 +114,7 @@ impl RoleMutation {
             .ok_or(errors::create_not_found_error())?;
 
         let planet = util::get_planet(role.planet.clone(), db).await?;
-        let member = util::get_planet_member(user_id, role.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), role.planet.clone(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission("planet.roles.edit", &planet, member.clone(), roles.clone())?;
         util::high_enough(roles, vec![role.clone()], member)?;
@@ -102,10 +130,32 @@ impl RoleMutation {
         active_role.name = ActiveValue::Set(name);
         active_role.color = ActiveValue::Set(color);
 
-        active_role
-            .update(db)
+        let txn = db::begin(db).await?;
+
+        let role = active_role
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            role.planet.clone(),
+            user_id,
+            AuditAction::RoleUpdate,
+            Some(role.id.clone()),
+            Some(format!("{} ({})", role.name, role.color)),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::RoleChanged {
+            planet: role.planet.clone(),
+            role: role.id.clone(),
+            change: ChangeKind::Updated,
+        });
+
+        Ok(role)
     }
 
     /// Updates permissions for the specified planet role.
@@ -121,6 +171,7 @@ impl RoleMutation {
         permissions: Vec<String>,
     ) -> Result<planet_role::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -131,7 +182,7 @@ impl RoleMutation {
             .ok_or(errors::create_not_found_error())?;
 
         let planet = util::get_planet(role.planet.clone(), db).await?;
-        let member = util::get_planet_member(user_id, role.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), role.planet.clone(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission(
             "planet.roles.edit_permissions",
@@ -142,19 +193,44 @@ impl RoleMutation {
         util::high_enough(roles, vec![role.clone()], member)?;
 
         let mut active_role: planet_role::ActiveModel = role.clone().into();
-        active_role.permissions =
-            ActiveValue::Set(util::update_permissions(role.permissions, permissions)?);
+        active_role.permissions = ActiveValue::Set(util::update_permissions(
+            role.permissions,
+            permissions.clone(),
+        )?);
 
-        active_role
-            .update(db)
+        let txn = db::begin(db).await?;
+
+        let role = active_role
+            .update(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            role.planet.clone(),
+            user_id,
+            AuditAction::RoleUpdatePermissions,
+            Some(role.id.clone()),
+            Some(permissions.join(" ")),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::RoleChanged {
+            planet: role.planet.clone(),
+            role: role.id.clone(),
+            change: ChangeKind::Updated,
+        });
+
+        Ok(role)
     }
 
     /// Deletes a role.
     #[graphql(complexity = 500)]
     async fn delete_role(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -165,7 +241,7 @@ impl RoleMutation {
             .ok_or(errors::create_not_found_error())?;
 
         let planet = util::get_planet(role.planet.clone(), db).await?;
-        let member = util::get_planet_member(user_id, role.planet.clone(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), role.planet.clone(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission(
             "planet.roles.delete",
@@ -175,7 +251,9 @@ impl RoleMutation {
         )?;
         util::high_enough(roles, vec![role.clone()], member)?;
 
-        db.query_one(Statement::from_sql_and_values(
+        let txn = db::begin(db).await?;
+
+        txn.query_one(Statement::from_sql_and_values(
             DatabaseBackend::Postgres,
             r#"SELECT ARRAY_REMOVE(roles, $1) FROM planet_member;"#,
             [role.id.clone().into()],
@@ -183,13 +261,58 @@ impl RoleMutation {
         .await
         .map_err(|_| errors::create_internal_server_error(None, "REMOVE_ARRAY_ERROR"))?;
 
-        let active_role: planet_role::ActiveModel = role.into();
+        auto_role_assignment::Entity::delete_many()
+            .filter(
+                auto_role_assignment::Column::Rule.in_subquery(
+                    Query::select()
+                        .column(auto_role::Column::Id)
+                        .from(auto_role::Entity)
+                        .and_where(auto_role::Column::Role.eq(role.id.clone()))
+                        .to_owned(),
+                ),
+            )
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_ASSIGNMENTS_ERROR"))?;
+
+        auto_role::Entity::delete_many()
+            .filter(auto_role::Column::Role.eq(role.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_AUTO_ROLES_ERROR"))?;
+
+        component_permission::Entity::delete_many()
+            .filter(component_permission::Column::Role.eq(role.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_OVERRIDES_ERROR"))?;
+
+        let active_role: planet_role::ActiveModel = role.clone().into();
 
         active_role
-            .delete(db)
+            .delete(&txn)
             .await
-            .map_err(|_| errors::create_internal_server_error(None, "DELETE_ROLE_ERROR"))
-            .map(|_| true)
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_ROLE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            role.planet.clone(),
+            user_id,
+            AuditAction::RoleDelete,
+            Some(role.id.clone()),
+            Some(role.name.clone()),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        broker.publish(PlanetEvent::RoleChanged {
+            planet: role.planet.clone(),
+            role: role.id.clone(),
+            change: ChangeKind::Deleted,
+        });
+
+        Ok(true)
     }
 
     /// Changes the order of roles.
@@ -201,6 +324,7 @@ impl RoleMutation {
         mut positions: Vec<RoleOrderEntry>,
     ) -> Result<Vec<planet_role::Model>, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let broker = ctx.data::<Broker>().unwrap();
         let session = ctx.data::<Session>().unwrap();
         let user_id = session.user.as_ref().map(|user| user.id.clone());
 
@@ -230,7 +354,7 @@ impl RoleMutation {
 
         // ensure we even have permission to do this
         let planet = util::get_planet(planet_id.to_string(), db).await?;
-        let member = util::get_planet_member(user_id, planet_id.to_string(), db).await?;
+        let member = util::get_planet_member(user_id.clone(), planet_id.to_string(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
         util::check_permission(
             "planet.roles.delete",
@@ -277,7 +401,7 @@ impl RoleMutation {
         }
 
         // store the updated roles
-        let txn = db.begin().await?;
+        let txn = db::begin(db).await?;
 
         for role in filtered_roles {
             let mut active_role: planet_role::ActiveModel = role.clone().into();
@@ -291,7 +415,31 @@ impl RoleMutation {
             active_role.update(&txn).await?;
         }
 
-        txn.commit().await?;
+        audit::log_action(
+            &txn,
+            planet_id.to_string(),
+            user_id,
+            AuditAction::RoleReorder,
+            None,
+            Some(
+                positions
+                    .iter()
+                    .map(|p| format!("{}:{}", p.role, p.position))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            ),
+        )
+        .await?;
+
+        db::commit(txn).await?;
+
+        for position in &positions {
+            broker.publish(PlanetEvent::RoleChanged {
+                planet: planet_id.to_string(),
+                role: position.role.clone(),
+                change: ChangeKind::Updated,
+            });
+        }
 
         // return the updated roles
         planet_role::Entity::find()
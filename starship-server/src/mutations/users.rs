@@ -1,13 +1,16 @@
+use crate::config::Config;
 use crate::entities::prelude::Token;
 use crate::entities::prelude::User;
 use crate::entities::token;
 use crate::entities::user;
 use crate::errors;
 use crate::guards::session::{SessionGuard, SessionType};
-use crate::permissions::util::verify_token;
+use crate::permissions::util::{validate_ban, verify_token};
+use crate::presence::Presence;
 use crate::sessions::{JWTLoginToken, Session};
 use async_graphql::{Context, Description, Error, Object, SimpleObject, ID};
 use bcrypt::hash;
+use chrono::NaiveDateTime;
 use email_address::EmailAddress;
 use hmac::{Hmac, Mac};
 use jwt::SignWithKey;
@@ -19,7 +22,6 @@ use sea_orm::{
     ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
 };
 use sha2::Sha256;
-use std::env;
 
 #[derive(SimpleObject)]
 struct LoginPayload {
@@ -46,8 +48,16 @@ impl UserMutation {
         // TODO: add RECAPTCHA support
         // TODO: verify email
 
+        let config = ctx.data::<Config>().unwrap();
         let db = ctx.data::<DatabaseConnection>().unwrap();
 
+        if !config.features.registration {
+            return Err(errors::create_forbidden_error(
+                Some("Registration is disabled on this server."),
+                "REGISTRATION_DISABLED",
+            ));
+        }
+
         let existing_user = User::find()
             .filter(
                 user::Column::Username
@@ -124,6 +134,7 @@ impl UserMutation {
         username: String,
         password: String,
     ) -> Result<LoginPayload, Error> {
+        let config = ctx.data::<Config>().unwrap();
         let db = ctx.data::<DatabaseConnection>().unwrap();
         let session = ctx.data::<Session>().unwrap();
 
@@ -137,7 +148,7 @@ impl UserMutation {
                 "INVALID_USER",
             ))?;
 
-        if env::var("SMTP_HOST").is_ok() && !user.verified {
+        if config.smtp.is_some() && !user.verified {
             return Err(errors::create_forbidden_error(
                 Some("You need to verify your email."),
                 "UNVERIFIED_EMAIL",
@@ -155,7 +166,7 @@ impl UserMutation {
         }
 
         //TODO: Find approx. location from IP
-        //TODO: Find OS and Browser
+        //TODO: Parse the OS and browser out of the user agent
 
         let addr = match session.ip_address {
             Some(value) => value.to_string(),
@@ -169,7 +180,12 @@ impl UserMutation {
             location: ActiveValue::Set("Unknown".to_string()),
             latitude: ActiveValue::Set(None),
             longitude: ActiveValue::Set(None),
-            browser: ActiveValue::Set("Unknown".to_string()),
+            browser: ActiveValue::Set(
+                session
+                    .user_agent
+                    .clone()
+                    .unwrap_or_else(|| "Unknown".to_string()),
+            ),
             operating_system: ActiveValue::Set("Unknown".to_string()),
             verified: ActiveValue::Set(!user.tfa_enabled),
         };
@@ -184,8 +200,7 @@ impl UserMutation {
             user_id: user.id,
         };
 
-        let secret = env::var("SECRET").unwrap();
-        let key: Hmac<Sha256> = Hmac::new_from_slice(secret.as_bytes()).unwrap();
+        let key: Hmac<Sha256> = Hmac::new_from_slice(config.auth.secret.as_bytes()).unwrap();
         let token = jwt_data.sign_with_key(&key).unwrap();
 
         Ok(LoginPayload {
@@ -194,10 +209,18 @@ impl UserMutation {
         })
     }
 
-    /// Toggles whether or not a user is banned.
+    /// Toggles whether or not a user is banned. When banning a user, a reason and an expiry date
+    /// can optionally be provided. Bans without an expiry date are permanent.
     #[graphql(guard = "SessionGuard::new(SessionType::Admin)", complexity = 10)]
-    async fn ban_user(&self, ctx: &Context<'_>, user_id: ID) -> Result<user::Model, Error> {
+    async fn ban_user(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+        reason: Option<String>,
+        expires: Option<NaiveDateTime>,
+    ) -> Result<user::Model, Error> {
         let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
         let id = user_id.to_string();
 
         let user = User::find_by_id(id)
@@ -216,6 +239,19 @@ impl UserMutation {
         let mut active_user: user::ActiveModel = user.clone().into();
         active_user.banned = ActiveValue::Set(!user.banned);
 
+        if user.banned {
+            active_user.ban_reason = ActiveValue::Set(None);
+            active_user.ban_expires = ActiveValue::Set(None);
+            active_user.banned_by = ActiveValue::Set(None);
+        } else {
+            validate_ban(reason.as_ref(), expires)?;
+
+            active_user.ban_reason = ActiveValue::Set(reason);
+            active_user.ban_expires = ActiveValue::Set(expires);
+            active_user.banned_by =
+                ActiveValue::Set(session.user.as_ref().map(|user| user.id.clone()));
+        }
+
         active_user
             .update(db)
             .await
@@ -288,7 +324,8 @@ impl UserMutation {
             .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
     }
 
-    /// Changes the current user's notification setting.
+    /// Changes the current user's notification setting, from `0` for every notification to `4` for
+    /// none. See `notifications::wants_notification` for what each setting receives.
     #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 10)]
     async fn set_notification_setting(
         &self,
@@ -313,6 +350,92 @@ impl UserMutation {
             .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
     }
 
+    /// Sets or clears the current user's custom status message.
+    #[graphql(guard = "SessionGuard::new(SessionType::NotBanned)", complexity = 10)]
+    async fn set_status_message(
+        &self,
+        ctx: &Context<'_>,
+        message: Option<String>,
+    ) -> Result<user::Model, Error> {
+        let message = message
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty());
+
+        if message.as_ref().is_some_and(|message| message.len() > 128) {
+            return Err(errors::create_user_input_error(
+                "Your status message cannot be longer than 128 characters.",
+                "STATUS_MESSAGE_TOO_LONG",
+            ));
+        }
+
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+
+        let mut active_user: user::ActiveModel = session.user.clone().unwrap().into();
+        active_user.status_message = ActiveValue::Set(message);
+
+        active_user
+            .update(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+    }
+
+    /// Sets whether or not the current user appears offline to everyone else.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 10)]
+    async fn set_invisible(
+        &self,
+        ctx: &Context<'_>,
+        invisible: bool,
+    ) -> Result<user::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+
+        let mut active_user: user::ActiveModel = session.user.clone().unwrap().into();
+        active_user.invisible = ActiveValue::Set(invisible);
+
+        active_user
+            .update(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))
+    }
+
+    /// Creates a new calendar token for the current user and returns it, replacing any previous
+    /// token. Calendar apps can't log in, so they add the token to the URL of an events feed as
+    /// `?token=` to see the events the user can view.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 10)]
+    async fn reset_calendar_token(&self, ctx: &Context<'_>) -> Result<String, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+
+        let token = nanoid!(32);
+
+        let mut active_user: user::ActiveModel = session.user.clone().unwrap().into();
+        active_user.calendar_token = ActiveValue::Set(Some(token.clone()));
+
+        active_user
+            .update(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "UPDATE_ERROR"))?;
+
+        Ok(token)
+    }
+
+    /// Keeps the current user online. Clients that don't hold a WebSocket connection open should
+    /// send a heartbeat every minute, and set `idle` when the user isn't doing anything.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 10)]
+    async fn heartbeat(&self, ctx: &Context<'_>, idle: Option<bool>) -> Result<bool, Error> {
+        let presence = ctx.data::<Presence>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+
+        // unwrap is safe because guard guarantees we have a user
+        presence.heartbeat(
+            session.user.as_ref().unwrap().id.clone(),
+            idle.unwrap_or(false),
+        );
+
+        Ok(true)
+    }
+
     /// Generates and stores a new TOTP secret for the current user.
     /// NB: This is step 1/2 in the TOTP flow. You need to run the `confirmTFA` mutation as well.
     #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 10)]
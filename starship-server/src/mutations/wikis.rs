@@ -0,0 +1,267 @@
+use crate::audit;
+use crate::components::access::ComponentAccess;
+use crate::components::wiki::{
+    check_slug_available, parse_links, save_revision, slugify, validate_content, validate_summary,
+    validate_title,
+};
+use crate::db::{begin, commit};
+use crate::entities::audit_log::AuditAction;
+use crate::entities::{wiki_article, wiki_revision};
+use crate::errors;
+use crate::guards::session::{SessionGuard, SessionType};
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use nanoid::nanoid;
+use sea_orm::{ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter};
+
+/// Gets a wiki article along with the wiki component it belongs to.
+async fn get_article(
+    ctx: &Context<'_>,
+    id: &ID,
+) -> Result<(ComponentAccess, wiki_article::Model), Error> {
+    let db = ctx.data::<DatabaseConnection>().unwrap();
+    let session = ctx.data::<Session>().unwrap();
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+    let article = wiki_article::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "ARTICLE_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let access = ComponentAccess::load_by_data(article.wiki.clone(), "wiki", user_id, db).await?;
+    access.check_permission("wiki.view")?;
+
+    Ok((access, article))
+}
+
+#[derive(Default, Description)]
+pub struct WikiMutation;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl WikiMutation {
+    /// Creates an article in a wiki, saving its content as the article's first revision.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn create_wiki_article(
+        &self,
+        ctx: &Context<'_>,
+        component_id: ID,
+        title: String,
+        content: String,
+        summary: Option<String>,
+    ) -> Result<wiki_article::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let access =
+            ComponentAccess::load(component_id.to_string(), "wiki", Some(user_id.clone()), db)
+                .await?;
+        access.check_permission("wiki.view")?;
+        access.check_permission("wiki.articles.create")?;
+
+        let title = validate_title(&title)?;
+        validate_content(&content)?;
+        let summary = validate_summary(summary)?;
+
+        let wiki = access.component.component_id.clone();
+        let slug = slugify(&title);
+        check_slug_available(&wiki, &slug, None, db).await?;
+
+        let now = chrono::offset::Utc::now().naive_utc();
+
+        let article = wiki_article::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            wiki: ActiveValue::Set(wiki),
+            title: ActiveValue::Set(title.clone()),
+            slug: ActiveValue::Set(slug),
+            links: ActiveValue::Set(parse_links(&content)),
+            content: ActiveValue::Set(content.clone()),
+            revision: ActiveValue::Set(1),
+            created: ActiveValue::Set(now),
+            updated: ActiveValue::Set(now),
+            updated_by: ActiveValue::Set(Some(user_id.clone())),
+        };
+
+        let txn = begin(db).await?;
+
+        let article = wiki_article::Entity::insert(article)
+            .exec_with_returning(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "ARTICLE_INSERTION_ERROR"))?;
+
+        let revision = wiki_revision::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            article: ActiveValue::Set(article.id.clone()),
+            number: ActiveValue::Set(1),
+            title: ActiveValue::Set(title),
+            content: ActiveValue::Set(content),
+            summary: ActiveValue::Set(summary),
+            author: ActiveValue::Set(Some(user_id)),
+            created: ActiveValue::Set(now),
+        };
+
+        wiki_revision::Entity::insert(revision)
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "REVISION_INSERTION_ERROR"))?;
+
+        access.record_activity(&txn).await?;
+
+        commit(txn).await?;
+
+        Ok(article)
+    }
+
+    /// Edits an article, saving the result as a new revision. If `baseRevision` is provided and
+    /// the article has been edited since that revision, the edit is rejected so that the other
+    /// changes aren't lost.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn edit_wiki_article(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        title: Option<String>,
+        content: Option<String>,
+        summary: Option<String>,
+        base_revision: Option<i32>,
+    ) -> Result<wiki_article::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, article) = get_article(ctx, &id).await?;
+        access.check_permission("wiki.articles.edit")?;
+
+        if base_revision.is_some_and(|base| base != article.revision) {
+            return Err(errors::create_user_input_error(
+                "This article has been edited since you started editing it.",
+                "EDIT_CONFLICT",
+            ));
+        }
+
+        let title = match title {
+            Some(title) => validate_title(&title)?,
+            None => article.title.clone(),
+        };
+
+        let content = content.unwrap_or_else(|| article.content.clone());
+        validate_content(&content)?;
+        let summary = validate_summary(summary)?;
+
+        if title == article.title && content == article.content {
+            return Ok(article);
+        }
+
+        check_slug_available(&article.wiki, &slugify(&title), Some(&article.id), db).await?;
+
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = access.user_id.clone().unwrap();
+
+        let txn = begin(db).await?;
+        let article = save_revision(article, title, content, summary, user_id, &txn).await?;
+        access.record_activity(&txn).await?;
+        commit(txn).await?;
+
+        Ok(article)
+    }
+
+    /// Deletes an article, along with its revision history.
+    #[graphql(complexity = 20)]
+    async fn delete_wiki_article(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, article) = get_article(ctx, &id).await?;
+        access.check_permission("wiki.articles.delete")?;
+
+        let txn = begin(db).await?;
+
+        wiki_revision::Entity::delete_many()
+            .filter(wiki_revision::Column::Article.eq(article.id.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_REVISIONS_ERROR"))?;
+
+        wiki_article::Entity::delete_by_id(article.id.clone())
+            .exec(&txn)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "DELETE_ARTICLE_ERROR"))?;
+
+        audit::log_action(
+            &txn,
+            access.planet.id,
+            access.user_id,
+            AuditAction::WikiArticleDelete,
+            Some(article.id),
+            Some(article.title),
+        )
+        .await?;
+
+        commit(txn).await?;
+
+        Ok(true)
+    }
+
+    /// Restores the title and content an article had at one of its revisions. The revert is saved
+    /// as a new revision, so the history after that revision is kept.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 20)]
+    async fn revert_to_revision(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        number: i32,
+        summary: Option<String>,
+    ) -> Result<wiki_article::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let (access, article) = get_article(ctx, &id).await?;
+        access.check_permission("wiki.articles.edit")?;
+
+        let revision = article
+            .find_related(wiki_revision::Entity)
+            .filter(wiki_revision::Column::Number.eq(number))
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_REVISION_ERROR"))?
+            .ok_or(errors::create_not_found_error())?;
+
+        let summary = validate_summary(summary)?
+            .or_else(|| Some(format!("Reverted to revision {}", revision.number)));
+
+        check_slug_available(
+            &article.wiki,
+            &slugify(&revision.title),
+            Some(&article.id),
+            db,
+        )
+        .await?;
+
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = access.user_id.clone().unwrap();
+
+        let txn = begin(db).await?;
+
+        let article = save_revision(
+            article,
+            revision.title,
+            revision.content,
+            summary,
+            user_id,
+            &txn,
+        )
+        .await?;
+
+        audit::log_action(
+            &txn,
+            access.planet.id,
+            access.user_id,
+            AuditAction::WikiArticleRevert,
+            Some(article.id.clone()),
+            Some(number.to_string()),
+        )
+        .await?;
+
+        commit(txn).await?;
+
+        Ok(article)
+    }
+}
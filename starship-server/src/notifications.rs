@@ -0,0 +1,248 @@
+use crate::components::access::ComponentAccess;
+use crate::entities::notification::{self, NotificationKind};
+use crate::entities::user;
+use crate::errors;
+use async_graphql::Error;
+use nanoid::nanoid;
+use sea_orm::{ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+
+/// The most characters of the content that caused a notification that are kept with it.
+const EXCERPT_LENGTH: usize = 200;
+
+/// The most users a single piece of content can mention.
+const MAX_MENTIONS: usize = 20;
+
+/// A notification to be sent to a user.
+pub struct NewNotification {
+    /// The ID of the user receiving the notification.
+    pub user: String,
+    pub kind: NotificationKind,
+    pub planet: Option<String>,
+    pub component: Option<String>,
+    /// The ID of the object the notification is about, such as a message.
+    pub target: Option<String>,
+    /// The ID of the user that caused the notification, if any.
+    pub actor: Option<String>,
+    pub content: String,
+}
+
+/// Checks whether a user with a notification setting wants notifications of a kind. The settings
+/// go from everything to nothing:
+///
+/// - `0`: all notifications, including replies to threads the user replied to
+/// - `1`: replies, mentions and direct messages
+/// - `2`: mentions and direct messages
+/// - `3`: direct messages only
+/// - `4`: no notifications
+pub fn wants_notification(setting: i16, kind: NotificationKind) -> bool {
+    let level = match kind {
+        NotificationKind::Activity => 0,
+        NotificationKind::Reply => 1,
+        NotificationKind::Mention => 2,
+        NotificationKind::DirectMessage => 3,
+    };
+
+    setting <= level
+}
+
+/// Shortens content to the length kept with a notification.
+pub fn excerpt(content: &str) -> String {
+    match content.char_indices().nth(EXCERPT_LENGTH) {
+        Some((index, _)) => format!("{}…", &content[..index]),
+        None => content.to_string(),
+    }
+}
+
+/// Finds the usernames mentioned in some content with `@username`. Each username is only returned
+/// once, and mentions inside words, such as email addresses, are ignored.
+pub fn parse_mentions(content: &str) -> Vec<String> {
+    let mut usernames: Vec<String> = vec![];
+    let mut previous: Option<char> = None;
+    let mut chars = content.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let at_word_start = previous.is_none_or(|p| !p.is_alphanumeric());
+        previous = Some(c);
+
+        if c != '@' || !at_word_start {
+            continue;
+        }
+
+        let start = index + 1;
+        let mut end = start;
+
+        while let Some(&(next_index, next)) = chars.peek() {
+            if !(next.is_ascii_alphanumeric() || matches!(next, '_' | '-' | '.')) {
+                break;
+            }
+
+            end = next_index + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+
+        // full stops at the end are more likely to end the sentence than the username
+        let username = content[start..end].trim_end_matches('.');
+
+        if !username.is_empty() && !usernames.iter().any(|existing| existing == username) {
+            usernames.push(username.to_string());
+        }
+
+        if usernames.len() == MAX_MENTIONS {
+            break;
+        }
+    }
+
+    usernames
+}
+
+/// Sends a notification, unless the user has turned off notifications of its kind, caused it
+/// themselves, or has blocked whoever caused it. Returns whether or not it was sent.
+///
+/// This accepts any connection so that notifications can be sent as part of a transaction.
+pub async fn notify<C: ConnectionTrait>(
+    db: &C,
+    notification: NewNotification,
+) -> Result<bool, Error> {
+    if notification.actor.as_ref() == Some(&notification.user) {
+        return Ok(false);
+    }
+
+    let Some(recipient) = user::Entity::find_by_id(notification.user.clone())
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_RECIPIENT_ERROR"))?
+    else {
+        return Ok(false);
+    };
+
+    if !wants_notification(recipient.notification_setting, notification.kind) {
+        return Ok(false);
+    }
+
+    if let Some(actor) = &notification.actor {
+        if recipient.blocked.contains(actor) {
+            return Ok(false);
+        }
+    }
+
+    let model = notification::ActiveModel {
+        id: ActiveValue::Set(nanoid!(16)),
+        user: ActiveValue::Set(notification.user),
+        kind: ActiveValue::Set(notification.kind),
+        planet: ActiveValue::Set(notification.planet),
+        component: ActiveValue::Set(notification.component),
+        target: ActiveValue::Set(notification.target),
+        actor: ActiveValue::Set(notification.actor),
+        content: ActiveValue::Set(excerpt(&notification.content)),
+        read: ActiveValue::Set(false),
+        created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+    };
+
+    notification::Entity::insert(model)
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "NOTIFICATION_INSERTION_ERROR"))?;
+
+    Ok(true)
+}
+
+/// Notifies the users mentioned in content posted to a component. Users that aren't members of
+/// the planet, or that can't see the component with `view_permission`, aren't notified.
+pub async fn notify_mentions<C: ConnectionTrait>(
+    db: &C,
+    access: &ComponentAccess,
+    view_permission: &str,
+    target: String,
+    content: &str,
+) -> Result<(), Error> {
+    let usernames = parse_mentions(content);
+
+    if usernames.is_empty() {
+        return Ok(());
+    }
+
+    let users = user::Entity::find()
+        .filter(user::Column::Username.is_in(usernames))
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_MENTIONED_ERROR"))?;
+
+    let users = users.into_iter().map(|user| user.id).collect();
+
+    notify_component_users(
+        db,
+        access,
+        view_permission,
+        users,
+        NotificationKind::Mention,
+        target,
+        content,
+    )
+    .await
+}
+
+/// Notifies users of activity in a component they took part in, such as a reply to a thread they
+/// replied to. Users that aren't members of the planet, or that can't see the component with
+/// `view_permission`, aren't notified.
+pub async fn notify_activity<C: ConnectionTrait>(
+    db: &C,
+    access: &ComponentAccess,
+    view_permission: &str,
+    users: Vec<String>,
+    target: String,
+    content: &str,
+) -> Result<(), Error> {
+    notify_component_users(
+        db,
+        access,
+        view_permission,
+        users,
+        NotificationKind::Activity,
+        target,
+        content,
+    )
+    .await
+}
+
+/// Sends a notification about content posted to a component to each user that can see it. The
+/// users' access is only read, so being notified doesn't count as activity on the planet.
+async fn notify_component_users<C: ConnectionTrait>(
+    db: &C,
+    access: &ComponentAccess,
+    view_permission: &str,
+    users: Vec<String>,
+    kind: NotificationKind,
+    target: String,
+    content: &str,
+) -> Result<(), Error> {
+    for user_id in users {
+        let user_access = ComponentAccess::load(
+            access.component.id.clone(),
+            &access.component.r#type,
+            Some(user_id.clone()),
+            db,
+        )
+        .await?;
+
+        if user_access.member.is_none() || !user_access.has_permission(view_permission) {
+            continue;
+        }
+
+        notify(
+            db,
+            NewNotification {
+                user: user_id,
+                kind,
+                planet: Some(access.planet.id.clone()),
+                component: Some(access.component.id.clone()),
+                target: Some(target.clone()),
+                actor: access.user_id.clone(),
+                content: content.to_string(),
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
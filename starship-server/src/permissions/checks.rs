@@ -1,32 +1,148 @@
-use super::constants;
+use super::constants::{self, PermissionLevel};
+use crate::entities::component_permission;
 use crate::entities::planet;
 use crate::entities::planet_member;
 use crate::entities::planet_role;
+use async_graphql::{Enum, SimpleObject, ID};
+use chrono::NaiveDateTime;
 use std::collections::HashMap;
 
-pub fn has_permission(
+/// Where a calculated permission came from.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PermissionSource {
+    /// Granted to everyone because the planet is public.
+    Default,
+    /// Granted or denied by one of the member's roles.
+    Role,
+    /// Granted or denied by an override on the member.
+    Member,
+    /// Granted because the member is an administrator.
+    Administrator,
+    /// Granted because the member owns the planet.
+    Owner,
+    /// Granted or denied to everyone by the component's overrides.
+    ComponentDefault,
+    /// Granted or denied by the component's override for one of the member's roles.
+    ComponentRole,
+    /// Granted or denied by the component's override for the member.
+    ComponentMember,
+}
+
+/// A permission after all of a member's roles and overrides have been applied.
+#[derive(SimpleObject, Clone, Debug, PartialEq, Eq)]
+#[graphql(rename_fields = "camelCase")]
+pub struct EffectivePermission {
+    pub permission: String,
+    pub granted: bool,
+    pub source: PermissionSource,
+    /// The role that granted or denied the permission, if the source is a role.
+    pub role_id: Option<ID>,
+}
+
+fn insert_permission(
+    calculated_permissions: &mut HashMap<String, EffectivePermission>,
     permission: &str,
+    granted: bool,
+    source: PermissionSource,
+    role_id: Option<&String>,
+) {
+    calculated_permissions.insert(
+        permission.to_string(),
+        EffectivePermission {
+            permission: permission.to_string(),
+            granted,
+            source,
+            role_id: role_id.map(|id| ID(id.clone())),
+        },
+    );
+}
+
+/// Orders the permissions of a single layer, such as a role, so that wildcards are applied before
+/// exact permissions. This lets an exact permission override a wildcard in the same layer, whatever
+/// order they were saved in.
+fn wildcards_first(permissions: &[String]) -> impl Iterator<Item = &String> {
+    let (wildcards, exact): (Vec<&String>, Vec<&String>) = permissions
+        .iter()
+        .partition(|permission| permission.ends_with(".*"));
+
+    wildcards.into_iter().chain(exact)
+}
+
+/// Applies a prefixed permission string (e.g. `+planet.view` or `-forum.posts.*`). Wildcards are
+/// expanded to every matching registered permission, so that they are overridden by any more
+/// specific permission applied after them.
+fn apply_permission(
+    calculated_permissions: &mut HashMap<String, EffectivePermission>,
+    permission: &str,
+    source: PermissionSource,
+    role_id: Option<&String>,
+) {
+    let mut permission_chars = permission.chars();
+    permission_chars.next();
+    let base_permission: String = permission_chars.collect();
+    let granted = permission.starts_with('+');
+
+    if base_permission.ends_with(".*") {
+        for matched in constants::matching(&base_permission) {
+            insert_permission(
+                calculated_permissions,
+                matched.name,
+                granted,
+                source,
+                role_id,
+            );
+        }
+    } else {
+        insert_permission(
+            calculated_permissions,
+            &base_permission,
+            granted,
+            source,
+            role_id,
+        );
+    }
+}
+
+/// Calculates every permission held or explicitly denied for a member, keyed by permission name.
+///
+/// If `component_overrides` is provided, the overrides for the component being accessed are
+/// applied on top of the planet's permissions: overrides for everyone first, then overrides for
+/// the member's roles in role order, and finally the override for the member themselves.
+/// Administrators and owners are granted every permission regardless of the overrides.
+pub fn calculate_permissions(
     planet: &planet::Model,
     member: Option<planet_member::Model>,
     roles: Option<Vec<planet_role::Model>>,
-) -> bool {
-    let mut calculated_permissions: HashMap<String, bool> = HashMap::new();
+    component_overrides: Option<&[component_permission::Model]>,
+) -> HashMap<String, EffectivePermission> {
+    let mut calculated_permissions: HashMap<String, EffectivePermission> = HashMap::new();
     let mut administrator = false;
     let mut owner = false;
+    let mut applied_roles: Vec<planet_role::Model> = vec![];
+    let mut applied_member: Option<String> = None;
 
     if !planet.private {
-        for permission in constants::VIEWER_PERMISSIONS
-            .iter()
-            .map(|a| (*a).trim_start_matches('+').to_string())
-        {
-            calculated_permissions.insert(permission, true);
+        for permission in constants::with_level(PermissionLevel::Viewer) {
+            insert_permission(
+                &mut calculated_permissions,
+                permission.name,
+                true,
+                PermissionSource::Default,
+                None,
+            );
         }
     };
 
     if planet.private && member.is_none() {
-        return false;
+        return HashMap::new();
     }
 
+    // overrides for everyone reach members that aren't banned and visitors to public planets, but
+    // never banned members
+    let everyone_applies = member
+        .as_ref()
+        .map_or(!planet.private, |member| !member.banned);
+
     if let Some(member) = member {
         if !member.banned {
             if let Some(roles) = roles {
@@ -35,28 +151,31 @@ pub fn has_permission(
 
                 for role in &role_vec {
                     if role.planet != planet.id {
-                        return false;
+                        return HashMap::new();
                     }
 
-                    for permission in &role.permissions {
+                    for permission in wildcards_first(&role.permissions) {
                         if permission == "+administrator" {
                             administrator = true;
                         }
 
-                        let mut permission_chars = permission.chars();
-                        permission_chars.next();
-
-                        calculated_permissions
-                            .insert(permission_chars.collect(), permission.starts_with('+'));
+                        apply_permission(
+                            &mut calculated_permissions,
+                            permission,
+                            PermissionSource::Role,
+                            Some(&role.id),
+                        );
                     }
                 }
+
+                applied_roles = role_vec;
             }
 
             if member.planet != planet.id {
-                return false;
+                return HashMap::new();
             }
 
-            for permission in member.permissions {
+            for permission in wildcards_first(&member.permissions) {
                 if permission == "+administrator" {
                     administrator = true;
                 }
@@ -65,38 +184,107 @@ pub fn has_permission(
                     owner = true;
                 }
 
-                let mut permission_chars = permission.chars();
-                permission_chars.next();
+                apply_permission(
+                    &mut calculated_permissions,
+                    permission,
+                    PermissionSource::Member,
+                    None,
+                );
+            }
 
-                calculated_permissions
-                    .insert(permission_chars.collect(), permission.starts_with('+'));
+            applied_member = Some(member.id);
+        }
+    }
+
+    if let Some(overrides) = component_overrides {
+        let everyone = overrides
+            .iter()
+            .filter(|o| everyone_applies && o.role.is_none() && o.member.is_none())
+            .map(|o| (o, PermissionSource::ComponentDefault, None));
+
+        let role_overrides = applied_roles.iter().flat_map(|role| {
+            overrides
+                .iter()
+                .filter(move |o| o.role.as_ref() == Some(&role.id))
+                .map(move |o| (o, PermissionSource::ComponentRole, Some(&role.id)))
+        });
+
+        let member_overrides = overrides
+            .iter()
+            .filter(|o| o.member.is_some() && o.member == applied_member)
+            .map(|o| (o, PermissionSource::ComponentMember, None));
+
+        let applicable: Vec<_> = everyone
+            .chain(role_overrides)
+            .chain(member_overrides)
+            .filter(|(o, _, _)| o.planet == planet.id)
+            .collect();
+
+        for (component_override, source, role_id) in applicable {
+            for permission in wildcards_first(&component_override.permissions) {
+                apply_permission(&mut calculated_permissions, permission, source, role_id);
             }
         }
     }
 
     if administrator || owner {
-        for permission in constants::ADMINISTRATOR_PERMISSIONS
-            .iter()
-            .chain(constants::VIEWER_PERMISSIONS.iter())
-            .chain(constants::MEMBER_PERMISSIONS.iter())
-            .map(|a| (*a).trim_start_matches('+').to_string())
+        let source = if owner {
+            PermissionSource::Owner
+        } else {
+            PermissionSource::Administrator
+        };
+
+        for permission in constants::with_level(PermissionLevel::Administrator)
+            .chain(constants::with_level(PermissionLevel::Viewer))
+            .chain(constants::with_level(PermissionLevel::Member))
         {
-            calculated_permissions.insert(permission, true);
+            insert_permission(
+                &mut calculated_permissions,
+                permission.name,
+                true,
+                source,
+                None,
+            );
         }
     }
 
     if owner {
-        for permission in constants::OWNER_PERMISSIONS
-            .iter()
-            .map(|a| (*a).trim_start_matches('+').to_string())
-        {
-            calculated_permissions.insert(permission, true);
+        for permission in constants::with_level(PermissionLevel::Owner) {
+            insert_permission(
+                &mut calculated_permissions,
+                permission.name,
+                true,
+                PermissionSource::Owner,
+                None,
+            );
         }
     }
 
     calculated_permissions
-        .iter()
-        .any(|p| *p.0 == permission && *p.1)
+}
+
+/// Checks whether or not a member holds a permission. If `component_overrides` is provided, the
+/// permission is checked against the overrides of the component being accessed as well.
+pub fn has_permission(
+    permission: &str,
+    planet: &planet::Model,
+    member: Option<planet_member::Model>,
+    roles: Option<Vec<planet_role::Model>>,
+    component_overrides: Option<&[component_permission::Model]>,
+) -> bool {
+    calculate_permissions(planet, member, roles, component_overrides)
+        .get(permission)
+        .is_some_and(|p| p.granted)
+}
+
+/// Gets the variant of a permission that applies to something owned by `owner`: `.own` if the user
+/// is the owner, and `.others` otherwise.
+pub fn ownership_permission(permission: &str, owner: &str, user_id: Option<&str>) -> String {
+    if user_id == Some(owner) {
+        format!("{permission}.own")
+    } else {
+        format!("{permission}.others")
+    }
 }
 
 pub fn high_enough(
@@ -121,3 +309,8 @@ pub fn high_enough(
 
     user_highest > origin_highest
 }
+
+/// Checks whether or not a ban has expired. Bans without an expiry date never expire.
+pub fn ban_expired(banned: bool, expires: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+    banned && expires.is_some_and(|expires| expires <= now)
+}
@@ -1,99 +1,620 @@
-// all permissions are in one of these three arrays
+use async_graphql::{Enum, SimpleObject};
 
-pub const VIEWER_PERMISSIONS: &[&str] = &[
-    // planet permissions
-    "+planet.view",        // view the planet
-    "+planet.member.view", // view members
-    // page permissions
-    "+page.view", // view pages
-    // forum permissions
-    "+forum.view", // view forums
-    // file permissions
-    "+files.view",             // view files
-    "+files.search",           // search the file tree
-    "+files.files.download",   // download files
-    "+files.folders.download", // download folders
-    // chat permissions
-    "+chat.view",   // view chats
-    "+chat.search", // search chat messages
-];
+/// The part of Starship a permission applies to.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PermissionCategory {
+    Special,
+    Planet,
+    Page,
+    Forum,
+    Files,
+    Chat,
+    Wiki,
+    Events,
+}
 
-pub const MEMBER_PERMISSIONS: &[&str] = &[
-    // forum permissions
-    "+forum.posts.create",       // create a forum post
-    "+forum.posts.edit.own",     // edit their own posts
-    "+forum.posts.delete.own",   // delete their own posts
-    "+forum.posts.react.own",    // react to their own posts
-    "+forum.posts.react.others", // react to other's posts
-    "+forum.posts.attach",       // attach files to posts
-    "+forum.post.reply",         // reply to posts
-    "+forum.tags.use",           // use tags in their files
-    // chat permissions
-    "+chat.messages.send",         // send chat messages
-    "+chat.messages.delete.own",   // delete own chat messages
-    "+chat.messages.edit.own",     // edit own chat messages
-    "+chat.messages.react.own",    // react to own chat messages
-    "+chat.messages.react.others", // react to other's chat messages
-    "+chat.messages.attach",       // attach files to chat messages
-];
+/// The group a permission is granted to by default.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PermissionLevel {
+    /// Granted to everyone on public planets, and to the default role.
+    Viewer,
+    /// Granted to the default role.
+    Member,
+    /// Granted to administrators.
+    Administrator,
+    /// Granted to the planet's owner.
+    Owner,
+}
+
+#[derive(SimpleObject, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Permission {
+    pub name: &'static str,
+    pub category: PermissionCategory,
+    pub level: PermissionLevel,
+    pub description: &'static str,
+}
 
-pub const ADMINISTRATOR_PERMISSIONS: &[&str] = &[
+impl Permission {
+    const fn new(
+        name: &'static str,
+        category: PermissionCategory,
+        level: PermissionLevel,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            category,
+            level,
+            description,
+        }
+    }
+}
+
+/// Every permission known to the server. Permissions that aren't in this list can't be granted or
+/// denied.
+pub const PERMISSIONS: &[Permission] = &[
     // special permissions
-    "+administrator",           // group permission
-    "+administrator.read_all",  // overrides per-component read rules
-    "+administrator.write_all", // overrides per-component write rules
+    Permission::new(
+        "administrator",
+        PermissionCategory::Special,
+        PermissionLevel::Administrator,
+        "Grants every administrator permission.",
+    ),
+    Permission::new(
+        "owner",
+        PermissionCategory::Special,
+        PermissionLevel::Owner,
+        "Grants every owner and administrator permission.",
+    ),
     // planet permissions
-    "+planet.change_name",            // change the planet name
-    "+planet.change_description",     // change the planet description
-    "+planet.component.create",       // create new components
-    "+planet.component.rename",       // rename components
-    "+planet.component.delete",       // delete components
-    "+planet.component.move",         // move components
-    "+planet.component.set_home",     // change the home component
-    "+planet.roles.create",           // create roles
-    "+planet.roles.edit",             // rename roles
-    "+planet.roles.edit_permissions", // change the permissions of roles
-    "+planet.roles.delete",           // delete roles
-    "+planet.roles.reorder",          // change the order of roles
-    "+planet.roles.add_member",       // add new members to roles
-    "+planet.roles.remove_member",
-    "+planet.member.edit_permissions", // edit a member's permissions
-    "+planet.member.ban",              // ban a member
-    "+planet.member.kick",             // kick a member
-    "+planet.emojis.create",           // create emojis
-    "+planet.emojis.delete",           // delete emojis
-    "+planet.change_css",              // change the planet's css
+    Permission::new(
+        "planet.view",
+        PermissionCategory::Planet,
+        PermissionLevel::Viewer,
+        "View the planet.",
+    ),
+    Permission::new(
+        "planet.member.view",
+        PermissionCategory::Planet,
+        PermissionLevel::Viewer,
+        "View members.",
+    ),
+    Permission::new(
+        "planet.change_name",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Change the planet name.",
+    ),
+    Permission::new(
+        "planet.change_description",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Change the planet description.",
+    ),
+    Permission::new(
+        "planet.component.create",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Create new components.",
+    ),
+    Permission::new(
+        "planet.component.rename",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Rename components.",
+    ),
+    Permission::new(
+        "planet.component.delete",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Delete components.",
+    ),
+    Permission::new(
+        "planet.component.move",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Move components.",
+    ),
+    Permission::new(
+        "planet.component.edit_permissions",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Change the permission overrides of components.",
+    ),
+    Permission::new(
+        "planet.component.set_home",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Change the home component.",
+    ),
+    Permission::new(
+        "planet.component.export",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Export the contents of components.",
+    ),
+    Permission::new(
+        "planet.roles.create",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Create roles.",
+    ),
+    Permission::new(
+        "planet.roles.edit",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Rename roles.",
+    ),
+    Permission::new(
+        "planet.roles.edit_permissions",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Change the permissions of roles.",
+    ),
+    Permission::new(
+        "planet.roles.delete",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Delete roles.",
+    ),
+    Permission::new(
+        "planet.roles.reorder",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Change the order of roles.",
+    ),
+    Permission::new(
+        "planet.roles.auto_assign",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Manage rules that give roles to members automatically.",
+    ),
+    Permission::new(
+        "planet.roles.add_member",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Add new members to roles.",
+    ),
+    Permission::new(
+        "planet.roles.remove_member",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Remove members from roles.",
+    ),
+    Permission::new(
+        "planet.member.edit_permissions",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Edit a member's permissions.",
+    ),
+    Permission::new(
+        "planet.member.ban",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Ban a member.",
+    ),
+    Permission::new(
+        "planet.member.kick",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Kick a member.",
+    ),
+    Permission::new(
+        "planet.join_requests.manage",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Review requests to join the planet, and change whether or not they can be made.",
+    ),
+    Permission::new(
+        "planet.member.prune",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Remove members that have been inactive for a number of days.",
+    ),
+    Permission::new(
+        "planet.member.manage_nicknames",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Change other member's nicknames.",
+    ),
+    Permission::new(
+        "planet.emojis.create",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Create emojis.",
+    ),
+    Permission::new(
+        "planet.emojis.delete",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Delete emojis.",
+    ),
+    Permission::new(
+        "planet.change_css",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "Change the planet's css.",
+    ),
+    Permission::new(
+        "planet.audit_log.view",
+        PermissionCategory::Planet,
+        PermissionLevel::Administrator,
+        "View the planet's audit log.",
+    ),
+    Permission::new(
+        "planet.change_publicity",
+        PermissionCategory::Planet,
+        PermissionLevel::Owner,
+        "Change whether or not the planet is public.",
+    ),
+    Permission::new(
+        "planet.delete",
+        PermissionCategory::Planet,
+        PermissionLevel::Owner,
+        "Delete the planet.",
+    ),
     // page permissions
-    "+page.edit", // edit pages
+    Permission::new(
+        "page.view",
+        PermissionCategory::Page,
+        PermissionLevel::Viewer,
+        "View pages.",
+    ),
+    Permission::new(
+        "page.edit",
+        PermissionCategory::Page,
+        PermissionLevel::Administrator,
+        "Edit pages.",
+    ),
     // forum permissions
-    "+forum.posts.edit.others",   // edit other's posts
-    "+forum.posts.delete.others", // delete other's posts
-    "+forum.posts.sticky",        // sticky/unsticky posts
-    "+forum.posts.lock.set",      // lock/unlock posts
-    "+forum.posts.lock.ignore",   // post replies to unlocked posts
-    "+forum.tags.create",         // create tags
-    "+forum.tags.delete",         // delete tags
-    // files permissions
-    "+files.files.upload",          // upload files
-    "+files.files.delete.own",      // delete own files
-    "+files.files.delete.others",   // delete other's files
-    "+files.files.rename.own",      // rename own files
-    "+files.files.rename.others",   // rename other's files
-    "+files.folders.create",        // create folders
-    "+files.folders.delete.own",    // delete own folders
-    "+files.folders.delete.others", // delete other's folders
-    "+files.folders.rename.own",    // rename own folders
-    "+files.folders.rename.others", // rename other's folders
+    Permission::new(
+        "forum.view",
+        PermissionCategory::Forum,
+        PermissionLevel::Viewer,
+        "View forums.",
+    ),
+    Permission::new(
+        "forum.posts.create",
+        PermissionCategory::Forum,
+        PermissionLevel::Member,
+        "Create a forum post.",
+    ),
+    Permission::new(
+        "forum.posts.edit.own",
+        PermissionCategory::Forum,
+        PermissionLevel::Member,
+        "Edit their own posts.",
+    ),
+    Permission::new(
+        "forum.posts.delete.own",
+        PermissionCategory::Forum,
+        PermissionLevel::Member,
+        "Delete their own posts.",
+    ),
+    Permission::new(
+        "forum.posts.react.own",
+        PermissionCategory::Forum,
+        PermissionLevel::Member,
+        "React to their own posts.",
+    ),
+    Permission::new(
+        "forum.posts.react.others",
+        PermissionCategory::Forum,
+        PermissionLevel::Member,
+        "React to other's posts.",
+    ),
+    Permission::new(
+        "forum.posts.attach",
+        PermissionCategory::Forum,
+        PermissionLevel::Member,
+        "Attach files to posts.",
+    ),
+    Permission::new(
+        "forum.post.reply",
+        PermissionCategory::Forum,
+        PermissionLevel::Member,
+        "Reply to posts.",
+    ),
+    Permission::new(
+        "forum.tags.use",
+        PermissionCategory::Forum,
+        PermissionLevel::Member,
+        "Use tags in their posts.",
+    ),
+    Permission::new(
+        "forum.posts.edit.others",
+        PermissionCategory::Forum,
+        PermissionLevel::Administrator,
+        "Edit other's posts.",
+    ),
+    Permission::new(
+        "forum.posts.delete.others",
+        PermissionCategory::Forum,
+        PermissionLevel::Administrator,
+        "Delete other's posts.",
+    ),
+    Permission::new(
+        "forum.posts.sticky",
+        PermissionCategory::Forum,
+        PermissionLevel::Administrator,
+        "Sticky/unsticky posts.",
+    ),
+    Permission::new(
+        "forum.posts.lock.set",
+        PermissionCategory::Forum,
+        PermissionLevel::Administrator,
+        "Lock/unlock posts.",
+    ),
+    Permission::new(
+        "forum.posts.lock.ignore",
+        PermissionCategory::Forum,
+        PermissionLevel::Administrator,
+        "Post replies to locked posts.",
+    ),
+    Permission::new(
+        "forum.tags.create",
+        PermissionCategory::Forum,
+        PermissionLevel::Administrator,
+        "Create tags.",
+    ),
+    Permission::new(
+        "forum.tags.delete",
+        PermissionCategory::Forum,
+        PermissionLevel::Administrator,
+        "Delete tags.",
+    ),
+    // file permissions
+    Permission::new(
+        "files.view",
+        PermissionCategory::Files,
+        PermissionLevel::Viewer,
+        "View files.",
+    ),
+    Permission::new(
+        "files.search",
+        PermissionCategory::Files,
+        PermissionLevel::Viewer,
+        "Search the file tree.",
+    ),
+    Permission::new(
+        "files.files.download",
+        PermissionCategory::Files,
+        PermissionLevel::Viewer,
+        "Download files.",
+    ),
+    Permission::new(
+        "files.folders.download",
+        PermissionCategory::Files,
+        PermissionLevel::Viewer,
+        "Download folders.",
+    ),
+    Permission::new(
+        "files.files.upload",
+        PermissionCategory::Files,
+        PermissionLevel::Administrator,
+        "Upload files.",
+    ),
+    Permission::new(
+        "files.files.delete.own",
+        PermissionCategory::Files,
+        PermissionLevel::Administrator,
+        "Delete own files.",
+    ),
+    Permission::new(
+        "files.files.delete.others",
+        PermissionCategory::Files,
+        PermissionLevel::Administrator,
+        "Delete other's files.",
+    ),
+    Permission::new(
+        "files.files.rename.own",
+        PermissionCategory::Files,
+        PermissionLevel::Administrator,
+        "Rename own files.",
+    ),
+    Permission::new(
+        "files.files.rename.others",
+        PermissionCategory::Files,
+        PermissionLevel::Administrator,
+        "Rename other's files.",
+    ),
+    Permission::new(
+        "files.folders.create",
+        PermissionCategory::Files,
+        PermissionLevel::Administrator,
+        "Create folders.",
+    ),
+    Permission::new(
+        "files.folders.delete.own",
+        PermissionCategory::Files,
+        PermissionLevel::Administrator,
+        "Delete own folders.",
+    ),
+    Permission::new(
+        "files.folders.delete.others",
+        PermissionCategory::Files,
+        PermissionLevel::Administrator,
+        "Delete other's folders.",
+    ),
+    Permission::new(
+        "files.folders.rename.own",
+        PermissionCategory::Files,
+        PermissionLevel::Administrator,
+        "Rename own folders.",
+    ),
+    Permission::new(
+        "files.folders.rename.others",
+        PermissionCategory::Files,
+        PermissionLevel::Administrator,
+        "Rename other's folders.",
+    ),
     // chat permissions
-    "+chat.set_topic",              // set the chat topic
-    "+chat.messages.delete.others", // delete other's messages
-    "+chat.messages.edit.others",   // delete other's edits
-    "+chat.messages.pin.own",       // pin own messages
-    "+chat.messages.pin.others",    // pin other's messages
+    Permission::new(
+        "chat.view",
+        PermissionCategory::Chat,
+        PermissionLevel::Viewer,
+        "View chats.",
+    ),
+    Permission::new(
+        "chat.search",
+        PermissionCategory::Chat,
+        PermissionLevel::Viewer,
+        "Search chat messages.",
+    ),
+    Permission::new(
+        "chat.messages.send",
+        PermissionCategory::Chat,
+        PermissionLevel::Member,
+        "Send chat messages.",
+    ),
+    Permission::new(
+        "chat.messages.delete.own",
+        PermissionCategory::Chat,
+        PermissionLevel::Member,
+        "Delete own chat messages.",
+    ),
+    Permission::new(
+        "chat.messages.edit.own",
+        PermissionCategory::Chat,
+        PermissionLevel::Member,
+        "Edit own chat messages.",
+    ),
+    Permission::new(
+        "chat.messages.react.own",
+        PermissionCategory::Chat,
+        PermissionLevel::Member,
+        "React to own chat messages.",
+    ),
+    Permission::new(
+        "chat.messages.react.others",
+        PermissionCategory::Chat,
+        PermissionLevel::Member,
+        "React to other's chat messages.",
+    ),
+    Permission::new(
+        "chat.messages.attach",
+        PermissionCategory::Chat,
+        PermissionLevel::Member,
+        "Attach files to chat messages.",
+    ),
+    Permission::new(
+        "chat.set_topic",
+        PermissionCategory::Chat,
+        PermissionLevel::Administrator,
+        "Set the chat topic.",
+    ),
+    Permission::new(
+        "chat.messages.delete.others",
+        PermissionCategory::Chat,
+        PermissionLevel::Administrator,
+        "Delete other's messages.",
+    ),
+    Permission::new(
+        "chat.messages.edit.others",
+        PermissionCategory::Chat,
+        PermissionLevel::Administrator,
+        "Edit other's messages.",
+    ),
+    Permission::new(
+        "chat.messages.pin.own",
+        PermissionCategory::Chat,
+        PermissionLevel::Administrator,
+        "Pin own messages.",
+    ),
+    Permission::new(
+        "chat.messages.pin.others",
+        PermissionCategory::Chat,
+        PermissionLevel::Administrator,
+        "Pin other's messages.",
+    ),
+    // wiki permissions
+    Permission::new(
+        "wiki.view",
+        PermissionCategory::Wiki,
+        PermissionLevel::Viewer,
+        "View wiki articles and their history.",
+    ),
+    Permission::new(
+        "wiki.articles.create",
+        PermissionCategory::Wiki,
+        PermissionLevel::Member,
+        "Create wiki articles.",
+    ),
+    Permission::new(
+        "wiki.articles.edit",
+        PermissionCategory::Wiki,
+        PermissionLevel::Member,
+        "Edit and revert wiki articles.",
+    ),
+    Permission::new(
+        "wiki.articles.delete",
+        PermissionCategory::Wiki,
+        PermissionLevel::Administrator,
+        "Delete wiki articles.",
+    ),
+    // event permissions
+    Permission::new(
+        "events.view",
+        PermissionCategory::Events,
+        PermissionLevel::Viewer,
+        "View events and who is attending them.",
+    ),
+    Permission::new(
+        "events.rsvp",
+        PermissionCategory::Events,
+        PermissionLevel::Member,
+        "RSVP to events.",
+    ),
+    Permission::new(
+        "events.create",
+        PermissionCategory::Events,
+        PermissionLevel::Administrator,
+        "Create events.",
+    ),
+    Permission::new(
+        "events.edit.own",
+        PermissionCategory::Events,
+        PermissionLevel::Administrator,
+        "Edit own events.",
+    ),
+    Permission::new(
+        "events.edit.others",
+        PermissionCategory::Events,
+        PermissionLevel::Administrator,
+        "Edit other's events.",
+    ),
+    Permission::new(
+        "events.delete.own",
+        PermissionCategory::Events,
+        PermissionLevel::Administrator,
+        "Delete own events.",
+    ),
+    Permission::new(
+        "events.delete.others",
+        PermissionCategory::Events,
+        PermissionLevel::Administrator,
+        "Delete other's events.",
+    ),
 ];
 
-pub const OWNER_PERMISSIONS: &[&str] = &[
-    "+owner",                   // group permission
-    "+planet.change_publicity", // change whether or not the planet is public
-    "+planet.delete",           // delete the planet
-];
+/// Returns every permission granted by default at the specified level.
+pub fn with_level(level: PermissionLevel) -> impl Iterator<Item = &'static Permission> {
+    PERMISSIONS.iter().filter(move |p| p.level == level)
+}
+
+/// Returns every permission matching the specified name. Names ending in `.*` are namespace
+/// wildcards: `forum.posts.*` matches every permission starting with `forum.posts.`.
+pub fn matching(name: &str) -> impl Iterator<Item = &'static Permission> + '_ {
+    let namespace = name.strip_suffix('*');
+
+    PERMISSIONS.iter().filter(move |p| match namespace {
+        Some(namespace) => p.name.starts_with(namespace),
+        None => p.name == name,
+    })
+}
+
+/// Checks whether or not a permission name (without a prefix) refers to at least one registered
+/// permission.
+pub fn is_valid(name: &str) -> bool {
+    (!name.ends_with('*') || name.ends_with(".*")) && matching(name).next().is_some()
+}
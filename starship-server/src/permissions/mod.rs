@@ -1,7 +1,7 @@
 /// Permissions checks.
 pub mod checks;
 
-/// Constants containing default & group permissions.
+/// The registry of every permission, along with the group each is granted to by default.
 pub mod constants;
 
 /// Various functions to make the permission checking flow easier.
@@ -1,16 +1,19 @@
 use super::checks;
-use crate::entities::{planet, planet_member, planet_role, user};
+use crate::entities::{component_permission, planet, planet_member, planet_role, user};
 use crate::errors;
-use crate::permissions::constants;
+use crate::permissions::constants::{self, PermissionLevel};
 use async_graphql::Error;
+use chrono::NaiveDateTime;
 use libreauth::oath::TOTPBuilder;
+use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    QueryFilter,
 };
 
 /// Gets a planet. If an error occurs or the planet is not found, an error ready for presentation to
 /// the client is returned.
-pub async fn get_planet(id: String, db: &DatabaseConnection) -> Result<planet::Model, Error> {
+pub async fn get_planet<C: ConnectionTrait>(id: String, db: &C) -> Result<planet::Model, Error> {
     planet::Entity::find_by_id(id.clone())
         .one(db)
         .await
@@ -20,10 +23,10 @@ pub async fn get_planet(id: String, db: &DatabaseConnection) -> Result<planet::M
 
 /// Gets a planet member. If an error occurs, an error ready for presentation to the client
 /// is returned.
-pub async fn get_planet_member(
+pub async fn get_planet_member<C: ConnectionTrait>(
     user_id: Option<String>,
     planet_id: String,
-    db: &DatabaseConnection,
+    db: &C,
 ) -> Result<Option<planet_member::Model>, Error> {
     match user_id {
         Some(user_id) => planet_member::Entity::find()
@@ -39,14 +42,38 @@ pub async fn get_planet_member(
     }
 }
 
+/// Updates the time a member was last active on their planet. This should be called when the
+/// member opens the planet and by mutations where the member does something on the planet, such as
+/// posting a message. To avoid writing on every request, this is only updated if the member hasn't
+/// been active for at least an hour.
+pub async fn record_activity<C: ConnectionTrait>(
+    member: &planet_member::Model,
+    db: &C,
+) -> Result<(), Error> {
+    let now = chrono::offset::Utc::now().naive_utc();
+
+    if now - member.last_active < chrono::Duration::hours(1) {
+        return Ok(());
+    }
+
+    planet_member::Entity::update_many()
+        .col_expr(planet_member::Column::LastActive, Expr::value(now))
+        .filter(planet_member::Column::Id.eq(member.id.clone()))
+        .exec(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "RECORD_ACTIVITY_ERROR"))?;
+
+    Ok(())
+}
+
 /// Gets the roles associated with a planet member. If an error occurs, an error ready for
 /// presentation to the client is returned.
 ///
 /// Note that the planet member input is an option: this function is designed to directly take the
 /// input of get planet member.
-pub async fn get_member_roles(
+pub async fn get_member_roles<C: ConnectionTrait>(
     planet_member: Option<planet_member::Model>,
-    db: &DatabaseConnection,
+    db: &C,
 ) -> Result<Option<Vec<planet_role::Model>>, Error> {
     match planet_member {
         Some(member) => planet_role::Entity::find()
@@ -59,21 +86,83 @@ pub async fn get_member_roles(
     }
 }
 
+/// Creates the error returned when a member is missing a permission. Banned members are told why
+/// they were banned instead of receiving a not found error.
+fn missing_permission_error(member: Option<planet_member::Model>) -> Error {
+    match member {
+        Some(member) if member.banned => errors::create_banned_error(
+            "this planet",
+            member.ban_reason.as_deref(),
+            member.ban_expires,
+            "MEMBER_BANNED",
+        ),
+        _ => errors::create_not_found_error(),
+    }
+}
+
 /// Checks for a permission and returns an error if that permission is not held by the user. This
 /// function exists to ensure permission behavior is consistent across the API.
+///
+/// Banned members are told why they were banned instead of receiving a not found error.
 pub fn check_permission(
     permission: &str,
     planet: &planet::Model,
     member: Option<planet_member::Model>,
     roles: Option<Vec<planet_role::Model>>,
 ) -> Result<(), Error> {
-    if checks::has_permission(permission, planet, member, roles) {
+    if checks::has_permission(permission, planet, member.clone(), roles, None) {
         Ok(())
     } else {
-        Err(errors::create_not_found_error())
+        Err(missing_permission_error(member))
+    }
+}
+
+/// Gets the permission overrides for a component. If an error occurs, an error ready for
+/// presentation to the client is returned.
+pub async fn get_component_overrides<C: ConnectionTrait>(
+    component_id: String,
+    db: &C,
+) -> Result<Vec<component_permission::Model>, Error> {
+    component_permission::Entity::find()
+        .filter(component_permission::Column::Component.eq(component_id))
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "OVERRIDES_RETRIEVAL_ERROR"))
+}
+
+/// Checks for a permission within a component, taking the component's permission overrides into
+/// account, and returns an error if that permission is not held by the user.
+pub fn check_component_permission(
+    permission: &str,
+    planet: &planet::Model,
+    member: Option<planet_member::Model>,
+    roles: Option<Vec<planet_role::Model>>,
+    overrides: &[component_permission::Model],
+) -> Result<(), Error> {
+    if checks::has_permission(permission, planet, member.clone(), roles, Some(overrides)) {
+        Ok(())
+    } else {
+        Err(missing_permission_error(member))
     }
 }
 
+/// Calculates the full set of permissions held or explicitly denied by a member, sorted by name.
+/// If `overrides` is provided, the permissions are calculated for the component they belong to.
+pub fn effective_permissions(
+    planet: &planet::Model,
+    member: Option<planet_member::Model>,
+    roles: Option<Vec<planet_role::Model>>,
+    overrides: Option<&[component_permission::Model]>,
+) -> Vec<checks::EffectivePermission> {
+    let mut permissions: Vec<checks::EffectivePermission> =
+        checks::calculate_permissions(planet, member, roles, overrides)
+            .into_values()
+            .collect();
+
+    permissions.sort_by(|a, b| a.permission.cmp(&b.permission));
+    permissions
+}
+
 /// Checks to see whether or not the user's highest priority role is higher than the origin's
 /// highest priority role, to be used in determining whether or not a role can be modified. This
 /// function exists to ensure permission behavior is consistent across the API.
@@ -89,12 +178,35 @@ pub fn high_enough(
     }
 }
 
-/// Modifies a permission vector using the prefixes from a vector of strings.
+/// Validates the reason and expiry date of a new ban. If either is invalid, an error ready for
+/// presentation to the client is returned.
+pub fn validate_ban(reason: Option<&String>, expires: Option<NaiveDateTime>) -> Result<(), Error> {
+    if reason.is_some_and(|reason| reason.len() > 512) {
+        return Err(errors::create_user_input_error(
+            "Ban reasons cannot be longer than 512 characters.",
+            "REASON_TOO_LONG",
+        ));
+    }
+
+    if expires.is_some_and(|expires| expires <= chrono::offset::Utc::now().naive_utc()) {
+        return Err(errors::create_user_input_error(
+            "A ban cannot expire in the past.",
+            "EXPIRY_IN_PAST",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Modifies a permission vector using the prefixes from a vector of strings. Permissions that
+/// aren't registered in `constants::PERMISSIONS` are rejected.
 ///
 /// Prefixes:
 /// '+' grants the permission.
 /// '*' (or any other unspecified character) falls back to the previous permission set.
 /// '-' explicitly denies the permission.
+///
+/// Permissions ending in `.*` apply to every permission in that namespace, e.g. `-forum.posts.*`.
 pub fn update_permissions(
     mut destination_vec: Vec<String>,
     permissions: Vec<String>,
@@ -113,9 +225,19 @@ pub fn update_permissions(
             base_p != base_permission
         });
 
-        if constants::MEMBER_PERMISSIONS
-            .iter()
-            .any(|&s| s == permission)
+        // unknown permissions can still be removed, in case they were stored before they were
+        // validated
+        let modifies = permission_prefix == Some('-') || permission_prefix == Some('+');
+
+        if modifies && !constants::is_valid(&base_permission) {
+            return Err(errors::create_user_input_error(
+                &format!("'{base_permission}' is not a valid permission."),
+                "UNKNOWN_PERMISSION",
+            ));
+        }
+
+        if permission_prefix == Some('+')
+            && constants::with_level(PermissionLevel::Member).any(|p| p.name == base_permission)
         {
             return Err(errors::create_user_input_error(
                 &format!("You cannot change the '{permission}' permission."),
@@ -123,7 +245,7 @@ pub fn update_permissions(
             ));
         }
 
-        if permission_prefix == Some('-') || permission_prefix == Some('+') {
+        if modifies {
             destination_vec.push(permission);
         }
     }
@@ -0,0 +1,188 @@
+use async_graphql::Enum;
+use chrono::{Duration, NaiveDateTime};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// How long after their last heartbeat a user without an open connection is still online.
+const HEARTBEAT_TIMEOUT: i64 = 90;
+
+/// How long a user can go without doing anything before they are shown as idle.
+const IDLE_AFTER: i64 = 5 * 60;
+
+/// Whether or not a user is using Starship right now.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PresenceStatus {
+    Online,
+    Idle,
+    Offline,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct UserPresence {
+    connections: usize,
+    last_seen: NaiveDateTime,
+    last_active: NaiveDateTime,
+}
+
+/// Keeps track of which users are online, from their open WebSocket connections and the
+/// heartbeats sent by clients that don't keep a connection open. Presence only lives in memory, so
+/// every user is offline when the server starts.
+#[derive(Clone, Default)]
+pub struct Presence {
+    users: Arc<Mutex<HashMap<String, UserPresence>>>,
+}
+
+fn now() -> NaiveDateTime {
+    chrono::offset::Utc::now().naive_utc()
+}
+
+impl Presence {
+    /// Records that a user opened a connection. The user stays online until the returned guard
+    /// is dropped.
+    pub fn connect(&self, user_id: String) -> PresenceGuard {
+        self.connect_at(user_id.clone(), now());
+
+        PresenceGuard {
+            presence: self.clone(),
+            user_id,
+        }
+    }
+
+    /// Records a heartbeat from a user. Clients should send one every minute or so, and say
+    /// whether the user is idle, for example because the window isn't focused.
+    pub fn heartbeat(&self, user_id: String, idle: bool) {
+        self.heartbeat_at(user_id, idle, now());
+    }
+
+    /// Gets whether a user is online, idle or offline.
+    pub fn status(&self, user_id: &str) -> PresenceStatus {
+        self.status_at(user_id, now())
+    }
+
+    pub(crate) fn connect_at(&self, user_id: String, at: NaiveDateTime) {
+        let mut users = self.users.lock().unwrap();
+
+        users
+            .entry(user_id)
+            .and_modify(|presence| {
+                presence.connections += 1;
+                presence.last_seen = at;
+                presence.last_active = at;
+            })
+            .or_insert(UserPresence {
+                connections: 1,
+                last_seen: at,
+                last_active: at,
+            });
+    }
+
+    pub(crate) fn disconnect_at(&self, user_id: &str, at: NaiveDateTime) {
+        let mut users = self.users.lock().unwrap();
+
+        if let Some(presence) = users.get_mut(user_id) {
+            presence.connections = presence.connections.saturating_sub(1);
+            presence.last_seen = at;
+        }
+    }
+
+    /// Records that a connected user did something, so that they aren't shown as idle.
+    pub(crate) fn activity_at(&self, user_id: &str, at: NaiveDateTime) {
+        let mut users = self.users.lock().unwrap();
+
+        if let Some(presence) = users.get_mut(user_id) {
+            presence.last_seen = at;
+            presence.last_active = at;
+        }
+    }
+
+    pub(crate) fn heartbeat_at(&self, user_id: String, idle: bool, at: NaiveDateTime) {
+        let mut users = self.users.lock().unwrap();
+
+        let presence = users.entry(user_id).or_insert(UserPresence {
+            connections: 0,
+            last_seen: at,
+            last_active: at,
+        });
+
+        presence.last_seen = at;
+
+        if !idle {
+            presence.last_active = at;
+        }
+    }
+
+    pub(crate) fn status_at(&self, user_id: &str, at: NaiveDateTime) -> PresenceStatus {
+        let users = self.users.lock().unwrap();
+
+        let Some(presence) = users.get(user_id) else {
+            return PresenceStatus::Offline;
+        };
+
+        if presence.connections == 0
+            && at - presence.last_seen > Duration::seconds(HEARTBEAT_TIMEOUT)
+        {
+            PresenceStatus::Offline
+        } else if at - presence.last_active > Duration::seconds(IDLE_AFTER) {
+            PresenceStatus::Idle
+        } else {
+            PresenceStatus::Online
+        }
+    }
+
+    /// Forgets users that have been offline for a while, so that the map doesn't keep growing.
+    pub(crate) fn prune_at(&self, at: NaiveDateTime) {
+        let mut users = self.users.lock().unwrap();
+
+        users.retain(|_, presence| {
+            presence.connections > 0
+                || at - presence.last_seen <= Duration::seconds(HEARTBEAT_TIMEOUT)
+        });
+    }
+
+    pub fn prune(&self) {
+        self.prune_at(now());
+    }
+
+    /// Counts the open WebSocket connections from signed in users.
+    pub fn connections(&self) -> usize {
+        let users = self.users.lock().unwrap();
+
+        users.values().map(|presence| presence.connections).sum()
+    }
+
+    /// Counts the users that are online or idle.
+    pub fn online_users(&self) -> usize {
+        self.online_users_at(now())
+    }
+
+    pub(crate) fn online_users_at(&self, at: NaiveDateTime) -> usize {
+        let users = self.users.lock().unwrap();
+
+        users
+            .values()
+            .filter(|presence| {
+                presence.connections > 0
+                    || at - presence.last_seen <= Duration::seconds(HEARTBEAT_TIMEOUT)
+            })
+            .count()
+    }
+}
+
+/// Keeps a user online for as long as one of their connections is open.
+pub struct PresenceGuard {
+    presence: Presence,
+    user_id: String,
+}
+
+impl PresenceGuard {
+    /// Records that the user sent something over the connection.
+    pub fn activity(&self) {
+        self.presence.activity_at(&self.user_id, now());
+    }
+}
+
+impl Drop for PresenceGuard {
+    fn drop(&mut self) {
+        self.presence.disconnect_at(&self.user_id, now());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::direct_messages::get_conversation;
+use crate::entities::{conversation, conversation_member};
+use crate::errors;
+use crate::guards::session::{SessionGuard, SessionType};
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use sea_orm::sea_query::Query;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+
+#[derive(Default, Description)]
+pub struct ConversationQuery;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl ConversationQuery {
+    /// Retrieves the current user's direct message conversations, most recently active first.
+    #[graphql(
+        guard = "SessionGuard::new(SessionType::User)",
+        complexity = "5 * size as usize + size as usize * child_complexity"
+    )]
+    async fn conversations(
+        &self,
+        ctx: &Context<'_>,
+        size: u64,
+        page: u64,
+    ) -> Result<Vec<conversation::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let memberships = Query::select()
+            .column(conversation_member::Column::Conversation)
+            .from(conversation_member::Entity)
+            .and_where(conversation_member::Column::User.eq(user_id))
+            .to_owned();
+
+        conversation::Entity::find()
+            .filter(conversation::Column::Id.in_subquery(memberships))
+            .order_by_desc(conversation::Column::Updated)
+            .order_by_desc(conversation::Column::Id)
+            .paginate(db, size)
+            .fetch_page(page)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FIND_CONVERSATIONS_ERROR"))
+    }
+
+    /// Finds one of the current user's conversations from its ID.
+    #[graphql(guard = "SessionGuard::new(SessionType::User)", complexity = 10)]
+    async fn conversation(&self, ctx: &Context<'_>, id: ID) -> Result<conversation::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        // unwrap is safe because guard guarantees we have a user
+        let user_id = session.user.as_ref().unwrap().id.clone();
+
+        let (conversation, _) = get_conversation(id.to_string(), &user_id, db).await?;
+
+        Ok(conversation)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::components::access::ComponentAccess;
+use crate::entities::file_tree;
+use crate::errors;
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+#[derive(Default, Description)]
+pub struct FileQuery;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl FileQuery {
+    /// Finds the file tree of a files component from the component's ID.
+    #[graphql(complexity = 10)]
+    async fn file_tree(
+        &self,
+        ctx: &Context<'_>,
+        component_id: ID,
+    ) -> Result<file_tree::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let access = ComponentAccess::load(component_id.to_string(), "files", user_id, db).await?;
+        access.check_permission("files.view")?;
+
+        file_tree::Entity::find_by_id(access.component.component_id)
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "TREE_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "TREE_MISSING_ERROR",
+            ))
+    }
+}
@@ -0,0 +1,32 @@
+use crate::components::access::ComponentAccess;
+use crate::entities::forum;
+use crate::errors;
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+#[derive(Default, Description)]
+pub struct ForumQuery;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl ForumQuery {
+    /// Finds the forum of a forum component from the component's ID.
+    #[graphql(complexity = 10)]
+    async fn forum(&self, ctx: &Context<'_>, component_id: ID) -> Result<forum::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        let access = ComponentAccess::load(component_id.to_string(), "forum", user_id, db).await?;
+        access.check_permission("forum.view")?;
+
+        forum::Entity::find_by_id(access.component.component_id)
+            .one(db)
+            .await
+            .map_err(|_| errors::create_internal_server_error(None, "FORUM_RETRIEVAL_ERROR"))?
+            .ok_or(errors::create_internal_server_error(
+                None,
+                "FORUM_MISSING_ERROR",
+            ))
+    }
+}
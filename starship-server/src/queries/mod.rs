@@ -1,8 +1,16 @@
+mod chats;
+mod conversations;
+mod events;
+mod files;
+mod forums;
 mod members;
+mod pages;
+mod permissions;
 mod planets;
 mod roles;
 mod sysinfo;
 mod users;
+mod wikis;
 
 use async_graphql::MergedObject;
 
@@ -13,4 +21,12 @@ pub struct Query(
     planets::PlanetQuery,
     members::MemberQuery,
     roles::RoleQuery,
+    permissions::PermissionQuery,
+    pages::PageQuery,
+    forums::ForumQuery,
+    chats::ChatQuery,
+    files::FileQuery,
+    wikis::WikiQuery,
+    events::EventQuery,
+    conversations::ConversationQuery,
 );
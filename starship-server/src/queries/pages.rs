@@ -0,0 +1,21 @@
+use crate::components::page::get_page;
+use crate::entities::page;
+use crate::sessions::Session;
+use async_graphql::{Context, Description, Error, Object, ID};
+use sea_orm::DatabaseConnection;
+
+#[derive(Default, Description)]
+pub struct PageQuery;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl PageQuery {
+    /// Finds the page of a page component from the component's ID.
+    #[graphql(complexity = 10)]
+    async fn page(&self, ctx: &Context<'_>, component_id: ID) -> Result<page::Model, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+        let session = ctx.data::<Session>().unwrap();
+        let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+        get_page(component_id.to_string(), user_id, "page.view", db).await
+    }
+}
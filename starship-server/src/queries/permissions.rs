@@ -0,0 +1,18 @@
+use crate::permissions::constants::{self, Permission, PermissionCategory};
+use async_graphql::{Description, Object};
+
+#[derive(Default, Description)]
+pub struct PermissionQuery;
+
+#[Object(rename_fields = "camelCase", rename_args = "camelCase")]
+impl PermissionQuery {
+    /// Lists every permission that can be granted or denied, optionally limited to a category.
+    #[graphql(complexity = 5)]
+    async fn permissions(&self, category: Option<PermissionCategory>) -> Vec<Permission> {
+        constants::PERMISSIONS
+            .iter()
+            .filter(|p| category.is_none_or(|category| p.category == category))
+            .copied()
+            .collect()
+    }
+}
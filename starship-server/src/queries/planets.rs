@@ -25,10 +25,35 @@ impl PlanetQuery {
         let user_id = session.user.as_ref().map(|user| user.id.clone());
         let member = util::get_planet_member(user_id, id.to_string(), db).await?;
         let roles = util::get_member_roles(member.clone(), db).await?;
-        util::check_permission("planet.view", &planet, member, roles)?;
+        util::check_permission("planet.view", &planet, member.clone(), roles)?;
+
+        // opening the planet counts as activity, so that members who only read aren't pruned
+        if let Some(member) = &member {
+            util::record_activity(member, db).await?;
+        }
+
         Ok(planet)
     }
 
+    /// Finds the question asked to users requesting to join a private planet. Planets that don't
+    /// accept join requests are not found.
+    #[graphql(complexity = 5)]
+    async fn join_question(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+    ) -> Result<Option<String>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap();
+
+        let planet = util::get_planet(planet_id.to_string(), db).await?;
+
+        if !planet.private || !planet.join_requests_enabled {
+            return Err(errors::create_not_found_error());
+        }
+
+        Ok(planet.join_question)
+    }
+
     /// Finds all the featured planets.
     #[graphql(complexity = 5)]
     async fn featured_planets(&self, ctx: &Context<'_>) -> Result<Vec<planet::Model>, Error> {
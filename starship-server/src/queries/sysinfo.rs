@@ -1,4 +1,6 @@
-use async_graphql::{Description, Object, SimpleObject};
+use crate::components::registry::ComponentRegistry;
+use crate::config::Config;
+use async_graphql::{Context, Description, Object, SimpleObject};
 
 #[derive(SimpleObject)]
 struct SysInfoPaths {
@@ -27,21 +29,26 @@ struct SysInfo {
     paths: SysInfoPaths,
 }
 
-impl Default for SysInfo {
-    fn default() -> Self {
-        // TODO: Fill this data out properly
+impl SysInfo {
+    fn from_config(config: &Config, supported_components: Vec<String>) -> Self {
+        let paths = config.paths();
+
+        let mut supported_features = vec!["users".to_string()];
+        supported_features.extend(config.features.enabled());
+
         Self {
-            server_name: "starship-server-rs".to_string(),
+            server_name: config.server.name.clone(),
             version: "2023.0-dev-milestone1".to_string(),
             feature_level: 1,
-            supported_features: vec!["users".to_string()],
-            supported_components: vec![],
+            supported_features,
+            supported_components,
             client_flags: vec![],
+            // paths that aren't configured are empty, since they aren't served by this server
             paths: SysInfoPaths {
-                emoji_url: String::new(),
-                pfp_url: String::new(),
-                banner_url: String::new(),
-                graphql_endpoint: String::new(),
+                emoji_url: paths.emoji_url.unwrap_or_default(),
+                pfp_url: paths.pfp_url.unwrap_or_default(),
+                banner_url: paths.banner_url.unwrap_or_default(),
+                graphql_endpoint: paths.graphql_endpoint,
             },
         }
     }
@@ -53,7 +60,10 @@ pub struct SysInfoQuery;
 #[Object(rename_fields = "camelCase", rename_args = "camelCase")]
 impl SysInfoQuery {
     /// Retrieves information about the server.
-    async fn sys_info(&self) -> SysInfo {
-        SysInfo::default()
+    async fn sys_info(&self, ctx: &Context<'_>) -> SysInfo {
+        let config = ctx.data::<Config>().unwrap();
+        let registry = ctx.data::<ComponentRegistry>().unwrap();
+
+        SysInfo::from_config(config, registry.names())
     }
 }
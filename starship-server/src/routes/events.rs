@@ -0,0 +1,105 @@
+use super::error_response;
+use crate::components::access::ComponentAccess;
+use crate::components::events::to_ics;
+use crate::config::Config;
+use crate::entities::{event, event_calendar, user};
+use crate::errors;
+use crate::sessions::Session;
+use actix_web::{web, HttpRequest, HttpResponse};
+use async_graphql::Error;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/events/{calendar_id}/calendar.ics").route(web::get().to(feed)));
+}
+
+#[derive(Deserialize)]
+struct FeedQuery {
+    /// The calendar token of the user viewing the feed. Calendar apps can't log in, so they
+    /// identify the user with this instead.
+    token: Option<String>,
+}
+
+/// Gets the ID of the user a calendar token belongs to. If no user has the token, an error ready
+/// for presentation to the client is returned.
+async fn get_token_user(token: String, db: &DatabaseConnection) -> Result<String, Error> {
+    user::Entity::find()
+        .filter(user::Column::CalendarToken.eq(token))
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "USER_RETRIEVAL_ERROR"))?
+        .map(|user| user.id)
+        .ok_or(errors::create_forbidden_error(
+            Some("That calendar token isn't valid."),
+            "INVALID_CALENDAR_TOKEN",
+        ))
+}
+
+/// Creates the iCalendar feed for a calendar, checking that the current user can view it. The
+/// current user is the owner of `token` if it is provided.
+async fn get_feed(
+    req: &HttpRequest,
+    calendar_id: String,
+    token: Option<String>,
+    config: &Config,
+    db: &DatabaseConnection,
+) -> Result<String, Error> {
+    let user_id = match token {
+        Some(token) => Some(get_token_user(token, db).await?),
+        None => {
+            let session = Session::make_session_from_request(req, config, db.clone()).await;
+            session.user.map(|user| user.id)
+        }
+    };
+
+    let calendar = event_calendar::Entity::find_by_id(calendar_id)
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "CALENDAR_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let access = ComponentAccess::load_by_data(calendar.id.clone(), "events", user_id, db).await?;
+    access.check_permission("events.view")?;
+
+    let events = calendar
+        .find_related(event::Entity)
+        .order_by_asc(event::Column::Start)
+        .order_by_asc(event::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_EVENTS_ERROR"))?;
+
+    let name = format!("{} - {}", access.planet.name, access.component.name);
+
+    Ok(to_ics(
+        &name,
+        &events,
+        chrono::offset::Utc::now().naive_utc(),
+    ))
+}
+
+/// Serves a calendar's events as an iCalendar feed. Calendar apps subscribe to the feed with the
+/// `token` query parameter set to a calendar token from the `resetCalendarToken` mutation.
+async fn feed(
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<FeedQuery>,
+    config: web::Data<Config>,
+    db: web::Data<DatabaseConnection>,
+) -> HttpResponse {
+    match get_feed(
+        &req,
+        path.into_inner(),
+        query.into_inner().token,
+        &config,
+        &db,
+    )
+    .await
+    {
+        Ok(feed) => HttpResponse::Ok()
+            .content_type("text/calendar; charset=utf-8")
+            .body(feed),
+        Err(error) => error_response(error),
+    }
+}
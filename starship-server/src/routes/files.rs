@@ -0,0 +1,373 @@
+use super::error_response;
+use crate::archive::{self, ArchiveEntry};
+use crate::components::access::ComponentAccess;
+use crate::components::files::{
+    archive_layout, delete_files, folder_with_descendants, get_folder, release_space,
+    reserve_space, storage_key, validate_name, MAX_ARCHIVE_SIZE, MAX_FILE_SIZE,
+};
+use crate::config::Config;
+use crate::entities::{file, file_folder};
+use crate::errors;
+use crate::sessions::Session;
+use crate::storage::Storage;
+use actix_web::http::header::{self, ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{web, HttpRequest, HttpResponse};
+use async_graphql::Error;
+use futures_util::{stream, StreamExt};
+use nanoid::nanoid;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+/// The largest upload request that will be read, in bytes. This leaves room for the multipart
+/// headers around a file of the largest size.
+const MAX_REQUEST_SIZE: usize = MAX_FILE_SIZE + 1_000_000;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/files/{component_id}/upload").route(web::post().to(upload)))
+        .service(web::resource("/files/download/{file_id}").route(web::get().to(download)))
+        .service(
+            web::resource("/files/folders/download/{folder_id}")
+                .route(web::get().to(download_folder)),
+        );
+}
+
+#[derive(Deserialize)]
+struct UploadQuery {
+    /// The folder to upload into. Files are uploaded to the top of the tree if it isn't provided.
+    folder: Option<String>,
+}
+
+/// Reads the whole body of a request, failing if it is larger than `MAX_REQUEST_SIZE`.
+async fn read_payload(mut payload: web::Payload) -> Result<web::Bytes, Error> {
+    let mut body = web::BytesMut::new();
+
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk
+            .map_err(|_| errors::create_user_input_error("Upload failed.", "UPLOAD_FAILED"))?;
+
+        if body.len() + chunk.len() > MAX_REQUEST_SIZE {
+            return Err(errors::create_user_input_error(
+                "Files cannot be larger than 100 MB.",
+                "FILE_TOO_LARGE",
+            ));
+        }
+
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body.freeze())
+}
+
+/// Stores every file in a multipart upload in a files component.
+async fn upload_files(
+    req: &HttpRequest,
+    component_id: String,
+    folder: Option<String>,
+    payload: web::Payload,
+    config: &Config,
+    db: &DatabaseConnection,
+    storage: &dyn Storage,
+) -> Result<Vec<file::Model>, Error> {
+    let session = Session::make_session_from_request(req, config, db.clone()).await;
+
+    let user = session.user.ok_or(errors::create_forbidden_error(
+        Some("Not logged in."),
+        "NOT_LOGGED_IN",
+    ))?;
+
+    if !session.verified {
+        return Err(errors::create_forbidden_error(None, "UNVERIFIED_TOKEN"));
+    }
+
+    let access = ComponentAccess::load(component_id, "files", Some(user.id.clone()), db).await?;
+    access.check_permission("files.view")?;
+    access.check_permission("files.files.upload")?;
+
+    let tree = access.component.component_id;
+
+    if let Some(folder) = &folder {
+        get_folder(&tree, folder, db).await?;
+    }
+
+    let content_type = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let boundary = multer::parse_boundary(content_type).map_err(|_| {
+        errors::create_user_input_error("Uploads must be multipart forms.", "INVALID_UPLOAD")
+    })?;
+
+    // the payload can't be sent between threads, so it is read before being parsed
+    let body = read_payload(payload).await?;
+    let mut multipart = multer::Multipart::new(
+        stream::once(async move { Ok::<_, Infallible>(body) }),
+        boundary,
+    );
+
+    let invalid_upload =
+        |_| errors::create_user_input_error("That upload isn't valid.", "INVALID_UPLOAD");
+
+    let mut files = vec![];
+
+    while let Some(field) = multipart.next_field().await.map_err(invalid_upload)? {
+        let Some(name) = field.file_name().map(validate_name).transpose()? else {
+            continue;
+        };
+
+        let mime_type = field.content_type().map_or_else(
+            || "application/octet-stream".to_string(),
+            ToString::to_string,
+        );
+
+        let data = field.bytes().await.map_err(invalid_upload)?;
+
+        if data.len() > MAX_FILE_SIZE {
+            return Err(errors::create_user_input_error(
+                "Files cannot be larger than 100 MB.",
+                "FILE_TOO_LARGE",
+            ));
+        }
+
+        let size = data.len() as i64;
+
+        // the space is taken up before the file is stored, so that concurrent uploads can't go
+        // over the quota together
+        reserve_space(&user.id, size, config.limits.user_storage_quota, db).await?;
+
+        let file = file::ActiveModel {
+            id: ActiveValue::Set(nanoid!(16)),
+            tree: ActiveValue::Set(tree.clone()),
+            folder: ActiveValue::Set(folder.clone()),
+            owner: ActiveValue::Set(user.id.clone()),
+            name: ActiveValue::Set(name),
+            size: ActiveValue::Set(size),
+            mime_type: ActiveValue::Set(mime_type),
+            created: ActiveValue::Set(chrono::offset::Utc::now().naive_utc()),
+        };
+
+        let file = match file.insert(db).await {
+            Ok(file) => file,
+            Err(_) => {
+                release_space(&user.id, size, db).await?;
+                return Err(errors::create_internal_server_error(
+                    None,
+                    "INSERTION_ERROR",
+                ));
+            }
+        };
+
+        if storage
+            .put(&storage_key(&file), data.to_vec())
+            .await
+            .is_err()
+        {
+            // this also gives the space back to the user
+            delete_files(&[file], db).await?;
+
+            return Err(errors::create_internal_server_error(None, "STORAGE_ERROR"));
+        }
+
+        files.push(file);
+    }
+
+    Ok(files)
+}
+
+/// Uploads files to a files component. The body is a multipart form, where every field with a
+/// file name is stored as a file. Responds with the uploaded files.
+async fn upload(
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<UploadQuery>,
+    payload: web::Payload,
+    config: web::Data<Config>,
+    db: web::Data<DatabaseConnection>,
+    storage: web::Data<Arc<dyn Storage>>,
+) -> HttpResponse {
+    let result = upload_files(
+        &req,
+        path.into_inner(),
+        query.into_inner().folder,
+        payload,
+        &config,
+        &db,
+        storage.as_ref().as_ref(),
+    )
+    .await;
+
+    match result {
+        Ok(files) => HttpResponse::Ok().json(json!({
+            "files": files
+                .iter()
+                .map(|file| json!({
+                    "id": file.id,
+                    "name": file.name,
+                    "size": file.size,
+                    "mimeType": file.mime_type,
+                }))
+                .collect::<Vec<_>>(),
+        })),
+        Err(error) => error_response(error),
+    }
+}
+
+/// Gets a file and its contents, checking that the current user can download it.
+async fn get_file_contents(
+    req: &HttpRequest,
+    file_id: String,
+    config: &Config,
+    db: &DatabaseConnection,
+    storage: &dyn Storage,
+) -> Result<(file::Model, Vec<u8>), Error> {
+    let session = Session::make_session_from_request(req, config, db.clone()).await;
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+    let file = file::Entity::find_by_id(file_id)
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FILE_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let access = ComponentAccess::load_by_data(file.tree.clone(), "files", user_id, db).await?;
+    access.check_permission("files.view")?;
+    access.check_permission("files.files.download")?;
+
+    let contents = storage
+        .get(&storage_key(&file))
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "STORAGE_ERROR"))?;
+
+    Ok((file, contents))
+}
+
+/// Downloads the contents of a file.
+async fn download(
+    req: HttpRequest,
+    path: web::Path<String>,
+    config: web::Data<Config>,
+    db: web::Data<DatabaseConnection>,
+    storage: web::Data<Arc<dyn Storage>>,
+) -> HttpResponse {
+    let result = get_file_contents(
+        &req,
+        path.into_inner(),
+        &config,
+        &db,
+        storage.as_ref().as_ref(),
+    )
+    .await;
+
+    match result {
+        Ok((file, contents)) => HttpResponse::Ok()
+            .content_type(file.mime_type)
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(file.name)],
+            })
+            .body(contents),
+        Err(error) => error_response(error),
+    }
+}
+
+/// Gets a folder and builds an archive of everything inside of it, checking that the current user
+/// can download it.
+async fn get_folder_archive(
+    req: &HttpRequest,
+    folder_id: String,
+    config: &Config,
+    db: &DatabaseConnection,
+    storage: &dyn Storage,
+) -> Result<(file_folder::Model, Vec<u8>), Error> {
+    let session = Session::make_session_from_request(req, config, db.clone()).await;
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+    let folder = file_folder::Entity::find_by_id(folder_id)
+        .one(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FOLDER_RETRIEVAL_ERROR"))?
+        .ok_or(errors::create_not_found_error())?;
+
+    let access = ComponentAccess::load_by_data(folder.tree.clone(), "files", user_id, db).await?;
+    access.check_permission("files.view")?;
+    access.check_permission("files.files.download")?;
+    access.check_permission("files.folders.download")?;
+
+    let folder_ids = folder_with_descendants(&folder, db).await?;
+
+    let folders = file_folder::Entity::find()
+        .filter(file_folder::Column::Id.is_in(folder_ids.clone()))
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_FOLDERS_ERROR"))?;
+
+    let files = file::Entity::find()
+        .filter(file::Column::Folder.is_in(folder_ids))
+        .all(db)
+        .await
+        .map_err(|_| errors::create_internal_server_error(None, "FIND_FILES_ERROR"))?;
+
+    if files.iter().map(|file| file.size).sum::<i64>() > MAX_ARCHIVE_SIZE
+        || folders.len() + files.len() > archive::MAX_ENTRIES
+    {
+        return Err(errors::create_user_input_error(
+            "That folder is too large to download at once.",
+            "FOLDER_TOO_LARGE",
+        ));
+    }
+
+    let mut entries = vec![];
+
+    for entry in archive_layout(&folder, &folders, &files) {
+        let contents = match entry.file {
+            Some(file) => storage
+                .get(&storage_key(file))
+                .await
+                .map_err(|_| errors::create_internal_server_error(None, "STORAGE_ERROR"))?,
+            None => vec![],
+        };
+
+        entries.push(ArchiveEntry {
+            path: entry.path,
+            contents,
+            modified: entry.modified,
+        });
+    }
+
+    Ok((folder, archive::zip(&entries)))
+}
+
+/// Downloads a folder, along with every folder and file inside of it, as a ZIP archive.
+async fn download_folder(
+    req: HttpRequest,
+    path: web::Path<String>,
+    config: web::Data<Config>,
+    db: web::Data<DatabaseConnection>,
+    storage: web::Data<Arc<dyn Storage>>,
+) -> HttpResponse {
+    let result = get_folder_archive(
+        &req,
+        path.into_inner(),
+        &config,
+        &db,
+        storage.as_ref().as_ref(),
+    )
+    .await;
+
+    match result {
+        Ok((folder, archive)) => HttpResponse::Ok()
+            .content_type("application/zip")
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(format!("{}.zip", folder.name))],
+            })
+            .body(archive),
+        Err(error) => error_response(error),
+    }
+}
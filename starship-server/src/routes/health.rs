@@ -0,0 +1,63 @@
+use crate::config::Config;
+use crate::metrics::{Gauges, Metrics};
+use crate::presence::Presence;
+use actix_web::{web, HttpResponse};
+use log::warn;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+use sqlx::PgPool;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/healthz").route(web::get().to(healthz)))
+        .service(web::resource("/readyz").route(web::get().to(readyz)))
+        .service(web::resource("/metrics").route(web::get().to(metrics)));
+}
+
+/// Reports that the server is running. This doesn't check anything else, so that an orchestrator
+/// doesn't restart the server just because the database is down.
+async fn healthz() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body("ok")
+}
+
+/// Reports whether or not the server can handle requests, by checking that the database responds.
+async fn readyz(db: web::Data<DatabaseConnection>) -> HttpResponse {
+    let ping = Statement::from_string(DatabaseBackend::Postgres, "SELECT 1".to_string());
+
+    match db.execute(ping).await {
+        Ok(_) => HttpResponse::Ok()
+            .content_type("text/plain; charset=utf-8")
+            .body("ok"),
+        Err(err) => {
+            warn!("readiness check failed: {err}");
+
+            HttpResponse::ServiceUnavailable()
+                .content_type("text/plain; charset=utf-8")
+                .body("database unavailable")
+        }
+    }
+}
+
+/// Serves metrics in the Prometheus text format, unless they have been turned off.
+async fn metrics(
+    config: web::Data<Config>,
+    metrics: web::Data<Metrics>,
+    pool: web::Data<PgPool>,
+    presence: web::Data<Presence>,
+) -> HttpResponse {
+    if !config.features.metrics {
+        return HttpResponse::NotFound().finish();
+    }
+
+    let gauges = Gauges {
+        pool_connections: pool.size(),
+        pool_idle: pool.num_idle(),
+        pool_max: config.database.max_connections,
+        active_sessions: presence.connections(),
+        online_users: presence.online_users(),
+    };
+
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4; charset=utf-8")
+        .body(metrics.render(&gauges))
+}
@@ -0,0 +1,39 @@
+//! HTTP routes served alongside the GraphQL API, for requests that don't fit into GraphQL such as
+//! file uploads, calendar feeds and health checks.
+
+mod events;
+mod files;
+mod health;
+
+use actix_web::{web, HttpResponse};
+use async_graphql::{Error, Value};
+use serde_json::json;
+
+/// Adds every route to an app.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    events::configure(cfg);
+    files::configure(cfg);
+    health::configure(cfg);
+}
+
+/// Converts an error into a response with a status code matching its type, and a body in the same
+/// shape as a GraphQL error.
+fn error_response(error: Error) -> HttpResponse {
+    let error_type = error
+        .extensions
+        .as_ref()
+        .and_then(|extensions| extensions.get("type"));
+
+    let mut response = match error_type {
+        Some(Value::String(error_type)) if error_type == "INVALID_USER_INPUT" => {
+            HttpResponse::BadRequest()
+        }
+        Some(Value::String(error_type)) if error_type == "NOT_FOUND" => HttpResponse::NotFound(),
+        Some(Value::String(error_type)) if error_type == "FORBIDDEN" => HttpResponse::Forbidden(),
+        _ => HttpResponse::InternalServerError(),
+    };
+
+    response.json(json!({
+        "errors": [{ "message": error.message, "extensions": error.extensions }],
+    }))
+}
@@ -1,7 +1,9 @@
+use crate::config::Config;
 use crate::entities::prelude::Token;
 use crate::entities::prelude::User;
 use crate::entities::token;
 use crate::entities::user;
+use crate::permissions::checks;
 use actix_web::http::header;
 use actix_web::HttpRequest;
 use hmac::{Hmac, Mac};
@@ -9,7 +11,6 @@ use jwt::VerifyWithKey;
 use sea_orm::{DatabaseConnection, EntityTrait};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
-use std::env;
 use std::net::SocketAddr;
 
 pub struct Session {
@@ -29,16 +30,51 @@ pub struct JWTLoginToken {
 impl Session {
     pub async fn make_session_from_request(
         request: &HttpRequest,
+        config: &Config,
         db: DatabaseConnection,
     ) -> Session {
         let headers = request.headers();
 
-        let data = if let Some(auth) = headers.get(header::AUTHORIZATION) {
-            let auth_string = auth.to_str().unwrap_or("");
+        let authorization = headers
+            .get(header::AUTHORIZATION)
+            .map(|auth| auth.to_str().unwrap_or(""));
 
+        let user_agent = headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().map(std::string::ToString::to_string).ok());
+
+        Self::make_session(authorization, user_agent, request.peer_addr(), config, db).await
+    }
+
+    /// Creates a session for a WebSocket connection from the payload of its `connection_init`
+    /// message. Clients authenticate by sending an `Authorization` value in the same format as the
+    /// HTTP header. The user agent and address come from the request that opened the connection.
+    pub async fn make_session_from_payload(
+        payload: &serde_json::Value,
+        user_agent: Option<String>,
+        ip_address: Option<SocketAddr>,
+        config: &Config,
+        db: DatabaseConnection,
+    ) -> Session {
+        let authorization = payload
+            .get("Authorization")
+            .or_else(|| payload.get("authorization"))
+            .and_then(serde_json::Value::as_str);
+
+        Self::make_session(authorization, user_agent, ip_address, config, db).await
+    }
+
+    async fn make_session(
+        authorization: Option<&str>,
+        user_agent: Option<String>,
+        ip_address: Option<SocketAddr>,
+        config: &Config,
+        db: DatabaseConnection,
+    ) -> Session {
+        let data = if let Some(auth_string) = authorization {
             if auth_string.starts_with("Bearer ") {
-                let secret = env::var("SECRET").unwrap();
-                let key: Hmac<Sha256> = Hmac::new_from_slice(secret.as_bytes()).unwrap();
+                let key: Hmac<Sha256> =
+                    Hmac::new_from_slice(config.auth.secret.as_bytes()).unwrap();
                 let jwt_token_data: JWTLoginToken = auth_string
                     .replace("Bearer ", "")
                     .verify_with_key(&key)
@@ -74,16 +110,26 @@ impl Session {
             (None, None, false)
         };
 
-        let user_agent = headers
-            .get(header::USER_AGENT)
-            .and_then(|v| v.to_str().map(std::string::ToString::to_string).ok());
+        // expired bans are lifted by the bans job, so until it next runs the user is treated as
+        // unbanned without writing anything while the session is built
+        let now = chrono::offset::Utc::now().naive_utc();
+        let user = data.1.map(|mut user| {
+            if checks::ban_expired(user.banned, user.ban_expires, now) {
+                user.banned = false;
+                user.ban_reason = None;
+                user.ban_expires = None;
+                user.banned_by = None;
+            }
+
+            user
+        });
 
         Session {
             token: data.0,
-            user: data.1,
+            user,
             verified: data.2,
             user_agent,
-            ip_address: request.peer_addr(),
+            ip_address,
         }
     }
 }
@@ -0,0 +1,53 @@
+use super::Storage;
+use async_trait::async_trait;
+use std::io;
+use std::path::PathBuf;
+use tokio::fs;
+
+/// Stores objects as files in a directory on the local filesystem.
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalStorage { root: root.into() }
+    }
+
+    /// Gets the path an object is stored at. Keys are generated by the server, but any key that
+    /// could escape the storage directory is rejected anyway.
+    fn path(&self, key: &str) -> io::Result<PathBuf> {
+        if key.is_empty() || key.split('/').any(|part| part.is_empty() || part == "..") {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid storage key",
+            ));
+        }
+
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, data: Vec<u8>) -> io::Result<()> {
+        let path = self.path(key)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        fs::write(path, data).await
+    }
+
+    async fn get(&self, key: &str) -> io::Result<Vec<u8>> {
+        fs::read(self.path(key)?).await
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
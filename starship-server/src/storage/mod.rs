@@ -0,0 +1,30 @@
+//! Storage for the contents of uploaded files. The database only records where files are stored,
+//! so the backend can be swapped for another, such as an S3-compatible object store, by
+//! implementing [`Storage`].
+
+mod local;
+
+pub use local::LocalStorage;
+
+use crate::config::StorageConfig;
+use async_trait::async_trait;
+use std::io;
+use std::sync::Arc;
+
+/// A place where the contents of files are stored, keyed by a path-like string.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// Stores an object, replacing any object with the same key.
+    async fn put(&self, key: &str, data: Vec<u8>) -> io::Result<()>;
+
+    /// Retrieves the contents of an object.
+    async fn get(&self, key: &str) -> io::Result<Vec<u8>>;
+
+    /// Deletes an object. Deleting an object that doesn't exist isn't an error.
+    async fn delete(&self, key: &str) -> io::Result<()>;
+}
+
+/// Creates the configured storage backend.
+pub fn from_config(config: &StorageConfig) -> Arc<dyn Storage> {
+    Arc::new(LocalStorage::new(config.path.clone()))
+}
@@ -0,0 +1,95 @@
+use async_graphql::Enum;
+use futures_util::{stream, Stream, StreamExt};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// The number of events kept for subscribers that fall behind. Subscribers that fall further
+/// behind than this miss the oldest events.
+const CAPACITY: usize = 1024;
+
+/// How something in a planet changed.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// A change to a planet that subscribers can be told about. Events only carry IDs, so that each
+/// subscriber retrieves the current state of whatever changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlanetEvent {
+    /// The planet's details, such as its name or home component, changed.
+    PlanetUpdated {
+        planet: String,
+    },
+    MemberJoined {
+        planet: String,
+        member: String,
+    },
+    MemberLeft {
+        planet: String,
+        member: String,
+        user: String,
+    },
+    RoleChanged {
+        planet: String,
+        role: String,
+        change: ChangeKind,
+    },
+    ComponentChanged {
+        planet: String,
+        component: String,
+        change: ChangeKind,
+    },
+}
+
+impl PlanetEvent {
+    /// The ID of the planet the event happened in.
+    pub fn planet(&self) -> &str {
+        match self {
+            PlanetEvent::PlanetUpdated { planet }
+            | PlanetEvent::MemberJoined { planet, .. }
+            | PlanetEvent::MemberLeft { planet, .. }
+            | PlanetEvent::RoleChanged { planet, .. }
+            | PlanetEvent::ComponentChanged { planet, .. } => planet,
+        }
+    }
+}
+
+/// Passes planet events from the mutations that cause them to subscribers.
+#[derive(Clone)]
+pub struct Broker {
+    sender: broadcast::Sender<PlanetEvent>,
+}
+
+impl Default for Broker {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(CAPACITY);
+
+        Broker { sender }
+    }
+}
+
+impl Broker {
+    /// Sends an event to everyone subscribed to its planet.
+    pub fn publish(&self, event: PlanetEvent) {
+        // sending only fails when nobody is subscribed, in which case the event can be dropped
+        let _ = self.sender.send(event);
+    }
+
+    /// Listens for the events that happen in a planet.
+    pub fn subscribe(&self, planet: String) -> impl Stream<Item = PlanetEvent> {
+        let receiver = self.sender.subscribe();
+
+        stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .filter(move |event| std::future::ready(event.planet() == planet))
+    }
+}
@@ -0,0 +1,102 @@
+use super::{check_subscriber, subscriber_has_permission, Broker, ChangeKind, PlanetEvent};
+use crate::entities::planet_component;
+use crate::permissions::constants;
+use async_graphql::{Context, Error, SimpleObject, Subscription, ID};
+use futures_util::{Stream, StreamExt};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+/// A component that was created, updated or deleted.
+#[derive(SimpleObject)]
+pub struct ComponentChange {
+    pub change: ChangeKind,
+    pub component_id: ID,
+    /// The component as it is now, if it still exists.
+    pub component: Option<planet_component::Model>,
+}
+
+/// Gets the permission needed to view a component, such as `forum.view`, if its type has one.
+fn view_permission(component: &planet_component::Model) -> Option<String> {
+    let permission = format!("{}.view", component.r#type);
+
+    constants::PERMISSIONS
+        .iter()
+        .any(|known| known.name == permission)
+        .then_some(permission)
+}
+
+#[derive(Default)]
+pub struct ComponentSubscription;
+
+#[Subscription(rename_fields = "camelCase", rename_args = "camelCase")]
+impl ComponentSubscription {
+    /// Sends the changes made to a planet's components, such as new, renamed or moved components.
+    /// Requires the `planet.view` permission, and changes to components are only sent if the
+    /// component can be viewed, such as with `forum.view` for forums.
+    async fn component_changed(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+    ) -> Result<impl Stream<Item = ComponentChange>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap().clone();
+        let broker = ctx.data::<Broker>().unwrap();
+
+        let user_id = check_subscriber(ctx, "planet.view", planet_id.to_string()).await?;
+
+        Ok(broker
+            .subscribe(planet_id.to_string())
+            .filter_map(move |event| {
+                let db = db.clone();
+                let user_id = user_id.clone();
+
+                async move {
+                    let PlanetEvent::ComponentChanged {
+                        planet,
+                        component,
+                        change,
+                    } = event
+                    else {
+                        return None;
+                    };
+
+                    if !subscriber_has_permission(
+                        "planet.view",
+                        planet.clone(),
+                        None,
+                        user_id.clone(),
+                        &db,
+                    )
+                    .await
+                    {
+                        return None;
+                    }
+
+                    let model = planet_component::Entity::find_by_id(component.clone())
+                        .one(&db)
+                        .await
+                        .ok()?;
+
+                    // components that still exist are only sent to subscribers that can view them,
+                    // which their overrides can prevent
+                    if let Some(permission) = model.as_ref().and_then(view_permission) {
+                        if !subscriber_has_permission(
+                            &permission,
+                            planet,
+                            Some(component.clone()),
+                            user_id,
+                            &db,
+                        )
+                        .await
+                        {
+                            return None;
+                        }
+                    }
+
+                    Some(ComponentChange {
+                        change,
+                        component_id: ID(component),
+                        component: model,
+                    })
+                }
+            }))
+    }
+}
@@ -0,0 +1,93 @@
+use super::{check_subscriber, subscriber_has_permission, Broker, PlanetEvent};
+use crate::entities::planet_member;
+use async_graphql::{Context, Error, SimpleObject, Subscription, ID};
+use futures_util::{Stream, StreamExt};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+/// A member that left a planet, whether they left themselves or were removed.
+#[derive(SimpleObject)]
+pub struct MemberLeft {
+    pub member_id: ID,
+    pub user_id: ID,
+}
+
+#[derive(Default)]
+pub struct MemberSubscription;
+
+#[Subscription(rename_fields = "camelCase", rename_args = "camelCase")]
+impl MemberSubscription {
+    /// Sends the members that join a planet. Requires the `planet.member.view` permission.
+    async fn member_joined(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+    ) -> Result<impl Stream<Item = planet_member::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap().clone();
+        let broker = ctx.data::<Broker>().unwrap();
+
+        let user_id = check_subscriber(ctx, "planet.member.view", planet_id.to_string()).await?;
+
+        Ok(broker
+            .subscribe(planet_id.to_string())
+            .filter_map(move |event| {
+                let db = db.clone();
+                let user_id = user_id.clone();
+
+                async move {
+                    let PlanetEvent::MemberJoined { planet, member } = event else {
+                        return None;
+                    };
+
+                    if !subscriber_has_permission("planet.member.view", planet, None, user_id, &db)
+                        .await
+                    {
+                        return None;
+                    }
+
+                    planet_member::Entity::find_by_id(member)
+                        .one(&db)
+                        .await
+                        .ok()
+                        .flatten()
+                }
+            }))
+    }
+
+    /// Sends the members that leave a planet, are kicked from it, or are pruned. Requires the
+    /// `planet.member.view` permission.
+    async fn member_left(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+    ) -> Result<impl Stream<Item = MemberLeft>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap().clone();
+        let broker = ctx.data::<Broker>().unwrap();
+
+        let user_id = check_subscriber(ctx, "planet.member.view", planet_id.to_string()).await?;
+
+        Ok(broker
+            .subscribe(planet_id.to_string())
+            .filter_map(move |event| {
+                let db = db.clone();
+                let user_id = user_id.clone();
+
+                async move {
+                    let PlanetEvent::MemberLeft {
+                        planet,
+                        member,
+                        user,
+                    } = event
+                    else {
+                        return None;
+                    };
+
+                    subscriber_has_permission("planet.member.view", planet, None, user_id, &db)
+                        .await
+                        .then_some(MemberLeft {
+                            member_id: ID(member),
+                            user_id: ID(user),
+                        })
+                }
+            }))
+    }
+}
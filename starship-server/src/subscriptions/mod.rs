@@ -0,0 +1,72 @@
+mod broker;
+mod components;
+mod members;
+mod planets;
+mod roles;
+
+pub use broker::{Broker, ChangeKind, PlanetEvent};
+
+use crate::permissions::{checks, util};
+use crate::sessions::Session;
+use async_graphql::{Context, Error, MergedSubscription};
+use sea_orm::DatabaseConnection;
+
+#[derive(MergedSubscription, Default)]
+pub struct Subscription(
+    planets::PlanetSubscription,
+    members::MemberSubscription,
+    roles::RoleSubscription,
+    components::ComponentSubscription,
+);
+
+/// Gets the ID of the user a subscription is for, checking that they hold a permission in the
+/// planet they are subscribing to.
+async fn check_subscriber(
+    ctx: &Context<'_>,
+    permission: &str,
+    planet_id: String,
+) -> Result<Option<String>, Error> {
+    let db = ctx.data::<DatabaseConnection>().unwrap();
+    let session = ctx.data::<Session>().unwrap();
+    let user_id = session.user.as_ref().map(|user| user.id.clone());
+
+    let planet = util::get_planet(planet_id.clone(), db).await?;
+    let member = util::get_planet_member(user_id.clone(), planet_id, db).await?;
+    let roles = util::get_member_roles(member.clone(), db).await?;
+    util::check_permission(permission, &planet, member, roles)?;
+
+    Ok(user_id)
+}
+
+/// Checks whether or not a subscriber still holds a permission in a planet when an event is sent
+/// to them, since their permissions may have changed since they subscribed. If `component_id` is
+/// provided, the permission is checked within that component, taking its overrides into account.
+async fn subscriber_has_permission(
+    permission: &str,
+    planet_id: String,
+    component_id: Option<String>,
+    user_id: Option<String>,
+    db: &DatabaseConnection,
+) -> bool {
+    let Ok(planet) = util::get_planet(planet_id.clone(), db).await else {
+        return false;
+    };
+
+    let Ok(member) = util::get_planet_member(user_id, planet_id, db).await else {
+        return false;
+    };
+
+    let Ok(roles) = util::get_member_roles(member.clone(), db).await else {
+        return false;
+    };
+
+    let overrides = match component_id {
+        Some(component_id) => match util::get_component_overrides(component_id, db).await {
+            Ok(overrides) => Some(overrides),
+            Err(_) => return false,
+        },
+        None => None,
+    };
+
+    checks::has_permission(permission, &planet, member, roles, overrides.as_deref())
+}
@@ -0,0 +1,45 @@
+use super::{check_subscriber, subscriber_has_permission, Broker, PlanetEvent};
+use crate::entities::planet;
+use crate::permissions::util;
+use async_graphql::{Context, Error, Subscription, ID};
+use futures_util::{Stream, StreamExt};
+use sea_orm::DatabaseConnection;
+
+#[derive(Default)]
+pub struct PlanetSubscription;
+
+#[Subscription(rename_fields = "camelCase", rename_args = "camelCase")]
+impl PlanetSubscription {
+    /// Sends a planet whenever its details change. Requires the `planet.view` permission.
+    async fn planet_updated(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+    ) -> Result<impl Stream<Item = planet::Model>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap().clone();
+        let broker = ctx.data::<Broker>().unwrap();
+
+        let user_id = check_subscriber(ctx, "planet.view", planet_id.to_string()).await?;
+
+        Ok(broker
+            .subscribe(planet_id.to_string())
+            .filter_map(move |event| {
+                let db = db.clone();
+                let user_id = user_id.clone();
+
+                async move {
+                    let PlanetEvent::PlanetUpdated { planet } = event else {
+                        return None;
+                    };
+
+                    if !subscriber_has_permission("planet.view", planet.clone(), None, user_id, &db)
+                        .await
+                    {
+                        return None;
+                    }
+
+                    util::get_planet(planet, &db).await.ok()
+                }
+            }))
+    }
+}
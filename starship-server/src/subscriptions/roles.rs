@@ -0,0 +1,66 @@
+use super::{check_subscriber, subscriber_has_permission, Broker, ChangeKind, PlanetEvent};
+use crate::entities::planet_role;
+use async_graphql::{Context, Error, SimpleObject, Subscription, ID};
+use futures_util::{Stream, StreamExt};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+/// A role that was created, updated or deleted.
+#[derive(SimpleObject)]
+pub struct RoleChange {
+    pub change: ChangeKind,
+    pub role_id: ID,
+    /// The role as it is now, if it still exists.
+    pub role: Option<planet_role::Model>,
+}
+
+#[derive(Default)]
+pub struct RoleSubscription;
+
+#[Subscription(rename_fields = "camelCase", rename_args = "camelCase")]
+impl RoleSubscription {
+    /// Sends the changes made to a planet's roles, including their permissions and order. Requires
+    /// the `planet.view` permission.
+    async fn role_changed(
+        &self,
+        ctx: &Context<'_>,
+        planet_id: ID,
+    ) -> Result<impl Stream<Item = RoleChange>, Error> {
+        let db = ctx.data::<DatabaseConnection>().unwrap().clone();
+        let broker = ctx.data::<Broker>().unwrap();
+
+        let user_id = check_subscriber(ctx, "planet.view", planet_id.to_string()).await?;
+
+        Ok(broker
+            .subscribe(planet_id.to_string())
+            .filter_map(move |event| {
+                let db = db.clone();
+                let user_id = user_id.clone();
+
+                async move {
+                    let PlanetEvent::RoleChanged {
+                        planet,
+                        role,
+                        change,
+                    } = event
+                    else {
+                        return None;
+                    };
+
+                    if !subscriber_has_permission("planet.view", planet, None, user_id, &db).await {
+                        return None;
+                    }
+
+                    let model = planet_role::Entity::find_by_id(role.clone())
+                        .one(&db)
+                        .await
+                        .ok()?;
+
+                    Some(RoleChange {
+                        change,
+                        role_id: ID(role),
+                        role: model,
+                    })
+                }
+            }))
+    }
+}
@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::archive::{dos_time, zip, ArchiveEntry};
+use chrono::NaiveDate;
+
+fn entry(path: &str, contents: &[u8]) -> ArchiveEntry {
+    ArchiveEntry {
+        path: path.to_string(),
+        contents: contents.to_vec(),
+        modified: NaiveDate::from_ymd_opt(2023, 4, 5)
+            .unwrap()
+            .and_hms_opt(6, 7, 8)
+            .unwrap(),
+    }
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn stores_entries() {
+    let archive = zip(&[entry("Folder/", b""), entry("Folder/a.txt", b"hello")]);
+
+    assert_eq!(
+        u32_at(&archive, 0),
+        0x0403_4b50,
+        "archive doesn't start with a local header"
+    );
+    assert_eq!(u16_at(&archive, 8), 0, "entries compressed");
+    assert_eq!(&archive[30..37], b"Folder/", "first entry name not written");
+
+    let end = archive.len() - 22;
+
+    assert_eq!(
+        u32_at(&archive, end),
+        0x0605_4b50,
+        "archive doesn't end with an end of central directory record"
+    );
+    assert_eq!(u16_at(&archive, end + 10), 2, "wrong number of entries");
+
+    let central_directory = u32_at(&archive, end + 16) as usize;
+
+    assert_eq!(
+        u32_at(&archive, central_directory),
+        0x0201_4b50,
+        "central directory offset is wrong"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn checksums_contents() {
+    let archive = zip(&[entry("a.txt", b"hello")]);
+
+    assert_eq!(
+        u32_at(&archive, 14),
+        0x3610_a686,
+        "wrong checksum for the contents"
+    );
+    assert_eq!(u32_at(&archive, 18), 5, "wrong size for the contents");
+    assert_eq!(&archive[35..40], b"hello", "contents not stored");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn converts_dos_time() {
+    let (time, date) = dos_time(entry("a.txt", b"").modified);
+
+    assert_eq!(time, (6 << 11) | (7 << 5) | 4, "wrong time");
+    assert_eq!(date, (43 << 9) | (4 << 5) | 5, "wrong date");
+
+    let (time, date) = dos_time(
+        NaiveDate::from_ymd_opt(1970, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap(),
+    );
+
+    assert_eq!(
+        (time, date),
+        (0, (1 << 5) | 1),
+        "times before 1980 not clamped"
+    );
+}
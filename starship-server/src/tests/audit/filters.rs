@@ -0,0 +1,84 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::audit::filter_entries;
+use crate::entities::audit_log::{self, AuditAction};
+use sea_orm::{DbBackend, EntityTrait, QueryTrait};
+
+fn sql(action: Option<AuditAction>, actor: Option<&str>, target: Option<&str>) -> String {
+    filter_entries(
+        audit_log::Entity::find(),
+        action,
+        actor.map(ToString::to_string),
+        target.map(ToString::to_string),
+    )
+    .build(DbBackend::Postgres)
+    .to_string()
+}
+
+/// Gets the `WHERE` clause of a query, which is everything between `WHERE` and `ORDER BY`.
+fn conditions(sql: &str) -> &str {
+    let start = sql.find(" WHERE ").unwrap_or(sql.len());
+    let end = sql.find(" ORDER BY ").unwrap_or(sql.len());
+
+    &sql[start..end]
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn no_filters() {
+    let sql = sql(None, None, None);
+
+    assert!(
+        !sql.contains("WHERE"),
+        "unfiltered logs shouldn't be narrowed down"
+    );
+    assert!(
+        sql.ends_with(r#"ORDER BY "audit_log"."created" DESC"#),
+        "entries should be returned newest first"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn action_filter() {
+    let sql = sql(Some(AuditAction::MemberKick), None, None);
+    let conditions = conditions(&sql);
+
+    assert!(
+        conditions.contains(r#""audit_log"."action" = 'member_kick'"#),
+        "entries should be filtered by their action"
+    );
+    assert!(
+        !conditions.contains(r#""audit_log"."actor""#),
+        "actor shouldn't be filtered"
+    );
+    assert!(
+        !conditions.contains(r#""audit_log"."target""#),
+        "target shouldn't be filtered"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn combined_filters() {
+    let sql = sql(Some(AuditAction::RoleDelete), Some("actor"), Some("target"));
+    let conditions = conditions(&sql);
+
+    assert!(
+        conditions.contains(r#""audit_log"."action" = 'role_delete'"#),
+        "entries should be filtered by their action"
+    );
+    assert!(
+        conditions.contains(r#""audit_log"."actor" = 'actor'"#),
+        "entries should be filtered by their actor"
+    );
+    assert!(
+        conditions.contains(r#""audit_log"."target" = 'target'"#),
+        "entries should be filtered by their target"
+    );
+    assert!(
+        conditions.contains(" AND "),
+        "every filter should have to match, not just one"
+    );
+}
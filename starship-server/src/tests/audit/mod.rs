@@ -0,0 +1 @@
+mod filters;
@@ -0,0 +1,261 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use super::forum::create_access;
+use crate::components::chat::{check_can_send, search_query};
+use crate::entities::chat_message;
+use crate::entities::graphql::chat::{messages_page, messages_query};
+use crate::entities::graphql::cursor::TimestampCursor;
+use async_graphql::connection::CursorType;
+use chrono::NaiveDateTime;
+use sea_orm::{DbBackend, QueryTrait};
+
+fn time(second: u32) -> NaiveDateTime {
+    chrono::NaiveDate::from_ymd_opt(2026, 10, 19)
+        .unwrap()
+        .and_hms_opt(12, 0, second)
+        .unwrap()
+}
+
+fn message(id: &str, second: u32) -> chat_message::Model {
+    chat_message::Model {
+        id: id.to_string(),
+        created: time(second),
+        // all other fields are unimportant for this test
+        chat: "chat".to_string(),
+        author: "irrelevant".to_string(),
+        content: "irrelevant".to_string(),
+        attachments: vec![],
+        pinned: false,
+        edited: None,
+    }
+}
+
+fn cursor(id: &str, second: u32) -> TimestampCursor {
+    TimestampCursor {
+        created: time(second),
+        id: id.to_string(),
+    }
+}
+
+fn ids(
+    connection: &async_graphql::connection::Connection<TimestampCursor, chat_message::Model>,
+) -> Vec<String> {
+    connection
+        .edges
+        .iter()
+        .map(|edge| edge.node.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn first_page() {
+    let sql = messages_query("chat", None, None, 2, false)
+        .build(DbBackend::Postgres)
+        .to_string();
+
+    assert!(
+        sql.ends_with(
+            r#"WHERE "chat_message"."chat" = 'chat' ORDER BY "chat_message"."created" ASC, "chat_message"."id" ASC LIMIT 3"#
+        ),
+        "first page not retrieved oldest first with an extra message: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn after_cursor() {
+    let sql = messages_query("chat", Some(cursor("b", 5)), None, 2, false)
+        .build(DbBackend::Postgres)
+        .to_string();
+
+    assert!(
+        sql.contains(
+            r#"("chat_message"."created" > '2026-10-19 12:00:05' OR ("chat_message"."created" = '2026-10-19 12:00:05' AND "chat_message"."id" > 'b'))"#
+        ),
+        "messages at or before the cursor included: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn before_cursor_backwards() {
+    let sql = messages_query("chat", None, Some(cursor("b", 5)), 2, true)
+        .build(DbBackend::Postgres)
+        .to_string();
+
+    assert!(
+        sql.contains(
+            r#"("chat_message"."created" < '2026-10-19 12:00:05' OR ("chat_message"."created" = '2026-10-19 12:00:05' AND "chat_message"."id" < 'b'))"#
+        ),
+        "messages at or after the cursor included: {sql}"
+    );
+    assert!(
+        sql.ends_with(
+            r#"ORDER BY "chat_message"."created" DESC, "chat_message"."id" DESC LIMIT 3"#
+        ),
+        "messages closest to the cursor not retrieved first: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn forwards_page_with_more() {
+    let messages = vec![message("a", 1), message("b", 1), message("c", 2)];
+
+    let page = messages_page(messages, 2, false, true, false);
+
+    assert_eq!(ids(&page), vec!["a", "b"], "extra message not dropped");
+    assert!(page.has_previous_page, "previous page not reported");
+    assert!(page.has_next_page, "next page not reported");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn forwards_last_page() {
+    let messages = vec![message("a", 1), message("b", 2)];
+
+    let page = messages_page(messages, 2, false, false, false);
+
+    assert_eq!(ids(&page), vec!["a", "b"], "messages dropped");
+    assert!(!page.has_previous_page, "previous page reported");
+    assert!(!page.has_next_page, "next page reported on the last page");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn backwards_page() {
+    // retrieved newest first, as `messages_query` orders them when paginating backwards
+    let messages = vec![message("c", 3), message("b", 2), message("a", 1)];
+
+    let page = messages_page(messages, 2, true, false, true);
+
+    assert_eq!(ids(&page), vec!["b", "c"], "page not put back oldest first");
+    assert!(page.has_previous_page, "previous page not reported");
+    assert!(page.has_next_page, "next page not reported");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn page_cursors() {
+    let page = messages_page(
+        vec![message("a", 1), message("b", 1)],
+        2,
+        false,
+        false,
+        false,
+    );
+
+    let cursor = TimestampCursor::decode_cursor(&page.edges[1].cursor.encode_cursor()).unwrap();
+
+    assert_eq!(cursor.created, time(1), "cursor has the wrong time");
+    assert_eq!(cursor.id, "b", "cursor has the wrong id");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn search_requires_permission() {
+    let access = create_access(&["+chat.view"], &[]);
+
+    let search = search_query(&access, "chat", "hello");
+
+    assert!(search.is_err(), "searching allowed without chat.search");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn search_override_denies() {
+    let access = create_access(&["+chat.view", "+chat.search"], &["-chat.search"]);
+
+    let search = search_query(&access, "chat", "hello");
+
+    assert!(search.is_err(), "component override not respected");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn search_query_built() {
+    let access = create_access(&["+chat.search"], &[]);
+
+    let sql = search_query(&access, "chat", " 100% ")
+        .expect("searching not allowed")
+        .expect("search not built")
+        .build(DbBackend::Postgres)
+        .to_string();
+
+    assert!(
+        sql.contains(r#""chat_message"."chat" = 'chat'"#),
+        "messages from other chats searched"
+    );
+    assert!(
+        sql.contains(r#""chat_message"."content" ILIKE E'%100\\%%'"#),
+        "search not trimmed and escaped: {sql}"
+    );
+
+    let empty = search_query(&access, "chat", "  ").expect("searching not allowed");
+
+    assert!(empty.is_none(), "empty search built");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn send_permissions() {
+    let access = create_access(&["+chat.view", "+chat.messages.send"], &[]);
+
+    assert!(check_can_send(&access, &[]).is_ok(), "sending not allowed");
+    assert!(
+        check_can_send(&access, &["file".to_string()]).is_err(),
+        "attaching allowed without chat.messages.attach"
+    );
+
+    let access = create_access(
+        &["+chat.view", "+chat.messages.send"],
+        &["-chat.messages.send"],
+    );
+
+    assert!(
+        check_can_send(&access, &[]).is_err(),
+        "component override not respected"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn message_ownership() {
+    let access = create_access(
+        &[
+            "+chat.messages.edit.own",
+            "+chat.messages.delete.own",
+            "+chat.messages.pin.others",
+        ],
+        &[],
+    );
+    let user = access.user_id.clone().unwrap();
+
+    for permission in ["chat.messages.edit", "chat.messages.delete"] {
+        assert!(
+            access.check_ownership_permission(permission, &user).is_ok(),
+            "{permission} not allowed on own messages"
+        );
+        assert!(
+            access
+                .check_ownership_permission(permission, "someone-else")
+                .is_err(),
+            "{permission} allowed on others' messages"
+        );
+    }
+
+    assert!(
+        access
+            .check_ownership_permission("chat.messages.pin", "someone-else")
+            .is_ok(),
+        "pinning others' messages not allowed"
+    );
+    assert!(
+        access
+            .check_ownership_permission("chat.messages.pin", &user)
+            .is_err(),
+        "pinning own messages allowed without chat.messages.pin.own"
+    );
+}
@@ -0,0 +1,219 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::components::events::{is_valid_timezone, occurrences, to_ics};
+use crate::entities::event::{self, Recurrence};
+use chrono::{NaiveDate, NaiveDateTime};
+
+fn time(year: i32, month: u32, day: u32, hour: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .unwrap()
+        .and_hms_opt(hour, 0, 0)
+        .unwrap()
+}
+
+fn event(start: NaiveDateTime, recurrence: Option<Recurrence>) -> event::Model {
+    event::Model {
+        id: "event".to_string(),
+        calendar: "calendar".to_string(),
+        title: "Game night".to_string(),
+        description: None,
+        location: None,
+        start,
+        end: start + chrono::Duration::hours(2),
+        timezone: "Europe/London".to_string(),
+        recurrence,
+        recurrence_interval: 1,
+        recurrence_count: None,
+        recurrence_until: None,
+        creator: "user".to_string(),
+        created: start,
+        updated: None,
+    }
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn single_event_overlap() {
+    let event = event(time(2026, 1, 1, 18), None);
+
+    let inside = occurrences(&event, time(2026, 1, 1, 19), time(2026, 1, 2, 0), 10);
+    let outside = occurrences(&event, time(2026, 1, 1, 20), time(2026, 1, 2, 0), 10);
+
+    assert_eq!(inside.len(), 1, "overlapping event wasn't listed");
+    assert!(outside.is_empty(), "finished event was listed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn weekly_in_range() {
+    let mut event = event(time(2026, 1, 1, 18), Some(Recurrence::Weekly));
+    event.recurrence_interval = 2;
+
+    let found = occurrences(&event, time(2026, 3, 1, 0), time(2026, 4, 1, 0), 10);
+
+    assert_eq!(
+        found.iter().map(|(start, _)| *start).collect::<Vec<_>>(),
+        vec![time(2026, 3, 12, 18), time(2026, 3, 26, 18)],
+        "occurrences were incorrect"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn daily_count_after_skip() {
+    let mut event = event(time(2026, 1, 1, 18), Some(Recurrence::Daily));
+    event.recurrence_count = Some(10);
+
+    let found = occurrences(&event, time(2026, 1, 9, 0), time(2026, 2, 1, 0), 10);
+
+    assert_eq!(
+        found.iter().map(|(start, _)| *start).collect::<Vec<_>>(),
+        vec![time(2026, 1, 9, 18), time(2026, 1, 10, 18)],
+        "count wasn't respected"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn monthly_skips_missing_days() {
+    let mut event = event(time(2026, 1, 31, 18), Some(Recurrence::Monthly));
+    event.timezone = "UTC".to_string();
+    event.recurrence_until = Some(time(2026, 5, 31, 18));
+
+    let found = occurrences(&event, time(2026, 1, 1, 0), time(2027, 1, 1, 0), 10);
+
+    assert_eq!(
+        found.iter().map(|(start, _)| *start).collect::<Vec<_>>(),
+        vec![
+            time(2026, 1, 31, 18),
+            time(2026, 3, 31, 18),
+            time(2026, 5, 31, 18)
+        ],
+        "months without the day weren't skipped"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn timezone_names() {
+    assert!(is_valid_timezone("UTC"), "UTC was rejected");
+    assert!(
+        is_valid_timezone("America/Argentina/Buenos_Aires"),
+        "nested timezone was rejected"
+    );
+    assert!(
+        !is_valid_timezone("Europe//London"),
+        "empty part was accepted"
+    );
+    assert!(!is_valid_timezone("../etc/passwd"), "path was accepted");
+    assert!(
+        !is_valid_timezone("Mars/Olympus_Mons"),
+        "unknown timezone was accepted"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn weekly_across_clock_change() {
+    // the clocks in London go forward on the 29th of March 2026
+    let event = event(time(2026, 3, 19, 18), Some(Recurrence::Weekly));
+
+    let found = occurrences(&event, time(2026, 3, 20, 0), time(2026, 4, 10, 0), 10);
+
+    assert_eq!(
+        found.iter().map(|(start, _)| *start).collect::<Vec<_>>(),
+        vec![
+            time(2026, 3, 26, 18),
+            time(2026, 4, 2, 17),
+            time(2026, 4, 9, 17)
+        ],
+        "occurrences didn't keep their local time"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn daily_skip_across_clock_change() {
+    // the clocks in London go back on the 25th of October 2026, so later repeats start an hour
+    // later in UTC and the one that overlaps the start of the range must not be skipped
+    let event = event(time(2026, 10, 1, 17), Some(Recurrence::Daily));
+
+    let found = occurrences(&event, time(2026, 10, 27, 19), time(2026, 10, 29, 0), 10);
+
+    assert_eq!(
+        found.iter().map(|(start, _)| *start).collect::<Vec<_>>(),
+        vec![time(2026, 10, 27, 18), time(2026, 10, 28, 18)],
+        "occurrence overlapping the range was skipped"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn ics_document() {
+    let mut event = event(time(2026, 1, 1, 18), Some(Recurrence::Weekly));
+    event.recurrence_count = Some(4);
+    event.description = Some("Bring snacks, drinks; and games\nto share".to_string());
+
+    let ics = to_ics("Planet - Events", &[event], time(2026, 1, 1, 0));
+
+    assert!(
+        ics.starts_with("BEGIN:VCALENDAR\r\n"),
+        "calendar wasn't opened"
+    );
+    assert!(ics.ends_with("END:VCALENDAR\r\n"), "calendar wasn't closed");
+    assert!(
+        ics.contains("DTSTART;TZID=Europe/London:20260101T180000\r\n"),
+        "start time was incorrect"
+    );
+    assert!(
+        ics.contains("DTEND;TZID=Europe/London:20260101T200000\r\n"),
+        "end time was incorrect"
+    );
+    assert!(
+        ics.contains("RRULE:FREQ=WEEKLY;INTERVAL=1;COUNT=4\r\n"),
+        "recurrence rule was incorrect"
+    );
+    assert!(
+        ics.contains("DESCRIPTION:Bring snacks\\, drinks\\; and games\\nto share\r\n"),
+        "description wasn't escaped"
+    );
+    assert!(
+        ics.split("\r\n").all(|line| line.len() <= 75),
+        "long line wasn't folded"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn ics_local_times() {
+    let mut summer = event(time(2026, 7, 1, 17), None);
+    summer.id = "summer".to_string();
+    let mut utc = event(time(2026, 7, 1, 17), None);
+    utc.timezone = "UTC".to_string();
+
+    let ics = to_ics("Planet - Events", &[summer, utc], time(2026, 1, 1, 0));
+
+    assert!(
+        ics.contains("DTSTART;TZID=Europe/London:20260701T180000\r\n"),
+        "start time wasn't in the event's timezone"
+    );
+    assert!(
+        ics.contains("DTSTART:20260701T170000Z\r\n"),
+        "UTC start time was incorrect"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn ics_carriage_returns() {
+    let mut event = event(time(2026, 1, 1, 18), None);
+    event.location = Some("Hall A\rRoom 2\r\nFloor 3".to_string());
+
+    let ics = to_ics("Planet - Events", &[event], time(2026, 1, 1, 0));
+
+    assert!(
+        ics.contains("LOCATION:Hall A\\nRoom 2\\nFloor 3\r\n"),
+        "carriage return wasn't escaped"
+    );
+}
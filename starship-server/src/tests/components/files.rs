@@ -0,0 +1,165 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::components::files::{archive_layout, reserve_space_query};
+use crate::entities::{file, file_folder};
+use chrono::NaiveDateTime;
+use sea_orm::{DbBackend, QueryTrait};
+
+fn sql(size: i64, quota: i64) -> String {
+    reserve_space_query("user", size, quota)
+        .build(DbBackend::Postgres)
+        .to_string()
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn reserves_space() {
+    let sql = sql(10, 100);
+
+    assert!(
+        sql.starts_with(r#"UPDATE "user" SET "bytes_used" = "bytes_used" + 10"#),
+        "space not taken up: {sql}"
+    );
+    assert!(
+        sql.contains(r#""user"."id" = 'user'"#),
+        "other users updated: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn checks_quota() {
+    let sql = sql(10, 100);
+
+    assert!(
+        sql.contains(r#""bytes_used" + 10 <= 100"#),
+        "quota not checked in the update: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn cap_waived() {
+    let sql = sql(10, 100);
+
+    assert!(
+        sql.contains(r#"("cap_waived" OR "bytes_used" + 10 <= 100)"#),
+        "users with a waived cap limited by the quota: {sql}"
+    );
+}
+
+fn folder(id: &str, parent: Option<&str>, name: &str) -> file_folder::Model {
+    file_folder::Model {
+        id: id.to_string(),
+        tree: "tree".to_string(),
+        parent: parent.map(|parent| parent.to_string()),
+        owner: "owner".to_string(),
+        name: name.to_string(),
+        created: NaiveDateTime::default(),
+    }
+}
+
+fn file(id: &str, folder: &str, name: &str) -> file::Model {
+    file::Model {
+        id: id.to_string(),
+        tree: "tree".to_string(),
+        folder: Some(folder.to_string()),
+        owner: "owner".to_string(),
+        name: name.to_string(),
+        size: 1,
+        mime_type: "text/plain".to_string(),
+        created: NaiveDateTime::default(),
+    }
+}
+
+fn layout(
+    root: &file_folder::Model,
+    folders: &[file_folder::Model],
+    files: &[file::Model],
+) -> Vec<String> {
+    archive_layout(root, folders, files)
+        .into_iter()
+        .map(|entry| entry.path)
+        .collect()
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn archive_nests_folders() {
+    let root = folder("root", None, "Root");
+    let folders = vec![
+        root.clone(),
+        folder("a", Some("root"), "A"),
+        folder("b", Some("a"), "B"),
+    ];
+    let files = vec![file("1", "b", "notes.txt"), file("2", "root", "top.txt")];
+
+    let paths = layout(&root, &folders, &files);
+
+    assert_eq!(
+        paths,
+        vec![
+            "Root/",
+            "Root/A/",
+            "Root/A/B/",
+            "Root/A/B/notes.txt",
+            "Root/top.txt"
+        ],
+        "paths don't follow the folder tree"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn archive_numbers_duplicates() {
+    let root = folder("root", None, "Root");
+    let folders = vec![root.clone(), folder("a", Some("root"), "notes")];
+    let files = vec![
+        file("1", "root", "report.pdf"),
+        file("2", "root", "report.pdf"),
+        file("3", "root", "notes"),
+        file("4", "root", ".env"),
+        file("5", "root", ".env"),
+    ];
+
+    let paths = layout(&root, &folders, &files);
+
+    assert!(
+        paths.contains(&"Root/report.pdf".to_string())
+            && paths.contains(&"Root/report (2).pdf".to_string()),
+        "duplicate files not numbered: {paths:?}"
+    );
+    assert!(
+        paths.contains(&"Root/notes (2)".to_string()),
+        "file with a folder's name not numbered: {paths:?}"
+    );
+    assert!(
+        paths.contains(&"Root/.env (2)".to_string()),
+        "file without a stem numbered wrongly: {paths:?}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn archive_leaves_out_other_folders() {
+    let root = folder("root", Some("parent"), "Root");
+    let folders = vec![
+        folder("parent", None, "Parent"),
+        root.clone(),
+        folder("sibling", Some("parent"), "Sibling"),
+    ];
+    let files = vec![
+        file("1", "parent", "outside.txt"),
+        file("2", "sibling", "sibling.txt"),
+        file("3", "root", "inside.txt"),
+    ];
+
+    let paths = layout(&root, &folders, &files);
+
+    assert_eq!(
+        paths,
+        vec!["Root/", "Root/inside.txt"],
+        "folders outside of the root included"
+    );
+}
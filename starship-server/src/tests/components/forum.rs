@@ -0,0 +1,180 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::components::access::ComponentAccess;
+use crate::components::forum::{check_can_reply, participants_query, thread_query};
+use crate::entities::{component_permission, forum_post, planet_component};
+use crate::tests::permissions::has_permission::{create_member, create_planet, create_role};
+use sea_orm::{DbBackend, QueryTrait};
+
+/// Creates access to a component for a member holding `permissions` through their role, with
+/// `overrides` applied to the member within the component.
+pub fn create_access(permissions: &[&str], overrides: &[&str]) -> ComponentAccess {
+    let member = create_member(vec![]);
+    let role = create_role(permissions.iter().map(ToString::to_string).collect(), 0);
+
+    ComponentAccess {
+        component: planet_component::Model {
+            id: "component".to_string(),
+            r#type: "irrelevant".to_string(),
+            component_id: "data".to_string(),
+            name: "irrelevant".to_string(),
+            planet: "irrelevant".to_string(),
+            created: chrono::offset::Utc::now().naive_utc(),
+            position: 0,
+            parent_id: None,
+        },
+        planet: create_planet(true),
+        user_id: Some(member.user.clone()),
+        overrides: vec![component_permission::Model {
+            id: "irrelevant".to_string(),
+            planet: "irrelevant".to_string(),
+            component: "component".to_string(),
+            role: None,
+            member: Some(member.id.clone()),
+            permissions: overrides.iter().map(ToString::to_string).collect(),
+        }],
+        member: Some(member),
+        roles: Some(vec![role]),
+    }
+}
+
+fn create_post(locked: bool) -> forum_post::Model {
+    let now = chrono::offset::Utc::now().naive_utc();
+
+    forum_post::Model {
+        locked,
+        // all other fields are unimportant for this test
+        id: "irrelevant".to_string(),
+        forum: "irrelevant".to_string(),
+        author: "irrelevant".to_string(),
+        title: "irrelevant".to_string(),
+        content: "irrelevant".to_string(),
+        tags: vec![],
+        attachments: vec![],
+        sticky: false,
+        reply_count: 0,
+        created: now,
+        edited: None,
+        last_activity: now,
+    }
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn reply_to_unlocked() {
+    let access = create_access(&["+forum.post.reply"], &[]);
+
+    let check = check_can_reply(&access, &create_post(false));
+
+    assert!(check.is_ok(), "replying not allowed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn reply_without_permission() {
+    let access = create_access(&[], &[]);
+
+    let check = check_can_reply(&access, &create_post(false));
+
+    assert!(check.is_err(), "replying allowed without forum.post.reply");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn reply_to_locked() {
+    let access = create_access(&["+forum.post.reply"], &[]);
+
+    let error = check_can_reply(&access, &create_post(true)).expect_err("replying allowed");
+
+    assert_eq!(
+        error.message, "This thread is locked.",
+        "wrong error returned"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn reply_to_locked_ignored() {
+    let access = create_access(&["+forum.post.reply", "+forum.posts.lock.ignore"], &[]);
+
+    let check = check_can_reply(&access, &create_post(true));
+
+    assert!(check.is_ok(), "forum.posts.lock.ignore not respected");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn override_denies_reply() {
+    let access = create_access(&["+forum.post.reply"], &["-forum.post.reply"]);
+
+    let check = check_can_reply(&access, &create_post(false));
+
+    assert!(check.is_err(), "component override not respected");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn edit_own_post() {
+    let access = create_access(&["+forum.posts.edit.own"], &[]);
+    let user = access.user_id.clone().unwrap();
+
+    let own = access.check_ownership_permission("forum.posts.edit", &user);
+    let others = access.check_ownership_permission("forum.posts.edit", "someone-else");
+
+    assert!(own.is_ok(), "editing own post not allowed");
+    assert!(others.is_err(), "editing others' posts allowed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn threads_sticky_first() {
+    let sql = thread_query("forum", None)
+        .build(DbBackend::Postgres)
+        .to_string();
+
+    assert!(
+        sql.ends_with(
+            r#"ORDER BY "forum_post"."sticky" DESC, "forum_post"."last_activity" DESC, "forum_post"."id" DESC"#
+        ),
+        "threads not ordered by sticky then activity: {sql}"
+    );
+    assert!(
+        sql.contains(r#"WHERE "forum_post"."forum" = 'forum'"#),
+        "threads from other forums included"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn threads_by_tag() {
+    let sql = thread_query("forum", Some("tag".to_string()))
+        .build(DbBackend::Postgres)
+        .to_string();
+
+    assert!(
+        sql.contains(r#"'tag' = ANY("forum_post"."tags")"#),
+        "threads not filtered by tag: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn participants_exclude_author() {
+    let sql = participants_query(&create_post(false))
+        .build(DbBackend::Postgres)
+        .to_string();
+
+    assert!(
+        sql.starts_with(r#"SELECT DISTINCT ON ("author")"#),
+        "participants not deduplicated: {sql}"
+    );
+    assert!(
+        sql.contains(r#""forum_reply"."author" <> 'irrelevant'"#),
+        "thread author included in participants: {sql}"
+    );
+    assert!(
+        sql.ends_with(r#"ORDER BY "forum_reply"."author" ASC LIMIT 50"#),
+        "participants not limited: {sql}"
+    );
+}
@@ -0,0 +1,7 @@
+mod chat;
+mod events;
+mod files;
+mod forum;
+mod registry;
+mod tree;
+mod wiki;
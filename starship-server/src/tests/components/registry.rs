@@ -0,0 +1,82 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::components::dummy::DummyComponent;
+use crate::components::registry::ComponentRegistry;
+use crate::permissions::constants::PermissionCategory;
+use crate::storage::LocalStorage;
+use std::sync::Arc;
+
+fn builtin_registry() -> ComponentRegistry {
+    let storage = Arc::new(LocalStorage::new(
+        std::env::temp_dir().join("starship-test"),
+    ));
+
+    ComponentRegistry::with_builtin_components(storage)
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn builtin_names() {
+    let registry = builtin_registry();
+
+    assert_eq!(
+        registry.names(),
+        vec!["chat", "dummy", "events", "files", "forum", "page", "wiki"],
+        "builtin components not listed in order"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn get_registered() {
+    let registry = builtin_registry();
+
+    let component = registry.get("forum").expect("forum not registered");
+
+    assert_eq!(component.name(), "forum", "wrong component returned");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn get_unknown() {
+    let registry = builtin_registry();
+
+    let error = registry.get("unknown").err().expect("unknown type found");
+
+    assert_eq!(error.message, "That component type isn't valid.");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn empty_registry() {
+    let mut registry = ComponentRegistry::default();
+
+    assert!(registry.names().is_empty(), "empty registry has names");
+
+    registry.register(DummyComponent);
+    registry.register(DummyComponent);
+
+    assert_eq!(registry.names(), vec!["dummy"], "duplicate type registered");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn permissions_by_category() {
+    let registry = builtin_registry();
+
+    let chat = registry.get("chat").expect("chat not registered");
+    let permissions = chat.permissions();
+
+    assert!(!permissions.is_empty(), "chat has no permissions");
+    assert!(
+        permissions
+            .iter()
+            .all(|permission| permission.category == PermissionCategory::Chat),
+        "chat has permissions from other categories"
+    );
+
+    let dummy = registry.get("dummy").expect("dummy not registered");
+
+    assert!(dummy.permissions().is_empty(), "dummy has permissions");
+}
@@ -0,0 +1,108 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::components::tree::{reorder, would_create_cycle};
+use crate::entities::planet_component;
+
+fn component(id: &str, parent: Option<&str>, position: i32) -> planet_component::Model {
+    planet_component::Model {
+        id: id.to_string(),
+        r#type: "dummy".to_string(),
+        component_id: "dummy".to_string(),
+        name: id.to_string(),
+        planet: "planet".to_string(),
+        created: chrono::offset::Utc::now().naive_utc(),
+        position,
+        parent_id: parent.map(ToString::to_string),
+    }
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn into_sibling() {
+    let components = vec![component("a", None, 0), component("b", None, 1)];
+
+    let cycle = would_create_cycle("a", "b", &components);
+
+    assert!(!cycle, "moving into sibling created cycle");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn into_itself() {
+    let components = vec![component("a", None, 0)];
+
+    let cycle = would_create_cycle("a", "a", &components);
+
+    assert!(cycle, "moving into itself didn't create cycle");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn into_descendant() {
+    let components = vec![
+        component("a", None, 0),
+        component("b", Some("a"), 0),
+        component("c", Some("b"), 0),
+    ];
+
+    let cycle = would_create_cycle("a", "c", &components);
+
+    assert!(cycle, "moving into descendant didn't create cycle");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn into_existing_cycle() {
+    let components = vec![
+        component("a", None, 0),
+        component("b", Some("c"), 0),
+        component("c", Some("b"), 0),
+    ];
+
+    let cycle = would_create_cycle("a", "b", &components);
+
+    assert!(cycle, "existing cycle not detected");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn reorder_middle() {
+    let siblings = vec![
+        component("a", None, 0),
+        component("b", None, 1),
+        component("c", None, 2),
+    ];
+
+    let order = reorder("c", 1, siblings);
+
+    assert_eq!(order, vec!["a", "c", "b"], "incorrect order");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn reorder_past_end() {
+    let siblings = vec![component("a", None, -2), component("b", None, -1)];
+
+    let order = reorder("d", 10, siblings);
+
+    assert_eq!(order, vec!["a", "b", "d"], "component not placed last");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn reorder_old_siblings() {
+    let siblings = vec![
+        component("a", None, 0),
+        component("b", None, 1),
+        component("c", None, 3),
+    ];
+
+    let order = reorder("b", usize::MAX, siblings);
+
+    assert_eq!(
+        order,
+        vec!["a", "c", "b"],
+        "old siblings not closed around the gap"
+    );
+}
@@ -0,0 +1,104 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::components::wiki::{diff_lines, parse_links, slugify, DiffKind, DiffLine};
+
+fn line(kind: DiffKind, content: &str) -> DiffLine {
+    DiffLine {
+        kind,
+        content: content.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn slug_from_title() {
+    let slug = slugify("  Getting Started: The Basics!  ");
+
+    assert_eq!(slug, "getting-started-the-basics", "slug was incorrect");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn links_parsed() {
+    let links =
+        parse_links("See [[Getting Started]] and [[rules|the rules]], or [[Getting started]].");
+
+    assert_eq!(
+        links,
+        vec!["getting-started".to_string(), "rules".to_string()],
+        "links were incorrect"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn malformed_links_ignored() {
+    let links = parse_links("[[]] [[ ! ]] [not a link] [[unclosed");
+
+    assert!(links.is_empty(), "malformed link was parsed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn diff_identical() {
+    let diff = diff_lines("a\nb", "a\nb");
+
+    assert_eq!(
+        diff,
+        vec![
+            line(DiffKind::Unchanged, "a"),
+            line(DiffKind::Unchanged, "b")
+        ],
+        "identical texts had changes"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn diff_changed_line() {
+    let diff = diff_lines("a\nb\nc", "a\nx\nc\nd");
+
+    assert_eq!(
+        diff,
+        vec![
+            line(DiffKind::Unchanged, "a"),
+            line(DiffKind::Removed, "b"),
+            line(DiffKind::Added, "x"),
+            line(DiffKind::Unchanged, "c"),
+            line(DiffKind::Added, "d"),
+        ],
+        "diff was incorrect"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn diff_reapplies() {
+    let old = "one\ntwo\nthree\nfour\nfive";
+    let new = "zero\none\nthree\nfour\n4.5\nfive\nsix";
+
+    let diff = diff_lines(old, new);
+
+    let rebuilt_old: Vec<&str> = diff
+        .iter()
+        .filter(|line| line.kind != DiffKind::Added)
+        .map(|line| line.content.as_str())
+        .collect();
+
+    let rebuilt_new: Vec<&str> = diff
+        .iter()
+        .filter(|line| line.kind != DiffKind::Removed)
+        .map(|line| line.content.as_str())
+        .collect();
+
+    assert_eq!(rebuilt_old.join("\n"), old, "old text wasn't kept");
+    assert_eq!(rebuilt_new.join("\n"), new, "new text wasn't kept");
+    assert_eq!(
+        diff.iter()
+            .filter(|line| line.kind == DiffKind::Unchanged)
+            .count(),
+        4,
+        "diff wasn't minimal"
+    );
+}
@@ -0,0 +1,143 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::config::Config;
+use std::collections::HashMap;
+
+const MINIMAL: &str = r#"
+[database]
+url = "postgres://localhost/starship"
+
+[auth]
+secret = "secret"
+"#;
+
+type Invalidate = fn(&mut Config);
+
+fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+    move |name| vars.get(name).cloned()
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn parsing() {
+    let config = Config::from_toml(MINIMAL).unwrap();
+
+    assert!(config.validate().is_ok(), "minimal config was invalid");
+    assert_eq!(config.server.port, 8000, "port did not default");
+    assert_eq!(config.limits.query_depth, 10, "depth limit did not default");
+    assert!(config.features.registration, "registration was not enabled");
+    assert!(config.smtp.is_none(), "smtp was set without a host");
+    assert!(!config.features.metrics, "metrics were served by default");
+
+    let example = Config::from_toml(include_str!("../../../starship.example.toml")).unwrap();
+    assert!(example.validate().is_ok(), "example config was invalid");
+
+    assert!(
+        Config::from_toml("[server]\nprot = 8000").is_err(),
+        "unknown setting was accepted"
+    );
+    assert!(
+        Config::from_toml("[server]\nport = \"eight thousand\"").is_err(),
+        "setting with the wrong type was accepted"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn overrides() {
+    let mut config = Config::from_toml(MINIMAL).unwrap();
+
+    config
+        .apply_overrides(lookup(&[
+            ("PORT", "9000"),
+            ("SECRET", "another secret"),
+            (
+                "CORS_ORIGINS",
+                "https://starship.example, https://beta.starship.example,",
+            ),
+            ("SMTP_HOST", "smtp.starship.example"),
+        ]))
+        .unwrap();
+
+    assert_eq!(config.server.port, 9000, "port was not overridden");
+    assert_eq!(
+        config.auth.secret, "another secret",
+        "secret was not overridden"
+    );
+    assert_eq!(
+        config.cors.origins,
+        vec!["https://starship.example", "https://beta.starship.example"],
+        "origins were not split"
+    );
+    assert_eq!(
+        config.smtp.map(|smtp| smtp.host),
+        Some("smtp.starship.example".to_string()),
+        "smtp host was not set"
+    );
+    assert_eq!(
+        config.database.url, "postgres://localhost/starship",
+        "unset variable overrode the file"
+    );
+
+    let mut config = Config::from_toml(MINIMAL).unwrap();
+
+    assert!(
+        config.apply_overrides(lookup(&[("PORT", "port")])).is_err(),
+        "invalid port was accepted"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn validation() {
+    let invalid: Vec<(&str, Invalidate)> = vec![
+        ("missing database url", |c| c.database.url.clear()),
+        ("missing secret", |c| c.auth.secret.clear()),
+        ("public url with trailing slash", |c| {
+            c.server.public_url = "http://localhost:8000/".to_string();
+        }),
+        ("public url without scheme", |c| {
+            c.server.public_url = "localhost:8000".to_string();
+        }),
+        ("origin without scheme", |c| {
+            c.cors.origins = vec!["starship.example".to_string()];
+        }),
+        ("zero depth limit", |c| c.limits.query_depth = 0),
+        ("negative quota", |c| c.limits.user_storage_quota = -1),
+        ("empty storage path", |c| c.storage.path.clear()),
+    ];
+
+    for (case, invalidate) in invalid {
+        let mut config = Config::from_toml(MINIMAL).unwrap();
+        invalidate(&mut config);
+
+        assert!(config.validate().is_err(), "{case} was accepted");
+    }
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn paths() {
+    let mut config = Config::from_toml(MINIMAL).unwrap();
+    config.server.public_url = "https://starship.example".to_string();
+    config.paths.emoji_url = Some("https://cdn.starship.example/emojis".to_string());
+
+    let paths = config.paths();
+
+    assert_eq!(
+        paths.emoji_url.as_deref(),
+        Some("https://cdn.starship.example/emojis"),
+        "set path was not used"
+    );
+    assert_eq!(paths.pfp_url, None, "unset path was filled in");
+    assert_eq!(
+        paths.graphql_endpoint, "https://starship.example/graphql",
+        "endpoint did not default"
+    );
+}
@@ -0,0 +1,90 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::direct_messages::{blocked_by_any, validate_name};
+use crate::entities::user;
+
+#[cfg(test)]
+#[actix_web::test]
+async fn blocked_by_recipient() {
+    let users = vec![
+        create_user("alice", vec![]),
+        create_user("bob", vec!["sender".to_string()]),
+    ];
+
+    assert!(
+        blocked_by_any("sender", &users),
+        "block by one recipient wasn't found"
+    );
+    assert!(
+        !blocked_by_any("someone", &users),
+        "user that wasn't blocked was treated as blocked"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn blocks_are_one_way() {
+    let users = vec![create_user("alice", vec![])];
+
+    // the sender blocking a recipient doesn't stop them messaging the recipient
+    assert!(
+        !blocked_by_any("sender", &users),
+        "recipient was treated as blocking the sender"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn names() {
+    assert_eq!(
+        validate_name(Some("  Friends  ".to_string())).unwrap(),
+        Some("Friends".to_string()),
+        "name wasn't trimmed"
+    );
+    assert_eq!(
+        validate_name(Some("   ".to_string())).unwrap(),
+        None,
+        "blank name was kept"
+    );
+    assert!(
+        validate_name(Some("a".repeat(101))).is_err(),
+        "long name was accepted"
+    );
+}
+
+fn create_user(id: &str, blocked: Vec<String>) -> user::Model {
+    user::Model {
+        id: id.to_string(),
+        created: chrono::offset::Utc::now().naive_utc(),
+        username: id.to_string(),
+        password: String::new(),
+        reset_token: None,
+        reset_expiry: None,
+        email_address: format!("{id}@example.com"),
+        verified: true,
+        verification_token: None,
+        blocked,
+        sessions: vec![],
+        banned: false,
+        ban_reason: None,
+        ban_expires: None,
+        banned_by: None,
+        admin: false,
+        notification_setting: 1,
+        cap_waived: false,
+        bytes_used: 0,
+        profile_picture: None,
+        profile_banner: None,
+        profile_bio: None,
+        tfa_secret: None,
+        tfa_enabled: false,
+        tfa_backup: vec![],
+        token_geofenced: false,
+        token_expires: false,
+        token_ip_locked: false,
+        status_message: None,
+        invisible: false,
+        calendar_token: None,
+    }
+}
@@ -0,0 +1,128 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::entities::auto_role::{self, AutoRoleTrigger};
+use crate::jobs::auto_roles::{assign_role, eligible_members, BATCH_SIZE};
+use chrono::NaiveDateTime;
+use sea_orm::{DbBackend, QueryTrait};
+
+fn time(day: u32) -> NaiveDateTime {
+    chrono::NaiveDate::from_ymd_opt(2026, 10, day)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+}
+
+fn rule(trigger: AutoRoleTrigger, days: Option<i32>) -> auto_role::Model {
+    auto_role::Model {
+        id: "rule".to_string(),
+        planet: "planet".to_string(),
+        role: "role".to_string(),
+        trigger,
+        days,
+        created_by: "creator".to_string(),
+        created: time(1),
+    }
+}
+
+fn sql(rule: &auto_role::Model) -> String {
+    eligible_members(rule, "role", time(19))
+        .build(DbBackend::Postgres)
+        .to_string()
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn join_trigger() {
+    let sql = sql(&rule(AutoRoleTrigger::Join, None));
+
+    assert!(
+        sql.contains(r#""planet_member"."created" >= '2026-10-01 00:00:00'"#),
+        "members that joined before the rule was created matched: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn membership_age_trigger() {
+    let sql = sql(&rule(AutoRoleTrigger::MembershipAge, Some(7)));
+
+    assert!(
+        sql.contains(r#""planet_member"."created" <= '2026-10-12 00:00:00'"#),
+        "members younger than the rule's age matched: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn skips_banned_and_assigned() {
+    let sql = sql(&rule(AutoRoleTrigger::Join, None));
+
+    assert!(
+        sql.contains(r#""planet_member"."planet" = 'planet'"#),
+        "members of other planets matched"
+    );
+    assert!(
+        sql.contains(r#""planet_member"."banned" = FALSE"#),
+        "banned members matched"
+    );
+    assert!(
+        sql.contains(r#"NOT ("planet_member"."roles" @> '{'role'}')"#),
+        "members with the role matched"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn removed_role_stays_removed() {
+    let sql = sql(&rule(AutoRoleTrigger::Join, None));
+
+    assert!(
+        sql.contains(r#"NOT EXISTS (SELECT 1 FROM "auto_role_assignment""#),
+        "members the rule already assigned its role to matched: {sql}"
+    );
+    assert!(
+        sql.contains(r#""auto_role_assignment"."rule" = 'rule'"#),
+        "assignments of other rules checked: {sql}"
+    );
+    assert!(
+        sql.contains(r#""auto_role_assignment"."member" = "planet_member"."id""#),
+        "assignments not matched to members: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn batched() {
+    let sql = sql(&rule(AutoRoleTrigger::Join, None));
+
+    assert!(
+        sql.ends_with(&format!("LIMIT {BATCH_SIZE}")),
+        "members not batched: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn assign_appends_conditionally() {
+    let sql = assign_role("member", "role")
+        .build(DbBackend::Postgres)
+        .to_string();
+
+    assert!(
+        sql.contains(r#"SET "roles" = array_append("roles", 'role')"#),
+        "role not appended: {sql}"
+    );
+    assert!(
+        sql.contains(r#""planet_member"."id" = 'member'"#),
+        "other members updated"
+    );
+    assert!(
+        sql.contains(r#"NOT ("planet_member"."roles" @> '{'role'}')"#),
+        "role can be assigned twice"
+    );
+    assert!(
+        sql.contains(r#""planet_member"."banned" = FALSE"#),
+        "banned members updated"
+    );
+}
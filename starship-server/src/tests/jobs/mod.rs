@@ -0,0 +1 @@
+mod auto_roles;
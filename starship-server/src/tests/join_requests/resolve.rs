@@ -0,0 +1,63 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::entities::join_request::JoinRequestStatus;
+use crate::mutations::join_requests::{check_resolved, resolve_query};
+use sea_orm::{DbBackend, QueryTrait};
+
+fn sql(status: JoinRequestStatus, user_id: Option<&str>) -> String {
+    let reviewed = chrono::NaiveDate::from_ymd_opt(2026, 10, 19)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+
+    resolve_query(
+        "request",
+        status,
+        user_id.map(ToString::to_string),
+        reviewed,
+    )
+    .build(DbBackend::Postgres)
+    .to_string()
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn only_pending_requests() {
+    let sql = sql(JoinRequestStatus::Approved, Some("reviewer"));
+
+    assert!(
+        sql.ends_with(
+            r#"WHERE ("join_request"."id" = 'request') AND ("join_request"."status" = 'pending')"#
+        ),
+        "reviewed requests can be resolved again: {sql}"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn sets_review() {
+    let sql = sql(JoinRequestStatus::Denied, Some("reviewer"));
+
+    assert!(sql.contains(r#""status" = 'denied'"#), "status not set");
+    assert!(
+        sql.contains(r#""reviewed_by" = 'reviewer'"#),
+        "reviewer not set"
+    );
+    assert!(
+        sql.contains(r#""reviewed" = '2026-10-19 12:00:00'"#),
+        "review time not set"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn already_reviewed() {
+    let error = check_resolved(0).expect_err("unresolved request accepted");
+
+    assert_eq!(
+        error.message, "This request has already been reviewed.",
+        "wrong error returned"
+    );
+    assert!(check_resolved(1).is_ok(), "resolved request rejected");
+}
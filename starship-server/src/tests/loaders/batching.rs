@@ -0,0 +1,154 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::errors;
+use crate::loaders::{create_loader, load_ordered};
+use async_graphql::dataloader::Loader;
+use async_graphql::Error;
+use futures_util::future::join_all;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Loads the length of each ID, recording the IDs it was asked for. IDs starting with `missing`
+/// have no value.
+#[derive(Clone, Default)]
+struct LengthLoader {
+    batches: Arc<Mutex<Vec<Vec<String>>>>,
+    fail: bool,
+}
+
+#[async_trait::async_trait]
+impl Loader<String> for LengthLoader {
+    type Value = usize;
+    type Error = Error;
+
+    async fn load(&self, ids: &[String]) -> Result<HashMap<String, usize>, Error> {
+        let mut batch = ids.to_vec();
+        batch.sort();
+        self.batches.lock().unwrap().push(batch);
+
+        if self.fail {
+            return Err(errors::create_internal_server_error(None, "LOAD_ERROR"));
+        }
+
+        Ok(ids
+            .iter()
+            .filter(|id| !id.starts_with("missing"))
+            .map(|id| (id.clone(), id.len()))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn batching() {
+    let loader = LengthLoader::default();
+    let data_loader = create_loader(loader.clone(), true);
+
+    let values = join_all(
+        ["a", "bb", "a", "missing"]
+            .into_iter()
+            .map(|id| data_loader.load_one(id.to_string())),
+    )
+    .await;
+
+    let values: Vec<Option<usize>> = values.into_iter().map(Result::unwrap).collect();
+
+    assert_eq!(
+        values,
+        vec![Some(1), Some(2), Some(1), None],
+        "values did not match their IDs"
+    );
+    assert_eq!(
+        *loader.batches.lock().unwrap(),
+        vec![vec![
+            "a".to_string(),
+            "bb".to_string(),
+            "missing".to_string()
+        ]],
+        "IDs were not loaded in a single batch"
+    );
+
+    let values = load_ordered(
+        &data_loader,
+        &["ccc".to_string(), "missing".to_string(), "bb".to_string()],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(values, vec![3, 2], "values were not in the same order");
+    // IDs without a value aren't cached, so they are loaded again
+    assert_eq!(
+        loader.batches.lock().unwrap().last().unwrap(),
+        &vec!["ccc".to_string(), "missing".to_string()],
+        "cached IDs were loaded again"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn large_batches() {
+    let loader = LengthLoader::default();
+    let data_loader = create_loader(loader.clone(), true);
+
+    let ids: Vec<String> = (0..100).map(|i| format!("id-{i}")).collect();
+    let values = join_all(ids.iter().map(|id| data_loader.load_one(id.clone()))).await;
+
+    assert!(
+        values.iter().all(|value| value.as_ref().unwrap().is_some()),
+        "values were missing"
+    );
+    assert_eq!(
+        loader.batches.lock().unwrap().len(),
+        1,
+        "IDs were not loaded in a single batch"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn without_cache() {
+    let loader = LengthLoader::default();
+    let data_loader = create_loader(loader.clone(), false);
+
+    data_loader.load_one("a".to_string()).await.unwrap();
+    data_loader.load_one("a".to_string()).await.unwrap();
+
+    assert_eq!(
+        loader.batches.lock().unwrap().len(),
+        2,
+        "values were cached"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn errors() {
+    let loader = LengthLoader {
+        fail: true,
+        ..LengthLoader::default()
+    };
+    let data_loader = create_loader(loader.clone(), true);
+
+    let values = join_all(
+        ["a", "b"]
+            .into_iter()
+            .map(|id| data_loader.load_one(id.to_string())),
+    )
+    .await;
+
+    assert!(
+        values.iter().all(Result::is_err),
+        "error was not returned to every resolver"
+    );
+    assert_eq!(
+        loader.batches.lock().unwrap().len(),
+        1,
+        "IDs were not loaded in a single batch"
+    );
+
+    assert!(
+        data_loader.load_one("a".to_string()).await.is_err(),
+        "failed load was cached"
+    );
+}
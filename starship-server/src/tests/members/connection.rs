@@ -0,0 +1,120 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::entities::graphql::cursor::{cursor_condition, TimestampCursor};
+use crate::entities::graphql::member_connection::{
+    complexity, escape_like, filter_members, MemberFilter, MemberOrder,
+};
+use crate::entities::planet_member;
+use async_graphql::connection::CursorType;
+use chrono::NaiveDate;
+use sea_orm::{DbBackend, EntityTrait, QueryFilter, QueryTrait};
+
+fn filter() -> MemberFilter {
+    MemberFilter {
+        planet: "planet".to_string(),
+        search: None,
+        role: None,
+        banned: None,
+        order: MemberOrder::NewestFirst,
+    }
+}
+
+fn sql(filter: &MemberFilter) -> String {
+    filter_members(filter)
+        .build(DbBackend::Postgres)
+        .to_string()
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn planet_only() {
+    let sql = sql(&filter());
+
+    assert!(
+        sql.contains(r#""planet_member"."planet" = 'planet'"#),
+        "members should be limited to the planet"
+    );
+    assert!(
+        !sql.contains("JOIN"),
+        "user shouldn't be joined without a search"
+    );
+    assert!(!sql.contains("ANY"), "roles shouldn't be filtered");
+    assert!(
+        !sql.contains(r#""planet_member"."banned" ="#),
+        "banned shouldn't be filtered"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn search_role_and_banned() {
+    let sql = sql(&MemberFilter {
+        search: Some("50%_off".to_string()),
+        role: Some("role".to_string()),
+        banned: Some(true),
+        ..filter()
+    });
+
+    assert!(
+        sql.contains(r#"JOIN "user""#),
+        "user should be joined to search"
+    );
+    assert!(
+        sql.contains(r#""user"."username" ILIKE E'%50\\%\\_off%'"#),
+        "usernames should be searched with wildcards escaped"
+    );
+    assert!(
+        sql.contains(r#""planet_member"."nickname" ILIKE E'%50\\%\\_off%'"#),
+        "nicknames should be searched with wildcards escaped"
+    );
+    assert!(
+        sql.contains(r#""planet_member"."roles" @> '{'role'}'"#),
+        "members should be filtered by role"
+    );
+    assert!(
+        sql.contains(r#""planet_member"."banned" = TRUE"#),
+        "members should be filtered by whether they are banned"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn escapes_like_wildcards() {
+    assert_eq!(escape_like(r"a\b%c_d"), r"a\\b\%c\_d");
+    assert_eq!(escape_like("plain"), "plain");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn complexity_uses_page_size() {
+    assert_eq!(complexity(None, None, 1), 20 * 5 + 20, "default page size");
+    assert_eq!(complexity(Some(10), None, 2), 10 * 5 + 10 * 2, "first");
+    assert_eq!(complexity(None, Some(10), 2), 10 * 5 + 10 * 2, "last");
+    assert_eq!(
+        complexity(Some(1000), None, 1),
+        100 * 5 + 100,
+        "page size should be capped"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn cursor_round_trip() {
+    let cursor = TimestampCursor {
+        created: NaiveDate::from_ymd_opt(2026, 1, 1)
+            .unwrap()
+            .and_hms_micro_opt(12, 30, 0, 250)
+            .unwrap(),
+        id: "member|id".to_string(),
+    };
+
+    let decoded = TimestampCursor::decode_cursor(&cursor.encode_cursor()).unwrap();
+
+    assert_eq!(decoded.created, cursor.created);
+    assert_eq!(decoded.id, cursor.id);
+    assert!(
+        TimestampCursor::decode_cursor("not a cursor").is_err(),
+        "invalid cursors should be rejected"
+    );
+}
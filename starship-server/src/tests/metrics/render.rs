@@ -0,0 +1,124 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::errors;
+use crate::metrics::{Gauges, Metrics, MetricsExtension};
+use async_graphql::{EmptyMutation, EmptySubscription, Error, Object, Response, Schema};
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn ok(&self) -> bool {
+        true
+    }
+
+    async fn fail(&self) -> Result<bool, Error> {
+        Err(errors::create_forbidden_error(None, "FORBIDDEN"))
+    }
+}
+
+fn gauges() -> Gauges {
+    Gauges {
+        pool_connections: 3,
+        pool_idle: 2,
+        pool_max: 10,
+        active_sessions: 4,
+        online_users: 5,
+    }
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn requests() {
+    let metrics = Metrics::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(MetricsExtension(metrics.clone()))
+        .finish();
+
+    schema.execute("query Home { ok }").await;
+    schema.execute("query Home { ok }").await;
+    schema.execute("{ fail }").await;
+    schema.execute("{ missing }").await;
+
+    let rendered = metrics.render(&gauges());
+
+    assert!(
+        rendered.contains("starship_graphql_request_duration_seconds_count{operation=\"Home\"} 2"),
+        "named operation was not counted"
+    );
+    assert!(
+        rendered.contains(
+            "starship_graphql_request_duration_seconds_bucket{operation=\"Home\",le=\"+Inf\"} 2"
+        ),
+        "named operation was not counted in the last bucket"
+    );
+    assert!(
+        rendered.contains(
+            "starship_graphql_errors_total{operation=\"anonymous\",type=\"FORBIDDEN\",code=\"FORBIDDEN\"} 1"
+        ),
+        "error was not counted by type and code"
+    );
+    assert!(
+        rendered.contains(
+            "starship_graphql_errors_total{operation=\"anonymous\",type=\"UNKNOWN\",code=\"UNKNOWN\"} 1"
+        ),
+        "validation error was not counted"
+    );
+    assert!(
+        !rendered.contains("operation=\"Home\",type="),
+        "successful operation had errors"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn operation_limit() {
+    let metrics = Metrics::default();
+
+    for i in 0..250 {
+        metrics.record_request(Some(&format!("Operation{i}")), 0.01, &Response::default());
+    }
+
+    metrics.record_request(Some("Operation0"), 0.01, &Response::default());
+
+    let rendered = metrics.render(&gauges());
+
+    assert!(
+        rendered.contains(
+            "starship_graphql_request_duration_seconds_count{operation=\"Operation0\"} 2"
+        ),
+        "tracked operation was not counted"
+    );
+    assert!(
+        rendered
+            .contains("starship_graphql_request_duration_seconds_count{operation=\"other\"} 50"),
+        "operations past the limit were not grouped"
+    );
+    assert!(
+        !rendered.contains("operation=\"Operation249\""),
+        "operation past the limit was tracked"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn gauges_and_escaping() {
+    let metrics = Metrics::default();
+    metrics.record_request(Some("Say \"hi\""), 0.2, &Response::default());
+
+    let rendered = metrics.render(&gauges());
+
+    for line in [
+        "starship_db_pool_connections 3",
+        "starship_db_pool_idle_connections 2",
+        "starship_db_pool_max_connections 10",
+        "starship_active_sessions 4",
+        "starship_online_users 5",
+        "# TYPE starship_active_sessions gauge",
+        "starship_graphql_request_duration_seconds_bucket{operation=\"Say \\\"hi\\\"\",le=\"0.1\"} 0",
+        "starship_graphql_request_duration_seconds_bucket{operation=\"Say \\\"hi\\\"\",le=\"0.25\"} 1",
+    ] {
+        assert!(rendered.contains(line), "missing line: {line}");
+    }
+}
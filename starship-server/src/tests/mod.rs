@@ -1 +1,16 @@
+mod archive;
+mod audit;
+mod components;
+mod config;
+mod direct_messages;
+mod jobs;
+mod join_requests;
+mod loaders;
+mod members;
+mod metrics;
+mod moderation;
+mod notifications;
 mod permissions;
+mod presence;
+mod storage;
+mod subscriptions;
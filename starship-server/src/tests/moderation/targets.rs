@@ -0,0 +1,155 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::entities::{planet, planet_member, planet_role};
+use crate::mutations::moderation::{check_target, dedupe_ids, Target, MAX_BULK_MEMBERS};
+use crate::tests::permissions::has_permission::{create_member, create_planet, create_role};
+use async_graphql::ID;
+
+#[cfg(test)]
+#[actix_web::test]
+async fn dedupe_keeps_order() {
+    let ids = vec![ID::from("b"), ID::from("a"), ID::from("b"), ID::from("c")];
+
+    let ids = dedupe_ids(ids).expect("ids rejected");
+
+    assert_eq!(ids, vec!["b", "a", "c"], "duplicates not removed in order");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn dedupe_limits_length() {
+    let ids: Vec<ID> = (0..=MAX_BULK_MEMBERS).map(ID::from).collect();
+
+    assert!(dedupe_ids(ids).is_err(), "too many ids accepted");
+
+    let repeated: Vec<ID> = (0..=MAX_BULK_MEMBERS).map(|_| ID::from("a")).collect();
+
+    assert!(
+        dedupe_ids(repeated).is_ok(),
+        "duplicates counted towards limit"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn missing_target() {
+    let planet = create_planet(false);
+
+    let result = check_target("missing", None, &planet, None, &None, &None);
+
+    assert_eq!(code(result), "NOT_FOUND", "missing member not rejected");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn self_target() {
+    let planet = create_planet(false);
+    let target = create_target("moderator", 0);
+    let user = "moderator".to_string();
+    let (member, roles) = moderator(10);
+
+    let result = check_target(
+        "target",
+        Some(&target),
+        &planet,
+        Some(&user),
+        &member,
+        &roles,
+    );
+
+    assert_eq!(code(result), "SELF", "moderating yourself allowed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn owner_target() {
+    let planet = create_planet(false);
+    let target = create_target(&planet.owner, 0);
+    let user = "moderator".to_string();
+    let (member, roles) = moderator(10);
+
+    let result = check_target(
+        "target",
+        Some(&target),
+        &planet,
+        Some(&user),
+        &member,
+        &roles,
+    );
+
+    assert_eq!(code(result), "PLANET_OWNER", "moderating the owner allowed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn equal_role_target() {
+    let planet = create_planet(false);
+    let target = create_target("target", 10);
+    let user = "moderator".to_string();
+    let (member, roles) = moderator(10);
+
+    let result = check_target(
+        "target",
+        Some(&target),
+        &planet,
+        Some(&user),
+        &member,
+        &roles,
+    );
+
+    assert_eq!(
+        code(result),
+        "ROLE_TOO_LOW",
+        "moderating an equal role allowed"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn lower_role_target() {
+    let planet = create_planet(false);
+    let target = create_target("target", 5);
+    let user = "moderator".to_string();
+    let (member, roles) = moderator(10);
+
+    let result = check_target(
+        "target",
+        Some(&target),
+        &planet,
+        Some(&user),
+        &member,
+        &roles,
+    );
+
+    assert!(result.is_ok(), "moderating a lower role not allowed");
+}
+
+fn code(result: Result<&Target, crate::mutations::moderation::BulkMemberResult>) -> String {
+    match result {
+        Ok(_) => "OK".to_string(),
+        Err(result) => result.code.unwrap_or_default(),
+    }
+}
+
+fn create_target(user: &str, position: i32) -> Target {
+    let mut member = create_member(vec![]);
+    member.user = user.to_string();
+
+    Target {
+        member,
+        roles: vec![create_role(vec![], position)],
+    }
+}
+
+fn moderator(
+    position: i32,
+) -> (
+    Option<planet_member::Model>,
+    Option<Vec<planet_role::Model>>,
+) {
+    let mut member = create_member(vec![]);
+    member.user = "moderator".to_string();
+
+    (Some(member), Some(vec![create_role(vec![], position)]))
+}
@@ -0,0 +1,73 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::entities::notification::NotificationKind;
+use crate::notifications::{excerpt, parse_mentions, wants_notification};
+
+#[cfg(test)]
+#[actix_web::test]
+async fn setting_levels() {
+    assert!(
+        wants_notification(0, NotificationKind::Activity),
+        "activity wasn't sent with every notification on"
+    );
+    assert!(
+        !wants_notification(1, NotificationKind::Activity),
+        "activity was sent with the default setting"
+    );
+    assert!(
+        wants_notification(1, NotificationKind::Reply),
+        "reply wasn't sent with the default setting"
+    );
+    assert!(
+        !wants_notification(2, NotificationKind::Reply),
+        "reply was sent with mentions only"
+    );
+    assert!(
+        wants_notification(3, NotificationKind::DirectMessage),
+        "direct message wasn't sent with direct messages only"
+    );
+    assert!(
+        !wants_notification(4, NotificationKind::DirectMessage),
+        "direct message was sent with notifications off"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn mentions() {
+    assert_eq!(
+        parse_mentions("@alice and @bob.smith, thanks @alice."),
+        vec!["alice".to_string(), "bob.smith".to_string()],
+        "mentions were parsed incorrectly"
+    );
+    assert!(
+        parse_mentions("email me at someone@example.com").is_empty(),
+        "email address was parsed as a mention"
+    );
+    assert!(
+        parse_mentions("just an @ on its own").is_empty(),
+        "bare @ was parsed as a mention"
+    );
+    assert_eq!(
+        parse_mentions("(@carol)"),
+        vec!["carol".to_string()],
+        "mention in brackets wasn't parsed"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn excerpts() {
+    assert_eq!(excerpt("short"), "short", "short content was changed");
+
+    let long = "é".repeat(250);
+    let shortened = excerpt(&long);
+
+    assert_eq!(
+        shortened.chars().count(),
+        201,
+        "long content wasn't shortened to 200 characters"
+    );
+    assert!(shortened.ends_with('…'), "shortened content wasn't marked");
+}
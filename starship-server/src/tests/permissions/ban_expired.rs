@@ -0,0 +1,45 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::permissions::checks::ban_expired;
+use chrono::Duration;
+
+#[cfg(test)]
+#[actix_web::test]
+async fn permanent_ban() {
+    let now = chrono::offset::Utc::now().naive_utc();
+
+    let check = ban_expired(true, None, now);
+
+    assert!(!check, "permanent ban expired");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn future_expiry() {
+    let now = chrono::offset::Utc::now().naive_utc();
+
+    let check = ban_expired(true, Some(now + Duration::days(1)), now);
+
+    assert!(!check, "ban expired early");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn past_expiry() {
+    let now = chrono::offset::Utc::now().naive_utc();
+
+    let check = ban_expired(true, Some(now - Duration::days(1)), now);
+
+    assert!(check, "ban did not expire");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn not_banned() {
+    let now = chrono::offset::Utc::now().naive_utc();
+
+    let check = ban_expired(false, Some(now - Duration::days(1)), now);
+
+    assert!(!check, "unbanned member treated as expired ban");
+}
@@ -0,0 +1,126 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use super::has_permission::{create_member, create_planet, create_role};
+use crate::permissions::checks::{calculate_permissions, PermissionSource};
+use async_graphql::ID;
+
+#[cfg(test)]
+#[actix_web::test]
+async fn default_source() {
+    let planet = create_planet(false);
+
+    let permissions = calculate_permissions(&planet, None, None, None);
+    let permission = permissions.get("planet.view").expect("planet.view missing");
+
+    assert!(permission.granted, "planet.view not granted");
+    assert_eq!(permission.source, PermissionSource::Default);
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn role_source() {
+    let planet = create_planet(true);
+    let member = create_member(vec![]);
+    let mut role = create_role(vec!["-planet.view".to_string()], 0);
+    role.id = "role".to_string();
+
+    let permissions = calculate_permissions(&planet, Some(member), Some(vec![role]), None);
+    let permission = permissions.get("planet.view").expect("planet.view missing");
+
+    assert!(!permission.granted, "planet.view granted");
+    assert_eq!(permission.source, PermissionSource::Role);
+    assert_eq!(permission.role_id, Some(ID("role".to_string())));
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn member_source() {
+    let planet = create_planet(true);
+    let member = create_member(vec!["+planet.view".to_string()]);
+    let role = create_role(vec!["-planet.view".to_string()], 0);
+
+    let permissions = calculate_permissions(&planet, Some(member), Some(vec![role]), None);
+    let permission = permissions.get("planet.view").expect("planet.view missing");
+
+    assert!(permission.granted, "planet.view not granted");
+    assert_eq!(permission.source, PermissionSource::Member);
+    assert_eq!(permission.role_id, None);
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn owner_source() {
+    let planet = create_planet(true);
+    let member = create_member(vec!["+owner".to_string()]);
+
+    let permissions = calculate_permissions(&planet, Some(member), None, None);
+    let permission = permissions
+        .get("planet.change_name")
+        .expect("planet.change_name missing");
+
+    assert!(permission.granted, "planet.change_name not granted");
+    assert_eq!(permission.source, PermissionSource::Owner);
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn private_without_member() {
+    let planet = create_planet(true);
+
+    let permissions = calculate_permissions(&planet, None, None, None);
+
+    assert!(
+        permissions.is_empty(),
+        "permissions calculated for non-member"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn exact_overrides_wildcard_in_role() {
+    let planet = create_planet(true);
+
+    for permissions in [
+        vec!["-forum.posts.*", "+forum.posts.create"],
+        vec!["+forum.posts.create", "-forum.posts.*"],
+    ] {
+        let member = create_member(vec![]);
+        let role = create_role(permissions.iter().map(ToString::to_string).collect(), 0);
+
+        let calculated = calculate_permissions(&planet, Some(member), Some(vec![role]), None);
+
+        assert!(
+            calculated.get("forum.posts.create").unwrap().granted,
+            "forum.posts.create not granted for {permissions:?}"
+        );
+        assert!(
+            !calculated.get("forum.posts.attach").unwrap().granted,
+            "forum.posts.attach granted for {permissions:?}"
+        );
+    }
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn exact_overrides_wildcard_on_member() {
+    let planet = create_planet(true);
+
+    for permissions in [
+        vec!["+forum.posts.*", "-forum.posts.create"],
+        vec!["-forum.posts.create", "+forum.posts.*"],
+    ] {
+        let member = create_member(permissions.iter().map(ToString::to_string).collect());
+
+        let calculated = calculate_permissions(&planet, Some(member), None, None);
+
+        assert!(
+            !calculated.get("forum.posts.create").unwrap().granted,
+            "forum.posts.create granted for {permissions:?}"
+        );
+        assert!(
+            calculated.get("forum.posts.attach").unwrap().granted,
+            "forum.posts.attach not granted for {permissions:?}"
+        );
+    }
+}
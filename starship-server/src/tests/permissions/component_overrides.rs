@@ -0,0 +1,202 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use super::has_permission::{create_member, create_planet, create_role};
+use crate::entities::component_permission;
+use crate::permissions::checks::{calculate_permissions, has_permission, PermissionSource};
+
+fn create_override(
+    role: Option<&str>,
+    member: Option<&str>,
+    permissions: Vec<String>,
+) -> component_permission::Model {
+    component_permission::Model {
+        role: role.map(|role| role.to_string()),
+        member: member.map(|member| member.to_string()),
+        permissions,
+        // all other fields are unimportant for this test
+        id: "irrelevant".to_string(),
+        planet: "irrelevant".to_string(),
+        component: "irrelevant".to_string(),
+    }
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn everyone_override_denies_public() {
+    let planet = create_planet(false);
+    let overrides = vec![create_override(None, None, vec!["-forum.view".to_string()])];
+
+    let check = has_permission("forum.view", &planet, None, None, Some(&overrides));
+
+    assert!(!check, "forum.view allowed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn role_override_supersedes_planet() {
+    let planet = create_planet(false);
+    let member = create_member(vec![]);
+    let role = create_role(vec!["+forum.posts.create".to_string()], 0);
+    let overrides = vec![create_override(
+        Some("irrelevant"),
+        None,
+        vec!["-forum.posts.create".to_string()],
+    )];
+
+    let permissions =
+        calculate_permissions(&planet, Some(member), Some(vec![role]), Some(&overrides));
+    let permission = permissions.get("forum.posts.create").unwrap();
+
+    assert!(!permission.granted, "forum.posts.create allowed");
+    assert_eq!(permission.source, PermissionSource::ComponentRole);
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn member_override_supersedes_role_override() {
+    let planet = create_planet(false);
+    let member = create_member(vec![]);
+    let role = create_role(vec![], 0);
+    let overrides = vec![
+        create_override(
+            None,
+            Some("irrelevant"),
+            vec!["+forum.posts.create".to_string()],
+        ),
+        create_override(Some("irrelevant"), None, vec!["-forum.posts.*".to_string()]),
+    ];
+
+    let check = has_permission(
+        "forum.posts.create",
+        &planet,
+        Some(member.clone()),
+        Some(vec![role.clone()]),
+        Some(&overrides),
+    );
+    let other_check = has_permission(
+        "forum.posts.attach",
+        &planet,
+        Some(member),
+        Some(vec![role]),
+        Some(&overrides),
+    );
+
+    assert!(check, "forum.posts.create not allowed");
+    assert!(!other_check, "forum.posts.attach allowed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn overrides_ignore_other_members() {
+    let planet = create_planet(false);
+    let member = create_member(vec![]);
+    let overrides = vec![create_override(
+        None,
+        Some("someone else"),
+        vec!["-forum.view".to_string()],
+    )];
+
+    let check = has_permission("forum.view", &planet, Some(member), None, Some(&overrides));
+
+    assert!(check, "forum.view not allowed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn admin_overrides_component() {
+    let planet = create_planet(false);
+    let member = create_member(vec!["+administrator".to_string()]);
+    let overrides = vec![create_override(None, None, vec!["-forum.*".to_string()])];
+
+    let check = has_permission(
+        "forum.posts.create",
+        &planet,
+        Some(member),
+        None,
+        Some(&overrides),
+    );
+
+    assert!(check, "forum.posts.create not allowed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn exact_overrides_wildcard_in_override() {
+    let planet = create_planet(false);
+
+    for permissions in [
+        vec!["-forum.posts.*", "+forum.posts.create"],
+        vec!["+forum.posts.create", "-forum.posts.*"],
+    ] {
+        let overrides = vec![create_override(
+            None,
+            None,
+            permissions.iter().map(ToString::to_string).collect(),
+        )];
+
+        let calculated = calculate_permissions(&planet, None, None, Some(&overrides));
+
+        assert!(
+            calculated.get("forum.posts.create").unwrap().granted,
+            "forum.posts.create not granted for {permissions:?}"
+        );
+        assert!(
+            !calculated.get("forum.posts.attach").unwrap().granted,
+            "forum.posts.attach granted for {permissions:?}"
+        );
+    }
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn everyone_override_grants_non_member() {
+    let planet = create_planet(false);
+    let overrides = vec![create_override(
+        None,
+        None,
+        vec!["+forum.posts.create".to_string()],
+    )];
+
+    let permissions = calculate_permissions(&planet, None, None, Some(&overrides));
+    let permission = permissions.get("forum.posts.create").unwrap();
+
+    assert!(permission.granted, "forum.posts.create not allowed");
+    assert_eq!(permission.source, PermissionSource::ComponentDefault);
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn everyone_override_skips_banned_member() {
+    let planet = create_planet(true);
+    let mut member = create_member(vec![]);
+    member.banned = true;
+    let overrides = vec![create_override(None, None, vec!["+forum.view".to_string()])];
+
+    let check = has_permission("forum.view", &planet, Some(member), None, Some(&overrides));
+
+    assert!(!check, "forum.view allowed for banned member");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn everyone_override_skips_banned_member_public() {
+    let planet = create_planet(false);
+    let mut member = create_member(vec![]);
+    member.banned = true;
+    let overrides = vec![create_override(
+        None,
+        None,
+        vec!["+forum.posts.create".to_string()],
+    )];
+
+    let check = has_permission(
+        "forum.posts.create",
+        &planet,
+        Some(member),
+        None,
+        Some(&overrides),
+    );
+
+    assert!(!check, "forum.posts.create allowed for banned member");
+}
@@ -11,7 +11,7 @@ use crate::permissions::checks::has_permission;
 async fn public_permissions() {
     let planet = create_planet(false);
 
-    let check = has_permission("planet.view", &planet, None, None);
+    let check = has_permission("planet.view", &planet, None, None, None);
 
     assert!(check, "planet.view not allowed");
 }
@@ -21,7 +21,7 @@ async fn public_permissions() {
 async fn private_permissions() {
     let planet = create_planet(true);
 
-    let check = has_permission("planet.view", &planet, None, None);
+    let check = has_permission("planet.view", &planet, None, None, None);
 
     assert!(!check, "planet.view allowed");
 }
@@ -32,7 +32,7 @@ async fn member_with_permission() {
     let planet = create_planet(true);
     let member = create_member(vec!["+planet.view".to_string()]);
 
-    let check = has_permission("planet.view", &planet, Some(member), None);
+    let check = has_permission("planet.view", &planet, Some(member), None, None);
 
     assert!(check, "planet.view not allowed");
 }
@@ -43,7 +43,7 @@ async fn member_without_permission() {
     let planet = create_planet(false);
     let member = create_member(vec!["-planet.view".to_string()]);
 
-    let check = has_permission("planet.view", &planet, Some(member), None);
+    let check = has_permission("planet.view", &planet, Some(member), None, None);
 
     assert!(!check, "planet.view allowed");
 }
@@ -55,7 +55,7 @@ async fn role_with_permission() {
     let member = create_member(vec![]);
     let role = create_role(vec!["+planet.view".to_string()], 0);
 
-    let check = has_permission("planet.view", &planet, Some(member), Some(vec![role]));
+    let check = has_permission("planet.view", &planet, Some(member), Some(vec![role]), None);
 
     assert!(check, "planet.view not allowed");
 }
@@ -67,7 +67,7 @@ async fn role_without_permission() {
     let member = create_member(vec![]);
     let role = create_role(vec!["-planet.view".to_string()], 0);
 
-    let check = has_permission("planet.view", &planet, Some(member), Some(vec![role]));
+    let check = has_permission("planet.view", &planet, Some(member), Some(vec![role]), None);
 
     assert!(!check, "planet.view allowed");
 }
@@ -79,7 +79,7 @@ async fn member_supersedes_role() {
     let member = create_member(vec!["+planet.view".to_string()]);
     let role = create_role(vec!["-planet.view".to_string()], 0);
 
-    let check = has_permission("planet.view", &planet, Some(member), Some(vec![role]));
+    let check = has_permission("planet.view", &planet, Some(member), Some(vec![role]), None);
 
     assert!(check, "planet.view not allowed");
 }
@@ -97,6 +97,7 @@ async fn correct_role_order() {
         &planet,
         Some(member),
         Some(vec![role2, role1]),
+        None,
     );
 
     assert!(check, "planet.view not allowed");
@@ -115,6 +116,7 @@ async fn admin_overrides() {
         &planet,
         Some(member),
         Some(vec![role1, role2]),
+        None,
     );
 
     assert!(check, "planet.view not allowed");
@@ -127,7 +129,7 @@ async fn owner_overrides() {
     let member = create_member(vec!["+owner".to_string()]);
     let role = create_role(vec!["-planet.view".to_string()], 0);
 
-    let check = has_permission("planet.view", &planet, Some(member), Some(vec![role]));
+    let check = has_permission("planet.view", &planet, Some(member), Some(vec![role]), None);
 
     assert!(check, "planet.view not allowed");
 }
@@ -139,7 +141,7 @@ async fn member_planet_locked() {
     let mut member = create_member(vec![]);
     member.planet = "different".to_string();
 
-    let check = has_permission("planet.view", &planet, Some(member), None);
+    let check = has_permission("planet.view", &planet, Some(member), None, None);
 
     assert!(!check, "permission check did not detect incorrect planet");
 }
@@ -152,12 +154,63 @@ async fn role_planet_locked() {
     let mut role = create_role(vec![], 0);
     role.planet = "different".to_string();
 
-    let check = has_permission("planet.view", &planet, Some(member), Some(vec![role]));
+    let check = has_permission("planet.view", &planet, Some(member), Some(vec![role]), None);
 
     assert!(!check, "permission check did not detect incorrect planet");
 }
 
-fn create_planet(private: bool) -> planet::Model {
+#[cfg(test)]
+#[actix_web::test]
+async fn wildcard_denies_namespace() {
+    let planet = create_planet(false);
+    let member = create_member(vec!["-forum.posts.*".to_string()]);
+    let role = create_role(vec!["+forum.posts.create".to_string()], 0);
+
+    let check = has_permission(
+        "forum.posts.create",
+        &planet,
+        Some(member),
+        Some(vec![role]),
+        None,
+    );
+
+    assert!(!check, "forum.posts.create allowed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn specific_overrides_wildcard() {
+    let planet = create_planet(false);
+    let member = create_member(vec![
+        "-forum.posts.*".to_string(),
+        "+forum.posts.create".to_string(),
+    ]);
+
+    let check = has_permission(
+        "forum.posts.create",
+        &planet,
+        Some(member.clone()),
+        None,
+        None,
+    );
+    let other_check = has_permission("forum.posts.attach", &planet, Some(member), None, None);
+
+    assert!(check, "forum.posts.create not allowed");
+    assert!(!other_check, "forum.posts.attach allowed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn wildcard_respects_namespace() {
+    let planet = create_planet(false);
+    let member = create_member(vec!["-forum.*".to_string()]);
+
+    let check = has_permission("planet.view", &planet, Some(member), None, None);
+
+    assert!(check, "planet.view not allowed");
+}
+
+pub fn create_planet(private: bool) -> planet::Model {
     planet::Model {
         private,
         // all other fields are unimportant for this test
@@ -173,10 +226,12 @@ fn create_planet(private: bool) -> planet::Model {
         css: "irrelevant".to_string(),
         description: Some("irrelevant".to_string()),
         home: Some("irrelevant".to_string()),
+        join_requests_enabled: false,
+        join_question: None,
     }
 }
 
-fn create_member(permissions: Vec<String>) -> planet_member::Model {
+pub fn create_member(permissions: Vec<String>) -> planet_member::Model {
     planet_member::Model {
         permissions,
         // all other fields are unimportant for this test
@@ -186,10 +241,16 @@ fn create_member(permissions: Vec<String>) -> planet_member::Model {
         roles: vec!["irrelevant".to_string()],
         created: chrono::offset::Utc::now().naive_utc(),
         banned: false,
+        ban_reason: None,
+        ban_expires: None,
+        banned_by: None,
+        banned_at: None,
+        nickname: None,
+        last_active: chrono::offset::Utc::now().naive_utc(),
     }
 }
 
-fn create_role(permissions: Vec<String>, position: i32) -> planet_role::Model {
+pub fn create_role(permissions: Vec<String>, position: i32) -> planet_role::Model {
     planet_role::Model {
         position,
         permissions,
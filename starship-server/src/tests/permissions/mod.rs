@@ -1 +1,6 @@
-mod has_permission;
+mod ban_expired;
+mod calculate_permissions;
+mod component_overrides;
+pub mod has_permission;
+mod ownership_permission;
+mod update_permissions;
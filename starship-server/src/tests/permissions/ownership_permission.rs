@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::permissions::checks::ownership_permission;
+
+#[cfg(test)]
+#[actix_web::test]
+async fn owner() {
+    let permission = ownership_permission("forum.posts.edit", "user", Some("user"));
+
+    assert_eq!(permission, "forum.posts.edit.own", "owner not given own");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn other_user() {
+    let permission = ownership_permission("forum.posts.edit", "user", Some("other"));
+
+    assert_eq!(
+        permission, "forum.posts.edit.others",
+        "other user not given others"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn logged_out() {
+    let permission = ownership_permission("forum.posts.edit", "user", None);
+
+    assert_eq!(
+        permission, "forum.posts.edit.others",
+        "logged out user not given others"
+    );
+}
@@ -0,0 +1,69 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::permissions::util::update_permissions;
+
+#[cfg(test)]
+#[actix_web::test]
+async fn adds_permission() {
+    let result = update_permissions(vec![], vec!["+planet.change_name".to_string()]);
+
+    assert_eq!(result.unwrap(), vec!["+planet.change_name".to_string()]);
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn replaces_permission() {
+    let result = update_permissions(
+        vec!["+planet.change_name".to_string()],
+        vec!["-planet.change_name".to_string()],
+    );
+
+    assert_eq!(result.unwrap(), vec!["-planet.change_name".to_string()]);
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn falls_back() {
+    let result = update_permissions(
+        vec!["+planet.change_name".to_string()],
+        vec!["*planet.change_name".to_string()],
+    );
+
+    assert!(result.unwrap().is_empty(), "permission was not removed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn rejects_unknown_permission() {
+    let result = update_permissions(vec![], vec!["+planet.chnage_name".to_string()]);
+
+    assert!(result.is_err(), "unknown permission accepted");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn accepts_wildcard() {
+    let result = update_permissions(vec![], vec!["-forum.posts.*".to_string()]);
+
+    assert_eq!(result.unwrap(), vec!["-forum.posts.*".to_string()]);
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn rejects_unknown_wildcard() {
+    let result = update_permissions(vec![], vec!["-nonexistent.*".to_string()]);
+
+    assert!(result.is_err(), "unknown wildcard accepted");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn removes_unknown_permission() {
+    let result = update_permissions(
+        vec!["+planet.chnage_name".to_string()],
+        vec!["*planet.chnage_name".to_string()],
+    );
+
+    assert!(result.unwrap().is_empty(), "permission was not removed");
+}
@@ -0,0 +1,136 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::presence::{Presence, PresenceStatus};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+fn time(minute: u32, second: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 1, 1)
+        .unwrap()
+        .and_hms_opt(12, minute, second)
+        .unwrap()
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn connections() {
+    let presence = Presence::default();
+
+    assert_eq!(
+        presence.status_at("user", time(0, 0)),
+        PresenceStatus::Offline,
+        "unknown user was not offline"
+    );
+
+    presence.connect_at("user".to_string(), time(0, 0));
+    presence.connect_at("user".to_string(), time(0, 0));
+    presence.disconnect_at("user", time(1, 0));
+
+    assert_eq!(
+        presence.status_at("user", time(4, 0)),
+        PresenceStatus::Online,
+        "user with an open connection was not online"
+    );
+    assert_eq!(
+        presence.status_at("user", time(10, 0)),
+        PresenceStatus::Idle,
+        "user that sent nothing over their connection was not idle"
+    );
+
+    presence.activity_at("user", time(10, 0));
+
+    assert_eq!(
+        presence.status_at("user", time(14, 0)),
+        PresenceStatus::Online,
+        "user sending messages over their connection was not online"
+    );
+
+    presence.disconnect_at("user", time(10, 0));
+
+    assert_eq!(
+        presence.status_at("user", time(15, 0)),
+        PresenceStatus::Offline,
+        "user without connections was not offline"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn heartbeats() {
+    let presence = Presence::default();
+
+    presence.heartbeat_at("user".to_string(), false, time(0, 0));
+
+    assert_eq!(
+        presence.status_at("user", time(1, 0)),
+        PresenceStatus::Online,
+        "user was not online after a heartbeat"
+    );
+    assert_eq!(
+        presence.status_at("user", time(2, 0)),
+        PresenceStatus::Offline,
+        "user was still online after heartbeats stopped"
+    );
+
+    presence.heartbeat_at("user".to_string(), true, time(1, 30));
+    presence.heartbeat_at("user".to_string(), true, time(5, 30));
+
+    assert_eq!(
+        presence.status_at("user", time(6, 0)),
+        PresenceStatus::Idle,
+        "user reporting idle heartbeats was not idle"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn prune() {
+    let presence = Presence::default();
+
+    presence.heartbeat_at("gone".to_string(), false, time(0, 0));
+    presence.connect_at("connected".to_string(), time(0, 0));
+    presence.prune_at(time(30, 0));
+
+    presence.heartbeat_at("gone".to_string(), true, time(30, 0));
+
+    assert_eq!(
+        presence.status_at("gone", time(30, 0)),
+        PresenceStatus::Online,
+        "pruned user kept their old activity"
+    );
+    assert_eq!(
+        presence.status_at("connected", time(30, 0)),
+        PresenceStatus::Idle,
+        "connected user was pruned"
+    );
+
+    presence.activity_at("gone", time(31, 0));
+    presence.prune_at(time(40, 0));
+    presence.activity_at("gone", time(40, 0));
+
+    assert_eq!(
+        presence.status_at("gone", time(40, 0)),
+        PresenceStatus::Offline,
+        "activity brought back a pruned user"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn counts() {
+    let presence = Presence::default();
+
+    presence.connect_at("connected".to_string(), time(0, 0));
+    presence.connect_at("connected".to_string(), time(0, 0));
+    presence.connect_at("other".to_string(), time(0, 0));
+    presence.heartbeat_at("heartbeat".to_string(), false, time(0, 0));
+    presence.heartbeat_at("timed out".to_string(), false, time(0, 0));
+    presence.heartbeat_at("heartbeat".to_string(), true, time(9, 0));
+
+    assert_eq!(presence.connections(), 3, "connections were not counted");
+    assert_eq!(
+        presence.online_users_at(time(10, 0)),
+        3,
+        "online users were not counted"
+    );
+}
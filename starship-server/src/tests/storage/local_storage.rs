@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::storage::{LocalStorage, Storage};
+use nanoid::nanoid;
+
+fn temporary_storage() -> LocalStorage {
+    LocalStorage::new(std::env::temp_dir().join(format!("starship-test-{}", nanoid!(8))))
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn round_trip() {
+    let storage = temporary_storage();
+
+    storage
+        .put("tree/file", b"contents".to_vec())
+        .await
+        .expect("put failed");
+
+    let contents = storage.get("tree/file").await.expect("get failed");
+
+    assert_eq!(contents, b"contents", "contents changed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn delete() {
+    let storage = temporary_storage();
+
+    storage
+        .put("tree/file", b"contents".to_vec())
+        .await
+        .expect("put failed");
+    storage.delete("tree/file").await.expect("delete failed");
+
+    assert!(storage.get("tree/file").await.is_err(), "file not deleted");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn delete_missing() {
+    let storage = temporary_storage();
+
+    let result = storage.delete("tree/missing").await;
+
+    assert!(result.is_ok(), "deleting missing file failed");
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn escaping_key() {
+    let storage = temporary_storage();
+
+    let result = storage.put("../escaped", b"contents".to_vec()).await;
+
+    assert!(result.is_err(), "key escaped storage directory");
+}
@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use crate::subscriptions::{Broker, ChangeKind, PlanetEvent};
+use futures_util::StreamExt;
+
+#[cfg(test)]
+#[actix_web::test]
+async fn filters_by_planet() {
+    let broker = Broker::default();
+    let mut stream = Box::pin(broker.subscribe("planet".to_string()));
+
+    broker.publish(PlanetEvent::PlanetUpdated {
+        planet: "other".to_string(),
+    });
+    broker.publish(PlanetEvent::RoleChanged {
+        planet: "planet".to_string(),
+        role: "role".to_string(),
+        change: ChangeKind::Created,
+    });
+
+    assert_eq!(
+        stream.next().await,
+        Some(PlanetEvent::RoleChanged {
+            planet: "planet".to_string(),
+            role: "role".to_string(),
+            change: ChangeKind::Created,
+        }),
+        "event from another planet was received"
+    );
+}
+
+#[cfg(test)]
+#[actix_web::test]
+async fn publish_without_subscribers() {
+    let broker = Broker::default();
+
+    // nobody is listening, so the event is dropped rather than kept for later subscribers
+    broker.publish(PlanetEvent::PlanetUpdated {
+        planet: "planet".to_string(),
+    });
+
+    let mut stream = Box::pin(broker.subscribe("planet".to_string()));
+
+    broker.publish(PlanetEvent::MemberJoined {
+        planet: "planet".to_string(),
+        member: "member".to_string(),
+    });
+
+    assert_eq!(
+        stream.next().await,
+        Some(PlanetEvent::MemberJoined {
+            planet: "planet".to_string(),
+            member: "member".to_string(),
+        }),
+        "event published before subscribing was received"
+    );
+}
@@ -0,0 +1,194 @@
+//! Plain representations of projects that can be serialized, so that projects can be saved and
+//! loaded. Observers and parent references aren't part of a document; they are recreated when a
+//! document is loaded.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::TreeError;
+use crate::project::{Project, Type};
+use crate::styles::stylesheet::StyleLayers;
+use crate::tree::nodes::{ShapeNode, TextNode};
+use crate::tree::page::{Page, Route, Title};
+use crate::tree::{CreatableNode, NodeBase, ValidNode};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProjectDocument {
+    pub name: String,
+    pub version: u16,
+    pub project_type: Type,
+    pub pages: Vec<PageDocument>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PageDocument {
+    pub id: String,
+    pub name: String,
+    pub styles: StyleLayers,
+    pub title: Title,
+    pub route: Vec<Route>,
+    pub children: Vec<NodeDocument>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum NodeDocument {
+    Text {
+        id: String,
+        name: String,
+        styles: StyleLayers,
+        text: String,
+    },
+    Shape {
+        id: String,
+        name: String,
+        styles: StyleLayers,
+        children: Vec<NodeDocument>,
+    },
+}
+
+impl ProjectDocument {
+    /// Creates a document from the current state of a project.
+    #[must_use]
+    pub fn from_project(project: &Project) -> Self {
+        ProjectDocument {
+            name: project.name(),
+            version: project.version(),
+            project_type: project.project_type(),
+            pages: project
+                .pages()
+                .unwrap_or_default()
+                .iter()
+                .map(|page| PageDocument::from_page(&mut page.borrow_mut()))
+                .collect(),
+        }
+    }
+
+    /// Recreates the project described by this document.
+    ///
+    /// # Errors
+    /// This function will return an error if the nodes in the document can't be added to the
+    /// tree, for example if a node's children contain the node itself.
+    pub fn into_project(self) -> Result<Rc<RefCell<Project>>, TreeError> {
+        let project = Project::create(self.name, self.project_type);
+        project.borrow_mut().set_version(self.version);
+
+        for document in self.pages {
+            let page = Page::create(document.name, Rc::downgrade(&project));
+            let mut page_ref = page.borrow_mut();
+
+            page_ref.set_id(document.id);
+            *page_ref.styles() = document.styles;
+            page_ref.title = document.title;
+            page_ref.route = document.route;
+
+            for child in document.children {
+                // nodes are attached before their children are restored, so that every node in
+                // the tree knows which page it belongs to
+                let node = child.create_node();
+                page_ref.add_child(node.clone(), None)?;
+                child.restore_children(&node)?;
+            }
+        }
+
+        Ok(project)
+    }
+}
+
+impl PageDocument {
+    fn from_page(page: &mut Page) -> Self {
+        PageDocument {
+            id: page.id().clone(),
+            name: page.name().clone(),
+            styles: page.styles().clone(),
+            title: page.title.clone(),
+            route: page.route.clone(),
+            children: NodeDocument::from_children(page.get_children()),
+        }
+    }
+}
+
+impl NodeDocument {
+    fn from_children(children: Option<Vec<Rc<RefCell<ValidNode>>>>) -> Vec<NodeDocument> {
+        children
+            .unwrap_or_default()
+            .iter()
+            .map(|child| NodeDocument::from_node(&mut child.borrow_mut()))
+            .collect()
+    }
+
+    fn from_node(node: &mut ValidNode) -> Self {
+        let id = node.id().clone();
+        let name = node.name().clone();
+        let styles = node.styles().clone();
+
+        match node {
+            ValidNode::TextNode(text_node) => NodeDocument::Text {
+                id,
+                name,
+                styles,
+                text: text_node.text.clone(),
+            },
+            ValidNode::ShapeNode(shape_node) => NodeDocument::Shape {
+                id,
+                name,
+                styles,
+                children: NodeDocument::from_children(shape_node.get_children()),
+            },
+        }
+    }
+
+    /// Creates the node described by this document, without any of its children.
+    fn create_node(&self) -> Rc<RefCell<ValidNode>> {
+        let (node, id, name, styles) = match self {
+            NodeDocument::Text {
+                id,
+                name,
+                styles,
+                text,
+            } => {
+                let node = TextNode::create();
+
+                if let ValidNode::TextNode(text_node) = &mut *node.borrow_mut() {
+                    text_node.text = text.clone();
+                }
+
+                (node, id, name, styles)
+            }
+            NodeDocument::Shape {
+                id, name, styles, ..
+            } => (ShapeNode::create(), id, name, styles),
+        };
+
+        {
+            let mut node_ref = node.borrow_mut();
+
+            match &mut *node_ref {
+                ValidNode::TextNode(text_node) => text_node.set_id(id.clone()),
+                ValidNode::ShapeNode(shape_node) => shape_node.set_id(id.clone()),
+            }
+
+            node_ref.set_name(name.clone());
+            *node_ref.styles() = styles.clone();
+        }
+
+        node
+    }
+
+    /// Adds the children described by this document to a node created from it.
+    fn restore_children(self, node: &Rc<RefCell<ValidNode>>) -> Result<(), TreeError> {
+        if let NodeDocument::Shape { children, .. } = self {
+            for child in children {
+                let child_node = child.create_node();
+                node.try_borrow_mut()
+                    .map_err(|_| TreeError::TreeNodeBorrowed)?
+                    .add_child(child_node.clone(), None)?;
+                child.restore_children(&child_node)?;
+            }
+        }
+
+        Ok(())
+    }
+}
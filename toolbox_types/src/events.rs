@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::macros::redef_units;
 
 redef_units!(
@@ -10,7 +12,7 @@ redef_units!(
     }
 );
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Type {
     Integer(i64),
     Float(f64),
@@ -1,3 +1,4 @@
+pub mod document;
 pub mod errors;
 pub mod events;
 mod macros;
@@ -1,12 +1,13 @@
 #![allow(clippy::missing_panics_doc)] // only panic in this code is impossible
 use nanoid::nanoid;
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::rc::{Rc, Weak};
 
 use crate::observers::{Observable, Observer};
 use crate::tree::page::Page;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Type {
     Component,
     Library,
@@ -52,6 +53,10 @@ impl Project {
         self.version
     }
 
+    pub(crate) fn set_version(&mut self, version: u16) {
+        self.version = version;
+    }
+
     #[must_use]
     pub fn project_type(&self) -> Type {
         self.project_type
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use super::types::{Border, Font, Graphic, Layout, Margin, TextAlignment, Transform};
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Stylesheet {
     // layout
     pub margin: StyleOption<Margin>,
@@ -49,7 +51,7 @@ impl Default for Stylesheet {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct StyleLayers {
     pub base: Stylesheet,
     pub hover: StyleOption<Stylesheet>,
@@ -58,7 +60,7 @@ pub struct StyleLayers {
     pub checked: StyleOption<Stylesheet>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum StyleOption<T> {
     Some(T),
     Default,
@@ -1,6 +1,7 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::Display;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Locked {
     None,
     LeftRight,
@@ -9,7 +10,7 @@ pub enum Locked {
     All,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ThemedColor {
     LightWhite,
     White,
@@ -48,26 +49,26 @@ pub enum ThemedColor {
     DarkOrange,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Color {
     Themed { color: ThemedColor, alpha: f32 },
     Rgba { r: u8, g: u8, b: u8, a: f32 },
     Hsla { h: u16, s: u8, v: u8, a: f32 },
 }
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum GradientType {
     Linear(u16),
     Radial,
     Conic,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Gradient {
     pub from: Color,
     pub to: Color,
     pub gradient_type: GradientType,
 }
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Graphic {
     Color(Color),
     Image {
@@ -79,7 +80,7 @@ pub enum Graphic {
     None,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Scale {
     Percent(f64),
     Em(f64),
@@ -88,7 +89,7 @@ pub enum Scale {
     Auto,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Direction {
     TopLeft,
     Top,
@@ -101,7 +102,7 @@ pub enum Direction {
     BottomRight,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum CardinalDirection {
     Top,
     Left,
@@ -109,7 +110,7 @@ pub enum CardinalDirection {
     Bottom,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum FlexDirection {
     RowLeft,
     RowRight,
@@ -117,7 +118,7 @@ pub enum FlexDirection {
     ColumnDown,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Layout {
     None,
     Flex { direction: FlexDirection },
@@ -125,7 +126,7 @@ pub enum Layout {
     GridScale { width: Scale, height: Option<Scale> },
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Margin {
     pub top: Scale,
     pub bottom: Scale,
@@ -134,7 +135,7 @@ pub struct Margin {
     pub locked: Locked,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum FontWeight {
     ExtraLight,
     Light,
@@ -146,7 +147,7 @@ pub enum FontWeight {
     Custom(u16),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Font {
     pub name: StyleString,
     pub weight: FontWeight,
@@ -154,7 +155,7 @@ pub struct Font {
     pub color: Color,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Transform {
     pub size_x: Scale,
     pub size_y: Scale,
@@ -164,7 +165,7 @@ pub struct Transform {
     pub degrees: f64,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Corners {
     pub top_left: Scale,
     pub top_right: Option<Scale>,
@@ -173,21 +174,21 @@ pub struct Corners {
     pub locked: bool,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum BorderStyle {
     Dotted,
     Dashed,
     Straight,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BorderSide {
     pub color: Color,
     pub size: Scale,
     pub style: BorderStyle,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Border {
     pub left: Option<BorderSide>,
     pub right: Option<BorderSide>,
@@ -197,7 +198,7 @@ pub struct Border {
     pub locked: Locked,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum TextAlignment {
     Left,
     Right,
@@ -223,4 +224,20 @@ impl Display for StyleString {
     }
 }
 
+// static strings can't be deserialized, so style strings are always read back as dynamic strings
+impl Serialize for StyleString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            StyleString::Static(s) => serializer.serialize_str(s),
+            StyleString::Dynamic(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StyleString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(StyleString::Dynamic)
+    }
+}
+
 pub enum StyleVec {}
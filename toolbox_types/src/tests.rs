@@ -1,2 +1,4 @@
 #[cfg(test)]
+mod document;
+#[cfg(test)]
 mod nodes;
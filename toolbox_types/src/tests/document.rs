@@ -0,0 +1,58 @@
+use std::rc::Rc;
+
+use crate::document::{NodeDocument, ProjectDocument};
+use crate::project::{Project, Type};
+use crate::tree::nodes::{ShapeNode, TextNode};
+use crate::tree::page::Page;
+use crate::tree::{CreatableNode, NodeBase, RegularNode, ValidNode};
+
+#[test]
+fn round_trip() {
+    let project = Project::create("Test".to_string(), Type::Component);
+    let page = Page::create("Home".to_string(), Rc::downgrade(&project));
+
+    let shape = ShapeNode::create();
+    let text = TextNode::create();
+
+    if let ValidNode::TextNode(text_node) = &mut *text.borrow_mut() {
+        text_node.text = "Hello".to_string();
+    }
+
+    page.borrow_mut()
+        .add_child(shape.clone(), None)
+        .expect("add_child() failed");
+    shape
+        .borrow_mut()
+        .add_child(text.clone(), None)
+        .expect("add_child() failed");
+
+    let document = ProjectDocument::from_project(&project.borrow());
+    let restored = document
+        .clone()
+        .into_project()
+        .expect("into_project() failed");
+
+    assert_eq!(
+        ProjectDocument::from_project(&restored.borrow()),
+        document,
+        "restored project does not match the original"
+    );
+
+    let NodeDocument::Shape { children, .. } = &document.pages[0].children[0] else {
+        panic!("root node is not a shape");
+    };
+
+    assert!(
+        matches!(&children[0], NodeDocument::Text { text, .. } if text == "Hello"),
+        "text node not saved"
+    );
+
+    let restored_page = restored.borrow().pages().unwrap()[0].clone();
+    let restored_shape = restored_page.borrow().get_children().unwrap()[0].clone();
+    let restored_text = restored_shape.borrow().get_children().unwrap()[0].clone();
+
+    assert!(
+        restored_text.borrow().page().is_some(),
+        "nested node not attached to page"
+    );
+}
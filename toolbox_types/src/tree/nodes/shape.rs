@@ -109,6 +109,12 @@ impl CreatableNode for ShapeNode {
     }
 }
 
+impl ShapeNode {
+    pub(crate) fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+}
+
 impl Observable<NodeFeature> for ShapeNode {
     fn register(
         &mut self,
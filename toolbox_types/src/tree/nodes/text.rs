@@ -106,6 +106,12 @@ impl CreatableNode for TextNode {
     }
 }
 
+impl TextNode {
+    pub(crate) fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+}
+
 impl Observable<NodeFeature> for TextNode {
     fn register(
         &mut self,
@@ -1,4 +1,5 @@
 use nanoid::nanoid;
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::rc::{Rc, Weak};
 
@@ -32,12 +33,14 @@ static PAGE_AUTO_STYLES: Stylesheet = Stylesheet {
     text_direction: StyleOption::Unsupported,
 };
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Route {
     Basic { path: String },
     Typed { name: String, value_type: Type },
     IdRef { name: String, id: String },
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Title {
     Basic { content: String },
 }
@@ -97,6 +100,10 @@ impl Page {
         page
     }
 
+    pub(crate) fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
     pub fn project(&self) -> Option<Rc<RefCell<Project>>> {
         self.project.upgrade()
     }